@@ -0,0 +1,101 @@
+// src-tauri/benches/capture_latency.rs
+//! Capture latency with and without concurrent readers.
+//!
+//! Saves clips into a store seeded with a realistic history, once while the
+//! store is otherwise idle and once while several threads keep listing and
+//! searching it, as open windows do. Writes run on the writer thread and reads
+//! on the read pool, so captures never queue behind a read: given a spare core
+//! per reader, both cases report about the same time. On fewer cores the
+//! readers still compete with the writer for CPU time, but not for the database.
+//!
+//! Run with `cargo bench --bench capture_latency`.
+
+use std::{
+    hint::black_box,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use clipcontex_lib::storage::{Clip, ClipOrder, ClipQuery, ClipStore};
+use criterion::{criterion_group, criterion_main, Criterion};
+use tempfile::TempDir;
+
+/// Number of clips in the history before measuring.
+const SEEDED_CLIPS: usize = 5_000;
+
+/// Number of threads reading while captures are measured.
+const READER_THREADS: usize = 4;
+
+fn clip(content: String) -> Clip {
+    Clip::new(
+        content,
+        "Firefox".into(),
+        "Docs - Mozilla Firefox".into(),
+        vec!["#url".into()],
+        false,
+    )
+}
+
+/// Opens a store on disk and fills it with [`SEEDED_CLIPS`] clips.
+fn seeded_store() -> (TempDir, Arc<ClipStore>) {
+    let dir = TempDir::new().expect("Failed to create temporary directory");
+    let store = ClipStore::new(dir.path().join("bench.db")).expect("Failed to open store");
+
+    let clips = (0..SEEDED_CLIPS)
+        .map(|i| clip(format!("https://example.com/page/{i} seeded clip number {i}")))
+        .collect();
+    store.import_clips(clips).expect("Failed to seed store");
+
+    (dir, Arc::new(store))
+}
+
+/// Measures saving new clips into `store`.
+fn bench_capture(c: &mut Criterion, name: &str, store: &ClipStore) {
+    let counter = AtomicUsize::new(0);
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            let n = counter.fetch_add(1, Ordering::Relaxed);
+            black_box(
+                store
+                    .save_or_bump_clip(clip(format!("{name} capture {n}")))
+                    .expect("Failed to save clip"),
+            )
+        })
+    });
+}
+
+fn capture_idle(c: &mut Criterion) {
+    let (_dir, store) = seeded_store();
+    bench_capture(c, "capture/idle", &store);
+}
+
+fn capture_with_concurrent_reads(c: &mut Criterion) {
+    let (_dir, store) = seeded_store();
+    let stop = Arc::new(AtomicBool::new(false));
+    let query = ClipQuery::parse("app:firefox example").expect("Invalid query");
+
+    let readers: Vec<_> = (0..READER_THREADS)
+        .map(|_| {
+            let (store, stop, query) = (store.clone(), stop.clone(), query.clone());
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    black_box(store.list_clips(ClipOrder::Frecency, 500).ok());
+                    black_box(store.search_clips(&query, 200).ok());
+                }
+            })
+        })
+        .collect();
+
+    bench_capture(c, "capture/concurrent_reads", &store);
+
+    stop.store(true, Ordering::Relaxed);
+    for reader in readers {
+        reader.join().expect("Reader thread panicked");
+    }
+}
+
+criterion_group!(benches, capture_idle, capture_with_concurrent_reads);
+criterion_main!(benches);
@@ -0,0 +1,355 @@
+// src-tauri/src/backup.rs
+//! Rotating database backups.
+//!
+//! Backups are snapshots of the clip database taken with
+//! [`ClipStore::snapshot_to`] and stored in `~/.clipcontex/backups` as
+//! `clipcontex-YYYYMMDD-HHMMSS.db`, or `.db.gz` when compressed with gzip. The
+//! timestamp (UTC) in the file name is the backup's creation time.
+//!
+//! Backups are written to a temporary file in the backup directory first, so a
+//! listing never includes a partial backup. Only the newest `keep` backups are
+//! kept; see [`rotate_backups`].
+//!
+//! Restoring a backup first backs up the current database, so a restore can
+//! itself be undone.
+
+// ===== Imports =====
+
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use tempfile::{tempdir_in, NamedTempFile};
+use tracing::{info, warn};
+
+use crate::{config::config_dir, error::AppError, storage::ClipStore};
+
+// ===== Constants =====
+
+/// Prefix of backup file names.
+const FILE_PREFIX: &str = "clipcontex-";
+
+/// Extension of uncompressed backups.
+const PLAIN_EXTENSION: &str = ".db";
+
+/// Extension of gzip-compressed backups.
+const COMPRESSED_EXTENSION: &str = ".db.gz";
+
+/// Format of the timestamp in backup file names.
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+// ===== Domain Types =====
+
+/// A backup file in the backup directory.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BackupInfo {
+    /// File name within the backup directory; identifies the backup.
+    pub file_name: String,
+    /// When the backup was taken.
+    pub created_at: DateTime<Utc>,
+    /// Size of the backup file in bytes.
+    pub size_bytes: u64,
+    /// Whether the backup is gzip-compressed.
+    pub compressed: bool,
+}
+
+// ===== Public API =====
+
+/// Returns the backup directory: `~/.clipcontex/backups`.
+pub fn backup_dir() -> PathBuf {
+    config_dir().join("backups")
+}
+
+/// Backs up the database of `store` into `dir`, creating `dir` if needed.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Backup`] if the snapshot cannot be taken or written,
+/// including when a backup with the same timestamp already exists.
+pub fn create_backup(
+    store: &ClipStore,
+    dir: &Path,
+    compress: bool,
+    now: DateTime<Utc>,
+) -> Result<BackupInfo, AppError> {
+    fs::create_dir_all(dir).map_err(|e| backup_error("create the backup directory", e))?;
+
+    let extension = if compress {
+        COMPRESSED_EXTENSION
+    } else {
+        PLAIN_EXTENSION
+    };
+    let file_name = format!(
+        "{}{}{}",
+        FILE_PREFIX,
+        now.format(TIMESTAMP_FORMAT),
+        extension
+    );
+    let path = dir.join(&file_name);
+    if path.exists() {
+        return Err(AppError::Backup(format!(
+            "Backup {} already exists",
+            file_name
+        )));
+    }
+
+    // `VACUUM INTO` refuses to overwrite files, so it writes into a fresh directory
+    let staging = tempdir_in(dir).map_err(|e| backup_error("create a staging directory", e))?;
+    let snapshot = staging.path().join("snapshot.db");
+    store
+        .snapshot_to(&snapshot)
+        .map_err(|e| AppError::Backup(format!("Failed to snapshot the database: {}", e)))?;
+
+    if compress {
+        let tmp =
+            NamedTempFile::new_in(dir).map_err(|e| backup_error("create a temporary file", e))?;
+        let mut encoder = GzEncoder::new(BufWriter::new(tmp), Compression::default());
+        let mut input = File::open(&snapshot).map_err(|e| backup_error("read the snapshot", e))?;
+        io::copy(&mut input, &mut encoder).map_err(|e| backup_error("compress the snapshot", e))?;
+        let tmp = encoder
+            .finish()
+            .and_then(|mut writer| {
+                writer.flush()?;
+                writer.into_inner().map_err(|e| e.into_error())
+            })
+            .map_err(|e| backup_error("compress the snapshot", e))?;
+        tmp.persist(&path)
+            .map_err(|e| backup_error("save the backup", e.error))?;
+    } else {
+        fs::rename(&snapshot, &path).map_err(|e| backup_error("save the backup", e))?;
+    }
+
+    let info = backup_info(&path)
+        .ok_or_else(|| AppError::Backup(format!("Backup {} was not written", file_name)))?;
+    info!(
+        "Backed up the database to {} ({} bytes)",
+        path.display(),
+        info.size_bytes
+    );
+    Ok(info)
+}
+
+/// Lists the backups in `dir`, newest first.
+///
+/// A missing directory has no backups. Files not named like backups are ignored.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Backup`] if `dir` exists but cannot be read.
+pub fn list_backups(dir: &Path) -> Result<Vec<BackupInfo>, AppError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(backup_error("read the backup directory", e)),
+    };
+
+    let mut backups: Vec<BackupInfo> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| backup_info(&entry.path()))
+        .collect();
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.created_at));
+    Ok(backups)
+}
+
+/// Deletes all but the newest `keep` backups in `dir`. `0` keeps every backup.
+///
+/// Returns the number of backups deleted. Backups that cannot be deleted are
+/// logged and skipped.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Backup`] if `dir` cannot be read.
+pub fn rotate_backups(dir: &Path, keep: u32) -> Result<usize, AppError> {
+    if keep == 0 {
+        return Ok(0);
+    }
+
+    let mut removed = 0;
+    for backup in list_backups(dir)?.into_iter().skip(keep as usize) {
+        match fs::remove_file(dir.join(&backup.file_name)) {
+            Ok(()) => removed += 1,
+            Err(e) => warn!("Failed to delete old backup {}: {}", backup.file_name, e),
+        }
+    }
+    Ok(removed)
+}
+
+/// Restores the backup named `file_name` in `dir` into `store`.
+///
+/// The current database is backed up first (see [`create_backup`]); that
+/// backup is returned.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Backup`] if `file_name` is not a backup in `dir`, if
+/// the current database cannot be backed up, or if the backup cannot be read
+/// or restored. The current database is left untouched in that case.
+pub fn restore_backup(
+    store: &ClipStore,
+    dir: &Path,
+    file_name: &str,
+    compress_safety_backup: bool,
+    now: DateTime<Utc>,
+) -> Result<BackupInfo, AppError> {
+    let backup = list_backups(dir)?
+        .into_iter()
+        .find(|backup| backup.file_name == file_name)
+        .ok_or_else(|| AppError::Backup(format!("No backup named {}", file_name)))?;
+
+    let safety = create_backup(store, dir, compress_safety_backup, now)?;
+
+    let staging = tempdir_in(dir).map_err(|e| backup_error("create a staging directory", e))?;
+    let snapshot = if backup.compressed {
+        let snapshot = staging.path().join("restore.db");
+        let input = File::open(dir.join(&backup.file_name))
+            .map_err(|e| backup_error("read the backup", e))?;
+        let mut decoder = GzDecoder::new(BufReader::new(input));
+        let mut output =
+            File::create(&snapshot).map_err(|e| backup_error("decompress the backup", e))?;
+        io::copy(&mut decoder, &mut output)
+            .map_err(|e| backup_error("decompress the backup", e))?;
+        snapshot
+    } else {
+        dir.join(&backup.file_name)
+    };
+
+    store
+        .restore_from(&snapshot)
+        .map_err(|e| AppError::Backup(format!("Failed to restore {}: {}", file_name, e)))?;
+
+    info!(
+        "Restored backup {} (previous database saved as {})",
+        file_name, safety.file_name
+    );
+    Ok(safety)
+}
+
+// ===== Helper Functions =====
+
+/// Reads the metadata of the backup at `path`, or `None` if it is not a backup.
+fn backup_info(path: &Path) -> Option<BackupInfo> {
+    let file_name = path.file_name()?.to_str()?;
+    let stem = file_name.strip_prefix(FILE_PREFIX)?;
+    let (timestamp, compressed) = match stem.strip_suffix(COMPRESSED_EXTENSION) {
+        Some(timestamp) => (timestamp, true),
+        None => (stem.strip_suffix(PLAIN_EXTENSION)?, false),
+    };
+    let created_at = NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT)
+        .ok()?
+        .and_utc();
+
+    let metadata = fs::metadata(path).ok()?;
+    metadata.is_file().then(|| BackupInfo {
+        file_name: file_name.to_string(),
+        created_at,
+        size_bytes: metadata.len(),
+        compressed,
+    })
+}
+
+/// Builds a [`AppError::Backup`] for a failed file operation.
+fn backup_error(action: &str, e: io::Error) -> AppError {
+    AppError::Backup(format!("Failed to {}: {}", action, e))
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use tempfile::tempdir;
+
+    use crate::storage::Clip;
+
+    fn store_with(contents: &[&str]) -> ClipStore {
+        let store = ClipStore::new(":memory:").unwrap();
+        for content in contents {
+            let clip = Clip::new(
+                content.to_string(),
+                "App".into(),
+                "Win".into(),
+                vec![],
+                false,
+            );
+            store.save_clip(clip).unwrap();
+        }
+        store
+    }
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 1, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_rotation_keeps_newest_backups() {
+        let dir = tempdir().unwrap();
+        let store = store_with(&["a"]);
+        for hour in 0..4 {
+            create_backup(&store, dir.path(), hour % 2 == 0, at(hour)).unwrap();
+        }
+        fs::write(dir.path().join("notes.txt"), "not a backup").unwrap();
+
+        assert_eq!(rotate_backups(dir.path(), 2).unwrap(), 2);
+
+        let backups = list_backups(dir.path()).unwrap();
+        let names: Vec<&str> = backups.iter().map(|b| b.file_name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "clipcontex-20240501-030000.db",
+                "clipcontex-20240501-020000.db.gz"
+            ]
+        );
+        assert!(backups[1].compressed);
+        assert_eq!(backups[0].created_at, at(3));
+        assert!(dir.path().join("notes.txt").exists());
+    }
+
+    #[test]
+    fn test_restore_compressed_backup_keeps_safety_backup() {
+        let dir = tempdir().unwrap();
+        let store = store_with(&["original"]);
+        let backup = create_backup(&store, dir.path(), true, at(1)).unwrap();
+        store
+            .save_clip(Clip::new(
+                "newer".into(),
+                "App".into(),
+                "Win".into(),
+                vec![],
+                false,
+            ))
+            .unwrap();
+
+        let safety = restore_backup(&store, dir.path(), &backup.file_name, false, at(2)).unwrap();
+
+        let clips = store.list_recent_clips(10).unwrap();
+        assert_eq!(clips.len(), 1);
+        assert_eq!(clips[0].content, "original");
+
+        // The safety backup holds the history as it was before the restore
+        restore_backup(
+            &store,
+            dir.path(),
+            &safety.file_name,
+            false,
+            at(2) + Duration::hours(1),
+        )
+        .unwrap();
+        assert_eq!(store.list_recent_clips(10).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_restore_rejects_unknown_names() {
+        let dir = tempdir().unwrap();
+        let store = store_with(&["a"]);
+
+        assert!(restore_backup(&store, dir.path(), "../clipcontex.db", false, at(1)).is_err());
+        assert!(list_backups(dir.path()).unwrap().is_empty());
+    }
+}
@@ -68,6 +68,7 @@ pub struct ClipboardWatcher {}
 pub struct ClipboardWatcherHandle {
     handle: Option<std::thread::JoinHandle<()>>,
     is_running: Arc<AtomicBool>,
+    is_paused: Arc<AtomicBool>,
 }
 
 impl ClipboardWatcher {
@@ -110,11 +111,13 @@ impl ClipboardWatcher {
         F: Fn(ClipboardEvent) + Send + 'static,
     {
         let is_running = Arc::new(AtomicBool::new(true));
+        let is_paused = Arc::new(AtomicBool::new(false));
         let deduplicator = Deduplicator::new(Duration::from_secs(10), 1000);
         let ignore_window = IgnoreWindow::global();
         let app_handle_clone = app_handle.clone();
 
         let thread_is_running = Arc::clone(&is_running);
+        let thread_is_paused = Arc::clone(&is_paused);
 
         let handle = thread::spawn(move || {
             let mut last_content = match read_clipboard_text(&app_handle) {
@@ -134,6 +137,10 @@ impl ClipboardWatcher {
             while thread_is_running.load(Ordering::Relaxed) {
                 thread::sleep(base_sleep);
 
+                if thread_is_paused.load(Ordering::Relaxed) {
+                    continue;
+                }
+
                 let content = match read_clipboard_text(&app_handle_clone) {
                     Ok(c) => c,
                     Err(e) => {
@@ -179,6 +186,7 @@ impl ClipboardWatcher {
         ClipboardWatcherHandle {
             handle: Some(handle),
             is_running,
+            is_paused,
         }
     }
 }
@@ -190,6 +198,19 @@ impl Default for ClipboardWatcher {
 }
 
 impl ClipboardWatcherHandle {
+    /// Pauses clipboard polling until [`resume`](ClipboardWatcherHandle::resume) is called.
+    ///
+    /// Content copied while paused is not lost: if it is still on the clipboard
+    /// after resuming, it is captured by the next poll.
+    pub fn pause(&self) {
+        self.is_paused.store(true, Ordering::Relaxed);
+    }
+
+    /// Resumes clipboard polling after [`pause`](ClipboardWatcherHandle::pause).
+    pub fn resume(&self) {
+        self.is_paused.store(false, Ordering::Relaxed);
+    }
+
     /// Stops the clipboard watcher thread gracefully.
     ///
     /// Waits for the thread to finish. If the thread panicked, the panic payload is logged.
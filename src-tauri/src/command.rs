@@ -4,35 +4,114 @@
 //! This module defines all commands exposed to the frontend via `invoke()`.
 //! Each command delegates to the service layer and may emit real-time events
 //! for UI updates. Errors are converted to user-friendly strings.
+//!
+//! Commands that touch the database run the service call on the blocking thread
+//! pool (see [`blocking`]), so a slow query or key derivation never stalls the
+//! async runtime that also drives clipboard capture.
 
-use std::sync::atomic::Ordering;
-use tauri::{command, AppHandle, Emitter, State};
+use chrono::{DateTime, Utc};
+use std::{collections::HashMap, path::PathBuf, sync::atomic::Ordering};
+use tauri::{command, AppHandle, Emitter, Manager, State};
+use tauri_plugin_clipboard_manager::ClipboardExt;
 use tracing::error;
 use tracing::info;
 
 use crate::{
+    backup::BackupInfo,
     clipboard::watcher::mark_ignore_next_clipboard_update,
     config::Settings,
     error::AppError,
-    service::{clip, settings},
+    export::{ExportFormat, ExportSummary},
+    importer::ImportSource,
+    service::{
+        backup, clip, collection, encryption, expiry, export, import, settings, snippet, stats,
+    },
     state::AppState,
-    storage::Clip,
+    storage::{
+        Clip, ClipCursor, ClipOrder, ClipPage, ClipRevision, ClipSearchResult, ClipStats,
+        Collection, EncryptionStatus, ImportReport, SaveOutcome, StatsInterval, StorageStatus,
+        TagCount,
+    },
 };
 
 // ===== Event Constants =====
 
-/// Emitted when a clip's pinned status changes.
+/// Emitted when a clip is changed in place: pinned or unpinned, edited, reverted
+/// to a revision, given new user tags or a note, or marked as a snippet.
+///
+/// The payload is the updated [`Clip`].
 pub const EVT_CLIP_UPDATED: &str = "clip-updated";
 
-/// Emitted when a clip is deleted.
+/// Emitted when a repeated copy bumps an existing clip instead of adding a new one.
+///
+/// The payload is the bumped [`Clip`], with its updated `copy_count` and `last_copied_at`.
+pub const EVT_CLIP_BUMPED: &str = "clip-bumped";
+
+/// Emitted when a clip is moved to the trash.
 pub const EVT_CLIP_DELETED: &str = "clip-deleted";
 
-/// Emitted when the entire clipboard history is cleared.
+/// Emitted when a bulk operation moves several clips to the trash at once.
+///
+/// The payload is the list of trashed clip IDs.
+pub const EVT_CLIPS_DELETED: &str = "clips-deleted";
+
+/// Emitted when a bulk operation pins, unpins or tags several clips at once.
+///
+/// The payload is the list of affected clip IDs.
+pub const EVT_CLIPS_UPDATED: &str = "clips-updated";
+
+/// Emitted when the entire clipboard history is moved to the trash.
 pub const EVT_HISTORY_CLEARED: &str = "history-cleared";
 
+/// Emitted when clips are restored from the trash.
+///
+/// The payload is the list of restored [`Clip`]s.
+pub const EVT_CLIPS_RESTORED: &str = "clips-restored";
+
+/// Emitted when history has been imported from another clipboard manager.
+///
+/// The payload is the [`ImportReport`].
+pub const EVT_HISTORY_IMPORTED: &str = "history-imported";
+
+/// Emitted when the database has been replaced by a backup.
+///
+/// The payload is the file name of the restored backup.
+pub const EVT_HISTORY_RESTORED: &str = "history-restored";
+
+/// Emitted at startup when the database was corrupt or could not be opened.
+///
+/// The payload is the [`StorageStatus`]. Windows that start listening later
+/// should also call [`storage_status`].
+pub const EVT_STORAGE_DEGRADED: &str = "storage-degraded";
+
+/// Emitted when a collection is created, renamed, or its clips change.
+///
+/// The payload is the updated [`Collection`].
+pub const EVT_COLLECTION_UPDATED: &str = "collection-updated";
+
+/// Emitted when a collection is deleted.
+///
+/// The payload is the collection ID.
+pub const EVT_COLLECTION_DELETED: &str = "collection-deleted";
+
+/// Emitted when a clip is added to or removed from a collection.
+///
+/// The payload is `(collection_id, clip_id, is_member)`.
+pub const EVT_COLLECTION_MEMBERSHIP_CHANGED: &str = "collection-membership-changed";
+
+/// Emitted when a bulk operation adds several clips to a collection at once.
+///
+/// The payload is `(collection_id, clip_ids)`, listing only the newly added clips.
+pub const EVT_COLLECTION_CLIPS_ADDED: &str = "collection-clips-added";
+
 /// Emitted when user settings are successfully updated.
 pub const EVT_SETTINGS_UPDATED: &str = "settings-updated";
 
+/// Emitted when encryption is enabled or the history is locked or unlocked.
+///
+/// The payload is the new [`EncryptionStatus`].
+pub const EVT_LOCK_STATE_CHANGED: &str = "lock-state-changed";
+
 // ===== Commands =====
 
 /// Retrieves the most recent clipboard entries.
@@ -40,16 +119,128 @@ pub const EVT_SETTINGS_UPDATED: &str = "settings-updated";
 /// # Arguments
 ///
 /// - `limit`: Maximum number of clips to return (e.g., 50).
+/// - `query`: Optional filter query (e.g., `app:firefox tag:#url after:yesterday`).
+/// - `order`: Optional sort order, `"recent"` (default) or `"frecency"`.
 ///
 /// # Returns
 ///
-/// A list of [`Clip`] objects ordered from newest to oldest.
+/// A list of [`Clip`] objects in the requested order.
 #[command]
 pub async fn list_recent_clips(
-    app_state: State<'_, AppState>,
+    app_handle: AppHandle,
     limit: i32,
+    query: Option<String>,
+    order: Option<ClipOrder>,
 ) -> Result<Vec<Clip>, String> {
-    ipc(clip::list_recent_clips(app_state.inner(), limit))
+    blocking(&app_handle, move |app_state| {
+        clip::list_recent_clips(
+            app_state,
+            limit,
+            query.as_deref(),
+            order.unwrap_or_default(),
+        )
+    })
+    .await
+}
+
+/// Retrieves one page of clipboard history, for scrolling through histories of any size.
+///
+/// # Arguments
+///
+/// - `query`: Optional filter query (e.g., `app:firefox tag:#url after:yesterday`).
+/// - `cursor`: The previous page's `next_cursor`, or `None` for the first page.
+/// - `page_size`: Maximum number of clips on the page.
+///
+/// # Returns
+///
+/// A [`ClipPage`] with clips ordered from newest to oldest, the cursor for the
+/// next page and the total number of matching clips.
+#[command]
+pub async fn list_clips_page(
+    app_handle: AppHandle,
+    query: Option<String>,
+    cursor: Option<ClipCursor>,
+    page_size: i32,
+) -> Result<ClipPage, String> {
+    blocking(&app_handle, move |app_state| {
+        clip::list_clips_page(app_state, query.as_deref(), cursor.as_ref(), page_size)
+    })
+    .await
+}
+
+/// Searches the whole clipboard history using the full-text index.
+///
+/// # Arguments
+///
+/// - `query`: Free text and filters, e.g. `app:firefox tag:#url after:yesterday "invoice"`.
+/// - `limit`: Maximum number of results to return.
+///
+/// # Returns
+///
+/// A list of [`ClipSearchResult`] ordered from most to least relevant.
+#[command]
+pub async fn search_clips(
+    app_handle: AppHandle,
+    query: String,
+    limit: i32,
+) -> Result<Vec<ClipSearchResult>, String> {
+    blocking(&app_handle, move |app_state| {
+        clip::search_clips(app_state, &query, limit)
+    })
+    .await
+}
+
+/// Lists every tag in use, with the number of clips carrying it.
+///
+/// # Returns
+///
+/// A list of [`TagCount`] ordered from most to least used.
+#[command]
+pub async fn list_tags(app_handle: AppHandle) -> Result<Vec<TagCount>, String> {
+    blocking(&app_handle, clip::list_tags).await
+}
+
+/// Computes usage statistics for the clips created within a time range.
+///
+/// # Arguments
+///
+/// - `from`: Start of the range; the beginning of the history if omitted.
+/// - `to`: End of the range (exclusive); now if omitted.
+/// - `interval`: Optional period length for `by_period`, `"day"` (default) or `"hour"`.
+///
+/// # Returns
+///
+/// A [`ClipStats`] report with clips per app, tag and period, the most repeated
+/// contents, sizes and pinned counts.
+#[command]
+pub async fn clip_stats(
+    app_handle: AppHandle,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    interval: Option<StatsInterval>,
+) -> Result<ClipStats, String> {
+    blocking(&app_handle, move |app_state| {
+        stats::clip_stats(app_state, from, to, interval.unwrap_or_default())
+    })
+    .await
+}
+
+/// Retrieves the most recent clipboard entries carrying all of the given tags.
+///
+/// # Arguments
+///
+/// - `tags`: Tag names as stored (e.g., `["#url", "#browser"]`).
+/// - `limit`: Maximum number of clips to return.
+#[command]
+pub async fn list_clips_by_tags(
+    app_handle: AppHandle,
+    tags: Vec<String>,
+    limit: i32,
+) -> Result<Vec<Clip>, String> {
+    blocking(&app_handle, move |app_state| {
+        clip::list_clips_by_tags(app_state, &tags, limit)
+    })
+    .await
 }
 
 /// Toggles the pinned status of a clipboard entry.
@@ -63,40 +254,275 @@ pub async fn list_recent_clips(
 ///
 /// # Events
 ///
-/// Emits [`EVT_CLIP_UPDATED`] with `(id, is_pinned)` on success.
+/// Emits [`EVT_CLIP_UPDATED`] with the updated clip on success.
 #[command]
 pub async fn toggle_pin_status(
     app_handle: AppHandle,
-    app_state: State<'_, AppState>,
     id: i32,
     is_pinned: bool,
 ) -> Result<(), String> {
-    ipc(clip::toggle_pin_status(app_state.inner(), id, is_pinned))?;
-    if let Err(e) = app_handle.emit(EVT_CLIP_UPDATED, &(id, is_pinned)) {
-        error!(
-            "Failed to emit pin_clip event '{}': {}",
-            EVT_CLIP_UPDATED, e
-        );
-    }
+    let clip = blocking(&app_handle, move |app_state| {
+        clip::toggle_pin_status(app_state, id, is_pinned)
+    })
+    .await?;
+    emit_updated(&app_handle, &clip);
     Ok(())
 }
 
-/// Deletes a clipboard entry by ID.
+/// Pins or unpins many clipboard entries in one transaction.
 ///
 /// # Arguments
 ///
-/// - `id`: Database ID of the clip to remove.
+/// - `ids`: Database IDs of the clips; mutually exclusive with `query`.
+/// - `query`: A query in the structured query language selecting the clips.
+/// - `is_pinned`: Desired pin state.
 ///
 /// # Events
 ///
-/// Emits [`EVT_CLIP_DELETED`] with the `id` on success.
+/// Emits [`EVT_CLIPS_UPDATED`] with the affected IDs, if any.
 #[command]
-pub async fn remove_clip(
+pub async fn set_clips_pinned(
+    app_handle: AppHandle,
+    ids: Option<Vec<i32>>,
+    query: Option<String>,
+    is_pinned: bool,
+) -> Result<Vec<i32>, String> {
+    let updated = blocking(&app_handle, move |app_state| {
+        clip::set_clips_pinned(app_state, ids, query.as_deref(), is_pinned)
+    })
+    .await?;
+    emit_clip_ids(&app_handle, EVT_CLIPS_UPDATED, &updated);
+    Ok(updated)
+}
+
+/// Replaces the content of a clipboard entry, keeping the old content as a revision.
+///
+/// Auto tags are regenerated from the new content; user tags and the note are kept.
+///
+/// # Arguments
+///
+/// - `id`: Database ID of the clip.
+/// - `content`: The new content; must not be blank.
+///
+/// # Events
+///
+/// Emits [`EVT_CLIP_UPDATED`] with the updated clip on success.
+#[command]
+pub async fn update_clip_content(
     app_handle: AppHandle,
-    app_state: State<'_, AppState>,
     id: i32,
-) -> Result<(), String> {
-    ipc(clip::remove_clip(app_state.inner(), id))?;
+    content: String,
+) -> Result<Clip, String> {
+    let clip = blocking(&app_handle, move |app_state| {
+        clip::update_clip_content(app_state, id, content)
+    })
+    .await?;
+    emit_updated(&app_handle, &clip);
+    Ok(clip)
+}
+
+/// Lists the earlier versions of an edited clip, most recently replaced first.
+#[command]
+pub async fn list_clip_revisions(
+    app_handle: AppHandle,
+    id: i32,
+) -> Result<Vec<ClipRevision>, String> {
+    blocking(&app_handle, move |app_state| {
+        clip::list_clip_revisions(app_state, id)
+    })
+    .await
+}
+
+/// Restores a clip's content from one of its revisions.
+///
+/// The content being replaced is kept as a new revision.
+///
+/// # Events
+///
+/// Emits [`EVT_CLIP_UPDATED`] with the updated clip on success.
+#[command]
+pub async fn revert_clip_revision(app_handle: AppHandle, revision_id: i64) -> Result<Clip, String> {
+    let clip = blocking(&app_handle, move |app_state| {
+        clip::revert_clip_revision(app_state, revision_id)
+    })
+    .await?;
+    emit_updated(&app_handle, &clip);
+    Ok(clip)
+}
+
+/// Adds a user tag to a clipboard entry.
+///
+/// User tags are kept apart from auto tags, so refreshing auto tags never removes them.
+///
+/// # Events
+///
+/// Emits [`EVT_CLIP_UPDATED`] with the updated clip on success.
+#[command]
+pub async fn add_clip_tag(app_handle: AppHandle, id: i32, tag: String) -> Result<Clip, String> {
+    let clip = blocking(&app_handle, move |app_state| {
+        clip::add_user_tag(app_state, id, &tag)
+    })
+    .await?;
+    emit_updated(&app_handle, &clip);
+    Ok(clip)
+}
+
+/// Adds a user tag to many clipboard entries in one transaction.
+///
+/// # Arguments
+///
+/// - `ids`: Database IDs of the clips; mutually exclusive with `query`.
+/// - `query`: A query in the structured query language selecting the clips.
+/// - `tag`: The tag to add; must not be blank.
+///
+/// # Events
+///
+/// Emits [`EVT_CLIPS_UPDATED`] with the affected IDs, if any.
+#[command]
+pub async fn add_clips_tag(
+    app_handle: AppHandle,
+    ids: Option<Vec<i32>>,
+    query: Option<String>,
+    tag: String,
+) -> Result<Vec<i32>, String> {
+    let updated = blocking(&app_handle, move |app_state| {
+        clip::add_user_tag_to_clips(app_state, ids, query.as_deref(), &tag)
+    })
+    .await?;
+    emit_clip_ids(&app_handle, EVT_CLIPS_UPDATED, &updated);
+    Ok(updated)
+}
+
+/// Removes a user tag from a clipboard entry. Auto tags cannot be removed.
+///
+/// # Events
+///
+/// Emits [`EVT_CLIP_UPDATED`] with the updated clip on success.
+#[command]
+pub async fn remove_clip_tag(app_handle: AppHandle, id: i32, tag: String) -> Result<Clip, String> {
+    let clip = blocking(&app_handle, move |app_state| {
+        clip::remove_user_tag(app_state, id, &tag)
+    })
+    .await?;
+    emit_updated(&app_handle, &clip);
+    Ok(clip)
+}
+
+/// Sets the note on a clipboard entry.
+///
+/// # Arguments
+///
+/// - `id`: Database ID of the clip.
+/// - `note`: The new note; `null` or blank clears it.
+///
+/// # Events
+///
+/// Emits [`EVT_CLIP_UPDATED`] with the updated clip on success.
+#[command]
+pub async fn set_clip_note(
+    app_handle: AppHandle,
+    id: i32,
+    note: Option<String>,
+) -> Result<Clip, String> {
+    let clip = blocking(&app_handle, move |app_state| {
+        clip::set_clip_note(app_state, id, note.as_deref())
+    })
+    .await?;
+    emit_updated(&app_handle, &clip);
+    Ok(clip)
+}
+
+/// Marks a clipboard entry as a snippet, or back as a plain clip.
+///
+/// Snippets are templates whose placeholders are expanded when pasted with
+/// [`use_snippet`]. A clip can only become a snippet if its content is a valid template.
+///
+/// # Arguments
+///
+/// - `id`: Database ID of the clip.
+/// - `is_snippet`: Desired snippet state.
+///
+/// # Events
+///
+/// Emits [`EVT_CLIP_UPDATED`] with the updated clip on success.
+#[command]
+pub async fn set_snippet(app_handle: AppHandle, id: i32, is_snippet: bool) -> Result<Clip, String> {
+    let clip = blocking(&app_handle, move |app_state| {
+        snippet::set_snippet(app_state, id, is_snippet)
+    })
+    .await?;
+    emit_updated(&app_handle, &clip);
+    Ok(clip)
+}
+
+/// Makes a clipboard entry self-destruct.
+///
+/// Expired clips are deleted for good, skipping the trash, and
+/// [`EVT_CLIP_DELETED`] is emitted with their `id`.
+///
+/// # Arguments
+///
+/// - `id`: Database ID of the clip.
+/// - `ttl_seconds`: Seconds from now until the clip expires, or `None` to keep it.
+/// - `delete_after_use`: Whether the clip is deleted the next time it is pasted.
+///
+/// # Events
+///
+/// Emits [`EVT_CLIP_UPDATED`] with the updated clip on success.
+#[command]
+pub async fn set_clip_expiry(
+    app_handle: AppHandle,
+    id: i32,
+    ttl_seconds: Option<u32>,
+    delete_after_use: bool,
+) -> Result<Clip, String> {
+    let clip = blocking(&app_handle, move |app_state| {
+        expiry::set_clip_expiry(app_state, id, ttl_seconds, delete_after_use)
+    })
+    .await?;
+    emit_updated(&app_handle, &clip);
+    Ok(clip)
+}
+
+/// Checks snippet template syntax, e.g. while the user edits a snippet.
+///
+/// # Returns
+///
+/// The labels of the template's `{input:<label>}` placeholders, or a message
+/// describing the first syntax error.
+#[command]
+pub async fn validate_snippet(content: String) -> Result<Vec<String>, String> {
+    ipc(snippet::validate_template(&content))
+}
+
+/// Lists the values the user must type in before a snippet can be pasted.
+///
+/// # Arguments
+///
+/// - `id`: Database ID of the snippet.
+#[command]
+pub async fn snippet_inputs(app_handle: AppHandle, id: i32) -> Result<Vec<String>, String> {
+    blocking(&app_handle, move |app_state| {
+        snippet::snippet_inputs(app_state, id)
+    })
+    .await
+}
+
+/// Moves a clipboard entry to the trash by ID.
+///
+/// # Arguments
+///
+/// - `id`: Database ID of the clip to remove.
+///
+/// # Events
+///
+/// Emits [`EVT_CLIP_DELETED`] with the `id` on success.
+#[command]
+pub async fn remove_clip(app_handle: AppHandle, id: i32) -> Result<(), String> {
+    blocking(&app_handle, move |app_state| {
+        clip::remove_clip(app_state, id)
+    })
+    .await?;
 
     if let Err(e) = app_handle.emit(EVT_CLIP_DELETED, &id) {
         error!(
@@ -108,19 +534,42 @@ pub async fn remove_clip(
     Ok(())
 }
 
+/// Moves many clipboard entries to the trash in one transaction.
+///
+/// The clips form a single deletion batch, so [`undo_delete`] restores them together.
+///
+/// # Arguments
+///
+/// - `ids`: Database IDs of the clips; mutually exclusive with `query`.
+/// - `query`: A query in the structured query language selecting the clips.
+///
+/// # Events
+///
+/// Emits [`EVT_CLIPS_DELETED`] with the trashed IDs, if any.
+#[command]
+pub async fn remove_clips(
+    app_handle: AppHandle,
+    ids: Option<Vec<i32>>,
+    query: Option<String>,
+) -> Result<Vec<i32>, String> {
+    let removed = blocking(&app_handle, move |app_state| {
+        clip::remove_clips(app_state, ids, query.as_deref())
+    })
+    .await?;
+    emit_clip_ids(&app_handle, EVT_CLIPS_DELETED, &removed);
+    Ok(removed)
+}
+
 /// Clears all clipboard history.
 ///
-/// Cannot be undone. Pinned clips are also removed.
+/// Clips, pinned ones included, are moved to the trash; [`undo_delete`] brings them back.
 ///
 /// # Events
 ///
 /// Emits [`EVT_HISTORY_CLEARED`] on success.
 #[command]
-pub async fn clear_clip_history(
-    app_handle: AppHandle,
-    app_state: State<'_, AppState>,
-) -> Result<(), String> {
-    ipc(clip::clear_clip_history(app_state.inner()))?;
+pub async fn clear_clip_history(app_handle: AppHandle) -> Result<(), String> {
+    blocking(&app_handle, clip::clear_clip_history).await?;
 
     if let Err(e) = app_handle.emit(EVT_HISTORY_CLEARED, ()) {
         error!(
@@ -132,6 +581,378 @@ pub async fn clear_clip_history(
     Ok(())
 }
 
+/// Lists trashed clips, most recently deleted first.
+///
+/// # Arguments
+///
+/// - `limit`: Maximum number of clips to return.
+#[command]
+pub async fn list_trash(app_handle: AppHandle, limit: i32) -> Result<Vec<Clip>, String> {
+    blocking(&app_handle, move |app_state| {
+        clip::list_trash(app_state, limit)
+    })
+    .await
+}
+
+/// Restores a trashed clip by ID.
+///
+/// # Events
+///
+/// Emits [`EVT_CLIPS_RESTORED`] with the restored clip on success.
+#[command]
+pub async fn restore_clip(app_handle: AppHandle, id: i32) -> Result<Clip, String> {
+    let clip = blocking(&app_handle, move |app_state| {
+        clip::restore_clip(app_state, id)
+    })
+    .await?;
+    emit_restored(&app_handle, std::slice::from_ref(&clip));
+    Ok(clip)
+}
+
+/// Restores every trashed clip.
+///
+/// # Events
+///
+/// Emits [`EVT_CLIPS_RESTORED`] with the restored clips on success.
+#[command]
+pub async fn restore_all(app_handle: AppHandle) -> Result<Vec<Clip>, String> {
+    let clips = blocking(&app_handle, clip::restore_all).await?;
+    emit_restored(&app_handle, &clips);
+    Ok(clips)
+}
+
+/// Reverses the most recent deletion or history clear.
+///
+/// # Returns
+///
+/// The restored clips; empty if there was nothing to undo.
+///
+/// # Events
+///
+/// Emits [`EVT_CLIPS_RESTORED`] with the restored clips, if any.
+#[command]
+pub async fn undo_delete(app_handle: AppHandle) -> Result<Vec<Clip>, String> {
+    let clips = blocking(&app_handle, clip::undo_delete).await?;
+    emit_restored(&app_handle, &clips);
+    Ok(clips)
+}
+
+/// Permanently deletes every trashed clip.
+///
+/// # Returns
+///
+/// The number of clips deleted.
+#[command]
+pub async fn empty_trash(app_handle: AppHandle) -> Result<usize, String> {
+    blocking(&app_handle, clip::empty_trash).await
+}
+
+/// Lists every collection, ordered by name.
+#[command]
+pub async fn list_collections(app_handle: AppHandle) -> Result<Vec<Collection>, String> {
+    blocking(&app_handle, collection::list_collections).await
+}
+
+/// Creates an empty collection.
+///
+/// # Arguments
+///
+/// - `name`: Collection name; must not be blank or match another collection, ignoring case.
+///
+/// # Events
+///
+/// Emits [`EVT_COLLECTION_UPDATED`] with the new collection on success.
+#[command]
+pub async fn create_collection(app_handle: AppHandle, name: String) -> Result<Collection, String> {
+    let created = blocking(&app_handle, move |app_state| {
+        collection::create_collection(app_state, &name)
+    })
+    .await?;
+    emit_collection_updated(&app_handle, &created);
+    Ok(created)
+}
+
+/// Renames a collection.
+///
+/// # Events
+///
+/// Emits [`EVT_COLLECTION_UPDATED`] with the renamed collection on success.
+#[command]
+pub async fn rename_collection(
+    app_handle: AppHandle,
+    id: i32,
+    name: String,
+) -> Result<Collection, String> {
+    let renamed = blocking(&app_handle, move |app_state| {
+        collection::rename_collection(app_state, id, &name)
+    })
+    .await?;
+    emit_collection_updated(&app_handle, &renamed);
+    Ok(renamed)
+}
+
+/// Deletes a collection. Its clips stay in the history.
+///
+/// # Events
+///
+/// Emits [`EVT_COLLECTION_DELETED`] with the `id` on success.
+#[command]
+pub async fn delete_collection(app_handle: AppHandle, id: i32) -> Result<(), String> {
+    blocking(&app_handle, move |app_state| {
+        collection::delete_collection(app_state, id)
+    })
+    .await?;
+
+    if let Err(e) = app_handle.emit(EVT_COLLECTION_DELETED, &id) {
+        error!(
+            "Failed to emit delete_collection event '{}': {}",
+            EVT_COLLECTION_DELETED, e
+        );
+    }
+
+    Ok(())
+}
+
+/// Adds a clip to the end of a collection.
+///
+/// Clips in a collection are excluded from automatic cleanup.
+///
+/// # Events
+///
+/// Emits [`EVT_COLLECTION_MEMBERSHIP_CHANGED`] with `(collection_id, clip_id, true)`,
+/// then [`EVT_COLLECTION_UPDATED`] with the updated collection, on success.
+#[command]
+pub async fn add_clip_to_collection(
+    app_handle: AppHandle,
+    collection_id: i32,
+    clip_id: i32,
+) -> Result<Collection, String> {
+    let updated = blocking(&app_handle, move |app_state| {
+        collection::add_clip_to_collection(app_state, collection_id, clip_id)
+    })
+    .await?;
+    emit_membership_changed(&app_handle, collection_id, clip_id, true);
+    emit_collection_updated(&app_handle, &updated);
+    Ok(updated)
+}
+
+/// Appends many clips to the end of a collection in one transaction.
+///
+/// Clips already in the collection keep their position.
+///
+/// # Arguments
+///
+/// - `collection_id`: Database ID of the collection.
+/// - `ids`: Database IDs of the clips; mutually exclusive with `query`.
+/// - `query`: A query in the structured query language selecting the clips.
+///
+/// # Events
+///
+/// Emits [`EVT_COLLECTION_CLIPS_ADDED`] with `(collection_id, clip_ids)`, then
+/// [`EVT_COLLECTION_UPDATED`] with the updated collection, if any clip was added.
+#[command]
+pub async fn add_clips_to_collection(
+    app_handle: AppHandle,
+    collection_id: i32,
+    ids: Option<Vec<i32>>,
+    query: Option<String>,
+) -> Result<Collection, String> {
+    let (updated, added) = blocking(&app_handle, move |app_state| {
+        collection::add_clips_to_collection(app_state, collection_id, ids, query.as_deref())
+    })
+    .await?;
+
+    if !added.is_empty() {
+        if let Err(e) = app_handle.emit(EVT_COLLECTION_CLIPS_ADDED, &(collection_id, &added)) {
+            error!(
+                "Failed to emit collection membership event '{}': {}",
+                EVT_COLLECTION_CLIPS_ADDED, e
+            );
+        }
+        emit_collection_updated(&app_handle, &updated);
+    }
+    Ok(updated)
+}
+
+/// Removes a clip from a collection. The clip stays in the history.
+///
+/// # Events
+///
+/// Emits [`EVT_COLLECTION_MEMBERSHIP_CHANGED`] with `(collection_id, clip_id, false)`,
+/// then [`EVT_COLLECTION_UPDATED`] with the updated collection, on success.
+#[command]
+pub async fn remove_clip_from_collection(
+    app_handle: AppHandle,
+    collection_id: i32,
+    clip_id: i32,
+) -> Result<Collection, String> {
+    let updated = blocking(&app_handle, move |app_state| {
+        collection::remove_clip_from_collection(app_state, collection_id, clip_id)
+    })
+    .await?;
+    emit_membership_changed(&app_handle, collection_id, clip_id, false);
+    emit_collection_updated(&app_handle, &updated);
+    Ok(updated)
+}
+
+/// Moves a clip within a collection.
+///
+/// # Arguments
+///
+/// - `collection_id`: Database ID of the collection.
+/// - `clip_id`: Database ID of a clip in the collection.
+/// - `index`: New zero-based position; past the end moves the clip last.
+///
+/// # Events
+///
+/// Emits [`EVT_COLLECTION_UPDATED`] with the updated collection on success.
+#[command]
+pub async fn move_clip_in_collection(
+    app_handle: AppHandle,
+    collection_id: i32,
+    clip_id: i32,
+    index: usize,
+) -> Result<Collection, String> {
+    let updated = blocking(&app_handle, move |app_state| {
+        collection::move_clip_in_collection(app_state, collection_id, clip_id, index)
+    })
+    .await?;
+    emit_collection_updated(&app_handle, &updated);
+    Ok(updated)
+}
+
+/// Lists the clips in a collection, in collection order.
+#[command]
+pub async fn list_collection_clips(
+    app_handle: AppHandle,
+    collection_id: i32,
+) -> Result<Vec<Clip>, String> {
+    blocking(&app_handle, move |app_state| {
+        collection::list_collection_clips(app_state, collection_id)
+    })
+    .await
+}
+
+/// Exports clipboard history to a file.
+///
+/// # Arguments
+///
+/// - `path`: Destination file; replaced atomically if it exists.
+/// - `format`: `"json"`, `"csv"` or `"markdown"`.
+/// - `query`: Optional filter query selecting the clips to export (all clips if omitted).
+///
+/// # Returns
+///
+/// An [`ExportSummary`] with the number of clips written.
+#[command]
+pub async fn export_clips(
+    app_handle: AppHandle,
+    path: PathBuf,
+    format: ExportFormat,
+    query: Option<String>,
+) -> Result<ExportSummary, String> {
+    blocking(&app_handle, move |app_state| {
+        export::export_clips(app_state, &path, format, query.as_deref())
+    })
+    .await
+}
+
+/// Imports history from another clipboard manager or a ClipContex JSON export.
+///
+/// # Arguments
+///
+/// - `source`: `"clipcontex"`, `"copyq"`, `"klipper"`, `"clipboardIndicator"` or `"gpaste"`.
+/// - `path`: History file to read; the source's default location if omitted.
+///
+/// # Returns
+///
+/// An [`ImportReport`] with the number of imported, skipped and failed entries.
+///
+/// # Events
+///
+/// Emits [`EVT_HISTORY_IMPORTED`] with the report on success.
+#[command]
+pub async fn import_history(
+    app_handle: AppHandle,
+    source: ImportSource,
+    path: Option<PathBuf>,
+) -> Result<ImportReport, String> {
+    let report = blocking(&app_handle, move |app_state| {
+        import::import_history(app_state, source, path.as_deref())
+    })
+    .await?;
+
+    if let Err(e) = app_handle.emit(EVT_HISTORY_IMPORTED, &report) {
+        error!(
+            "Failed to emit import_history event '{}': {}",
+            EVT_HISTORY_IMPORTED, e
+        );
+    }
+
+    Ok(report)
+}
+
+/// Lists the database backups, newest first.
+#[command]
+pub async fn list_backups() -> Result<Vec<BackupInfo>, String> {
+    ipc(backup::list_backups())
+}
+
+/// Backs up the database now, rotating out the oldest backups.
+///
+/// # Returns
+///
+/// The new backup.
+#[command]
+pub async fn create_backup(app_handle: AppHandle) -> Result<BackupInfo, String> {
+    blocking(&app_handle, backup::create_backup).await
+}
+
+/// Replaces the database with a backup, after backing up the current one.
+///
+/// # Arguments
+///
+/// - `file_name`: The backup's [`BackupInfo::file_name`].
+///
+/// # Returns
+///
+/// The backup of the database as it was before the restore.
+///
+/// # Events
+///
+/// Emits [`EVT_HISTORY_RESTORED`] with `file_name`, then [`EVT_LOCK_STATE_CHANGED`]
+/// (the restored history is locked if it is encrypted).
+#[command]
+pub async fn restore_backup(
+    app_handle: AppHandle,
+    app_state: State<'_, AppState>,
+    file_name: String,
+) -> Result<BackupInfo, String> {
+    let previous = {
+        let file_name = file_name.clone();
+        blocking(&app_handle, move |app_state| {
+            backup::restore_backup(app_state, &file_name)
+        })
+        .await?
+    };
+
+    if let Err(e) = app_handle.emit(EVT_HISTORY_RESTORED, &file_name) {
+        error!(
+            "Failed to emit restore_backup event '{}': {}",
+            EVT_HISTORY_RESTORED, e
+        );
+    }
+    emit_lock_state(&app_handle, encryption::encryption_status(app_state.inner()));
+
+    Ok(previous)
+}
+
+/// Returns the health of the database, as determined at startup.
+#[command]
+pub async fn storage_status(app_state: State<'_, AppState>) -> Result<StorageStatus, String> {
+    Ok(app_state.storage_status.as_ref().clone())
+}
+
 /// Instructs the clipboard watcher to ignore the next update with this content.
 ///
 /// Used to prevent self-triggering when the app itself writes to the clipboard
@@ -141,6 +962,162 @@ pub async fn ignore_next_clip(content: String) {
     mark_ignore_next_clipboard_update(content);
 }
 
+/// Records that a clip is being pasted and ignores the resulting clipboard update.
+///
+/// Call this right before writing the clip to the clipboard; it replaces
+/// [`ignore_next_clip`] for pastes from the history. The use is recorded for
+/// frecency ranking. A clip marked with `delete_after_use` is deleted for good
+/// and [`EVT_CLIP_DELETED`] is emitted with its `id`.
+///
+/// # Arguments
+///
+/// - `id`: The clip being pasted.
+///
+/// # Returns
+///
+/// The updated [`Clip`].
+#[command]
+pub async fn use_clip(app_handle: AppHandle, id: i32) -> Result<Clip, String> {
+    let (clip, content) = blocking(&app_handle, move |app_state| {
+        // `clip.content` may be a preview; the clipboard receives the full body.
+        // Load it first, since recording the use may delete the clip.
+        let content = clip::get_clip_content(app_state, id)?;
+        let clip = clip::use_clip(app_state, id)?;
+        Ok((clip, content))
+    })
+    .await?;
+    mark_ignore_next_clipboard_update(content);
+    emit_if_deleted_after_use(&app_handle, &clip);
+    Ok(clip)
+}
+
+/// Expands a snippet for pasting and ignores the resulting clipboard update.
+///
+/// Like [`use_clip`], call this right before writing to the clipboard; the use is
+/// recorded for frecency ranking, and a snippet marked with `delete_after_use` is
+/// deleted. `{clipboard}` expands to the text on the
+/// clipboard now and `{app}` to the app the quick picker was opened over.
+///
+/// # Arguments
+///
+/// - `id`: The snippet being pasted.
+/// - `inputs`: Values for the snippet's `{input:<label>}` placeholders, keyed by label.
+///
+/// # Returns
+///
+/// The expanded text to write to the clipboard.
+#[command]
+pub async fn use_snippet(
+    app_handle: AppHandle,
+    id: i32,
+    inputs: HashMap<String, String>,
+) -> Result<String, String> {
+    let clipboard = app_handle.clipboard().read_text().unwrap_or_default();
+    let (clip, expanded) = blocking(&app_handle, move |app_state| {
+        snippet::use_snippet(app_state, id, clipboard, inputs)
+    })
+    .await?;
+    mark_ignore_next_clipboard_update(expanded.clone());
+    emit_if_deleted_after_use(&app_handle, &clip);
+    Ok(expanded)
+}
+
+/// Returns the full content of a clip.
+///
+/// Listings and events carry at most a preview of long clips (`is_truncated`
+/// is set); call this to load the whole body, e.g. before pasting.
+///
+/// # Arguments
+///
+/// - `id`: The clip to load.
+#[command]
+pub async fn get_clip_content(app_handle: AppHandle, id: i32) -> Result<String, String> {
+    blocking(&app_handle, move |app_state| {
+        clip::get_clip_content(app_state, id)
+    })
+    .await
+}
+
+/// Returns whether encryption is enabled and whether the history is locked.
+#[command]
+pub async fn encryption_status(
+    app_state: State<'_, AppState>,
+) -> Result<EncryptionStatus, String> {
+    Ok(encryption::encryption_status(app_state.inner()))
+}
+
+/// Enables encryption of clip content and window titles at rest.
+///
+/// Existing clips are encrypted in place. The history stays unlocked.
+///
+/// # Arguments
+///
+/// - `passphrase`: The passphrase the encryption key is derived from. It cannot be
+///   recovered; forgetting it makes the history unreadable.
+///
+/// Emits [`EVT_LOCK_STATE_CHANGED`] on success.
+#[command]
+pub async fn enable_encryption(
+    app_handle: AppHandle,
+    passphrase: String,
+) -> Result<EncryptionStatus, String> {
+    let status = blocking(&app_handle, move |app_state| {
+        encryption::enable_encryption(app_state, &passphrase)
+    })
+    .await?;
+    emit_lock_state(&app_handle, status);
+    Ok(status)
+}
+
+/// Unlocks the encrypted history and saves clips captured while it was locked.
+///
+/// # Arguments
+///
+/// - `passphrase`: The passphrase given when encryption was enabled.
+///
+/// Emits `clip-added` or [`EVT_CLIP_BUMPED`] for each queued clip, then
+/// [`EVT_LOCK_STATE_CHANGED`].
+#[command]
+pub async fn unlock_history(
+    app_handle: AppHandle,
+    app_state: State<'_, AppState>,
+    passphrase: String,
+) -> Result<EncryptionStatus, String> {
+    let outcomes = blocking(&app_handle, move |app_state| {
+        encryption::unlock(app_state, &passphrase)
+    })
+    .await?;
+
+    for outcome in outcomes {
+        let (event, clip) = match outcome {
+            SaveOutcome::Inserted(clip) => ("clip-added", clip),
+            SaveOutcome::Bumped(clip) => (EVT_CLIP_BUMPED, clip),
+        };
+        if let Err(e) = app_handle.emit(event, &clip) {
+            error!("Failed to emit queued clip event '{}': {}", event, e);
+        }
+    }
+
+    let status = encryption::encryption_status(app_state.inner());
+    emit_lock_state(&app_handle, status);
+    Ok(status)
+}
+
+/// Locks the encrypted history, forgetting the key until the next unlock.
+///
+/// While locked, captured clips are queued or discarded per the `lockedCapture` setting.
+///
+/// Emits [`EVT_LOCK_STATE_CHANGED`] on success.
+#[command]
+pub async fn lock_history(
+    app_handle: AppHandle,
+    app_state: State<'_, AppState>,
+) -> Result<EncryptionStatus, String> {
+    let status = ipc(encryption::lock(app_state.inner()))?;
+    emit_lock_state(&app_handle, status);
+    Ok(status)
+}
+
 /// Loads current user settings from disk.
 ///
 /// Falls back to defaults if config is missing or invalid.
@@ -220,6 +1197,102 @@ pub async fn set_dragging(app_state: State<'_, AppState>, is_dragging: bool) ->
 
 // ===== Helper Functions =====
 
+/// Emits [`EVT_LOCK_STATE_CHANGED`] with the given status.
+fn emit_lock_state(app_handle: &AppHandle, status: EncryptionStatus) {
+    if let Err(e) = app_handle.emit(EVT_LOCK_STATE_CHANGED, status) {
+        error!(
+            "Failed to emit lock state event '{}': {}",
+            EVT_LOCK_STATE_CHANGED, e
+        );
+    }
+}
+
+/// Emits [`EVT_CLIP_UPDATED`] with the updated clip.
+fn emit_updated(app_handle: &AppHandle, clip: &Clip) {
+    if let Err(e) = app_handle.emit(EVT_CLIP_UPDATED, clip) {
+        error!("Failed to emit update event '{}': {}", EVT_CLIP_UPDATED, e);
+    }
+}
+
+/// Emits [`EVT_CLIP_DELETED`] if using the clip deleted it.
+fn emit_if_deleted_after_use(app_handle: &AppHandle, clip: &Clip) {
+    if !clip.delete_after_use {
+        return;
+    }
+    if let Some(id) = clip.id {
+        if let Err(e) = app_handle.emit(EVT_CLIP_DELETED, &id) {
+            error!("Failed to emit delete event '{}': {}", EVT_CLIP_DELETED, e);
+        }
+    }
+}
+
+/// Emits a bulk operation `event` with the affected clip IDs, unless there are none.
+fn emit_clip_ids(app_handle: &AppHandle, event: &str, ids: &[i32]) {
+    if ids.is_empty() {
+        return;
+    }
+    if let Err(e) = app_handle.emit(event, ids) {
+        error!("Failed to emit bulk event '{}': {}", event, e);
+    }
+}
+
+/// Emits [`EVT_CLIPS_RESTORED`] with the restored clips, unless there are none.
+fn emit_restored(app_handle: &AppHandle, clips: &[Clip]) {
+    if clips.is_empty() {
+        return;
+    }
+    if let Err(e) = app_handle.emit(EVT_CLIPS_RESTORED, clips) {
+        error!(
+            "Failed to emit restore event '{}': {}",
+            EVT_CLIPS_RESTORED, e
+        );
+    }
+}
+
+/// Emits [`EVT_COLLECTION_UPDATED`] with the given collection.
+fn emit_collection_updated(app_handle: &AppHandle, collection: &Collection) {
+    if let Err(e) = app_handle.emit(EVT_COLLECTION_UPDATED, collection) {
+        error!(
+            "Failed to emit collection event '{}': {}",
+            EVT_COLLECTION_UPDATED, e
+        );
+    }
+}
+
+/// Emits [`EVT_COLLECTION_MEMBERSHIP_CHANGED`] with `(collection_id, clip_id, is_member)`.
+fn emit_membership_changed(
+    app_handle: &AppHandle,
+    collection_id: i32,
+    clip_id: i32,
+    is_member: bool,
+) {
+    if let Err(e) = app_handle.emit(
+        EVT_COLLECTION_MEMBERSHIP_CHANGED,
+        &(collection_id, clip_id, is_member),
+    ) {
+        error!(
+            "Failed to emit collection membership event '{}': {}",
+            EVT_COLLECTION_MEMBERSHIP_CHANGED, e
+        );
+    }
+}
+
+/// Runs `task` on the blocking thread pool and waits for it without blocking the runtime.
+///
+/// The managed [`AppState`] is looked up on the blocking thread, since
+/// `State` borrows cannot be moved there.
+async fn blocking<T, F>(app_handle: &AppHandle, task: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&AppState) -> Result<T, AppError> + Send + 'static,
+{
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn_blocking(move || task(app_handle.state::<AppState>().inner()))
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(ipc)
+}
+
 /// Converts application errors to strings for IPC.
 fn ipc<T>(res: Result<T, AppError>) -> Result<T, String> {
     res.map_err(|e| e.to_string())
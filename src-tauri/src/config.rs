@@ -13,6 +13,8 @@ use std::{fs, io::Write, path::PathBuf};
 use tempfile::NamedTempFile;
 use tracing::warn;
 
+use crate::storage::RetentionRule;
+
 // ===== Domain Types =====
 
 /// Application-wide user settings.
@@ -24,9 +26,67 @@ pub struct Settings {
     /// Number of days after which unpinned clips are automatically deleted.
     pub auto_clean_days: u32,
 
-    /// Maximum number of clips to retain in history (after cleanup).
+    /// Maximum number of unpinned clips to retain in history (after cleanup).
     pub max_history_size: u32,
 
+    /// Number of days after which pinned clips are automatically deleted.
+    ///
+    /// `0` (the default) keeps pinned clips forever.
+    #[serde(default)]
+    pub pinned_auto_clean_days: u32,
+
+    /// Maximum number of pinned clips to retain. `0` (the default) means unlimited.
+    #[serde(default)]
+    pub pinned_max_history_size: u32,
+
+    /// Ordered retention rules for unpinned clips.
+    ///
+    /// Each clip is governed by the first rule it matches; clips matching no rule
+    /// use `auto_clean_days` / `max_history_size`. Empty by default.
+    #[serde(default)]
+    pub retention_rules: Vec<RetentionRule>,
+
+    /// Number of days deleted clips stay in the trash before they are purged.
+    ///
+    /// Defaults to 30. `0` keeps trashed clips until the trash is emptied.
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+
+    /// Hours between automatic database backups.
+    ///
+    /// Defaults to 24. `0` disables automatic backups.
+    #[serde(default = "default_backup_interval_hours")]
+    pub backup_interval_hours: u32,
+
+    /// Number of database backups to keep.
+    ///
+    /// Defaults to 7. `0` keeps every backup.
+    #[serde(default = "default_backup_keep_count")]
+    pub backup_keep_count: u32,
+
+    /// Whether database backups are gzip-compressed. Enabled by default.
+    #[serde(default = "default_true")]
+    pub compress_backups: bool,
+
+    /// Whether copying already-stored content bumps the existing clip
+    /// (incrementing its copy count) instead of saving a duplicate.
+    ///
+    /// Enabled by default.
+    #[serde(default = "default_true")]
+    pub is_dedupe_enabled: bool,
+
+    /// Ordered rules making clips from chosen apps expire or self-destruct.
+    ///
+    /// Each new clip takes the first rule matching its app. Empty by default.
+    #[serde(default)]
+    pub expiry_rules: Vec<ExpiryRule>,
+
+    /// What happens to copied content while the encrypted history is locked.
+    ///
+    /// Defaults to [`LockedCapture::Queue`].
+    #[serde(default)]
+    pub locked_capture: LockedCapture,
+
     /// List of application names whose clipboard content should be ignored.
     ///
     /// Example: `["BitWarden", "1Password"]`.
@@ -50,6 +110,32 @@ pub struct Settings {
     pub quick_picker_shortcut: ShortcutConfig,
 }
 
+/// Capture behavior while the encrypted clip history is locked.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum LockedCapture {
+    /// Keep captured clips in memory and save them once the history is unlocked.
+    #[default]
+    Queue,
+    /// Discard clipboard changes until the history is unlocked.
+    Pause,
+}
+
+/// Expiry applied automatically to clips copied from one application.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpiryRule {
+    /// Application whose clips the rule applies to (exact name, case-insensitive).
+    pub app_name: String,
+    /// Seconds after capture at which the clip is deleted, or `None` to keep it
+    /// until it is used or deleted otherwise.
+    #[serde(default)]
+    pub ttl_seconds: Option<u32>,
+    /// Whether the clip is deleted the first time it is pasted.
+    #[serde(default)]
+    pub delete_after_use: bool,
+}
+
 /// Represents a keyboard shortcut configuration.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -67,6 +153,16 @@ impl Default for Settings {
         Self {
             auto_clean_days: 30,
             max_history_size: 200,
+            pinned_auto_clean_days: 0,
+            pinned_max_history_size: 0,
+            retention_rules: Vec::new(),
+            trash_retention_days: default_trash_retention_days(),
+            backup_interval_hours: default_backup_interval_hours(),
+            backup_keep_count: default_backup_keep_count(),
+            compress_backups: true,
+            is_dedupe_enabled: true,
+            expiry_rules: Vec::new(),
+            locked_capture: LockedCapture::Queue,
             ignored_apps: vec!["BitWarden".to_string(), "1Password".to_string()],
             is_new_user: true,
             is_autostart_enabled: true,
@@ -164,3 +260,23 @@ pub fn config_dir() -> PathBuf {
 fn config_file_path() -> PathBuf {
     config_dir().join("config.json")
 }
+
+/// Serde default for boolean settings that are enabled unless turned off.
+fn default_true() -> bool {
+    true
+}
+
+/// Serde default for [`Settings::trash_retention_days`].
+fn default_trash_retention_days() -> u32 {
+    30
+}
+
+/// Serde default for [`Settings::backup_interval_hours`].
+fn default_backup_interval_hours() -> u32 {
+    24
+}
+
+/// Serde default for [`Settings::backup_keep_count`].
+fn default_backup_keep_count() -> u32 {
+    7
+}
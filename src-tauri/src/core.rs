@@ -6,11 +6,15 @@
 //! - Window management ([`window_creation`]).
 //! - System tray integration ([`system_tray`]).
 //! - Background cleanup tasks ([`cleanup`]).
+//! - Scheduled database backups ([`backup`]).
+//! - Deleting expired clips on time ([`expiry`]).
 //! - Global shortcut handling ([`global_shortcut`]).
 //!
 //! It serves as the glue between Tauri's runtime and your domain logic.
 
+pub mod backup;
 pub mod cleanup;
+pub mod expiry;
 pub mod global_shortcut;
 pub mod setup;
 pub mod system_tray;
@@ -0,0 +1,92 @@
+// src-tauri/src/core/backup.rs
+//! Background backup task for the clip database.
+//!
+//! Checks every hour whether the newest backup is older than the configured
+//! interval, and if so takes a new one and rotates out the oldest.
+
+use chrono::Utc;
+use std::sync::{Arc, RwLock};
+use tauri::async_runtime;
+use tokio::time::{interval, Duration};
+use tracing::error;
+
+use crate::{
+    backup::{backup_dir, create_backup, list_backups, rotate_backups},
+    config::Settings,
+    storage::ClipStore,
+};
+
+/// Backup-related user settings.
+struct BackupSettings {
+    interval_hours: u32,
+    keep_count: u32,
+    compress: bool,
+}
+
+/// Spawns a background task that backs up the database on a schedule.
+///
+/// The schedule follows:
+/// - `backup_interval_hours`: Minimum time between backups; `0` disables them.
+/// - `backup_keep_count`: Number of backups kept; `0` keeps all of them.
+/// - `compress_backups`: Whether new backups are gzip-compressed.
+///
+/// Because the interval is measured from the newest backup on disk, restarting
+/// the app does not trigger extra backups. Backups are taken even while the
+/// encrypted history is locked, since snapshots copy content still encrypted.
+pub fn spawn_auto_backup_task(settings: Arc<RwLock<Settings>>, clip_store: Arc<ClipStore>) {
+    async_runtime::spawn(async move {
+        let mut ticker = interval(Duration::from_hours(1));
+        let dir = backup_dir();
+
+        loop {
+            ticker.tick().await;
+
+            let schedule = read_backup_settings(&settings);
+            if schedule.interval_hours == 0 {
+                continue;
+            }
+
+            let now = Utc::now();
+            let is_due = match list_backups(&dir) {
+                Ok(backups) => backups.first().is_none_or(|newest| {
+                    now - newest.created_at
+                        >= chrono::Duration::hours(schedule.interval_hours.into())
+                }),
+                Err(e) => {
+                    tracing::error!("Failed to list backups: {}", e);
+                    continue;
+                }
+            };
+            if !is_due {
+                continue;
+            }
+
+            match create_backup(&clip_store, &dir, schedule.compress, now) {
+                Ok(backup) => tracing::info!("Scheduled backup written: {}", backup.file_name),
+                Err(e) => {
+                    tracing::error!("Scheduled backup failed: {}", e);
+                    continue;
+                }
+            }
+
+            match rotate_backups(&dir, schedule.keep_count) {
+                Ok(removed) => tracing::info!("Rotated out {} old backups", removed),
+                Err(e) => tracing::error!("Backup rotation failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Reads the backup schedule from user settings.
+fn read_backup_settings(settings_arc: &Arc<RwLock<Settings>>) -> BackupSettings {
+    let settings = settings_arc.read().unwrap_or_else(|poisoned| {
+        error!("Settings lock is poisoned");
+        poisoned.into_inner()
+    });
+
+    BackupSettings {
+        interval_hours: settings.backup_interval_hours,
+        keep_count: settings.backup_keep_count,
+        compress: settings.compress_backups,
+    }
+}
@@ -9,13 +9,23 @@ use tauri::async_runtime;
 use tokio::time::{interval, Duration};
 use tracing::error;
 
-use crate::{config::Settings, storage::ClipStore};
+use crate::{
+    config::Settings,
+    storage::{ClipStore, RetentionLimits, RetentionPolicy},
+};
 
 /// Spawns a background task that performs automatic cleanup every 6 hours.
 ///
 /// Cleanup respects:
-/// - `auto_clean_days`: Remove clips older than N days.
-/// - `max_history_size`: Keep at most N clips.
+/// - `auto_clean_days` / `max_history_size`: Limits for unpinned clips.
+/// - `pinned_auto_clean_days` / `pinned_max_history_size`: Limits for pinned clips
+///   (unlimited by default, so pinned clips are kept).
+/// - `retention_rules`: Ordered per-app, per-tag or per-size rules for unpinned clips,
+///   checked before the limits above.
+/// - `trash_retention_days`: How long deleted clips stay in the trash.
+///
+/// A value of `0` disables the corresponding limit. Cleanup is skipped while the
+/// encrypted history is locked.
 pub fn spawn_auto_cleanup_task(settings: Arc<RwLock<Settings>>, clip_store: Arc<ClipStore>) {
     async_runtime::spawn(async move {
         let mut ticker = interval(Duration::from_hours(6));
@@ -23,26 +33,68 @@ pub fn spawn_auto_cleanup_task(settings: Arc<RwLock<Settings>>, clip_store: Arc<
         loop {
             ticker.tick().await;
 
-            let (days, max_size) = read_cleanup_settings(&settings);
+            if clip_store.is_locked() {
+                tracing::info!("Clip history is locked; skipping auto cleanup");
+                continue;
+            }
+
+            let (policy, trash_retention_days) = read_cleanup_settings(&settings);
+
+            match clip_store.perform_cleanup(&policy) {
+                Ok(report) => {
+                    tracing::info!(
+                        "Auto cleanup completed: removed {} unpinned and {} pinned clips",
+                        report.unpinned_removed,
+                        report.pinned_removed
+                    );
+                    for rule in &report.rules {
+                        tracing::info!(
+                            "Retention rule '{}' removed {} clips",
+                            rule.name,
+                            rule.removed
+                        );
+                    }
+                }
+                Err(e) => tracing::error!("Auto cleanup failed: {}", e),
+            }
 
-            if days > 0 {
-                match clip_store.perform_cleanup(days as i64, max_size as i64) {
-                    Ok(_) => tracing::info!("Auto cleanup completed"),
-                    Err(e) => tracing::error!("Auto cleanup failed: {}", e),
+            if trash_retention_days > 0 {
+                match clip_store.purge_trash(trash_retention_days) {
+                    Ok(purged) => tracing::info!("Purged {} clips from the trash", purged),
+                    Err(e) => tracing::error!("Trash purge failed: {}", e),
                 }
             }
         }
     });
 }
 
-fn read_cleanup_settings(settings_arc: &Arc<RwLock<Settings>>) -> (u32, u32) {
+/// Reads the retention policy and the trash retention period from user settings.
+fn read_cleanup_settings(settings_arc: &Arc<RwLock<Settings>>) -> (RetentionPolicy, u32) {
     match settings_arc.read() {
-        Ok(settings) => (settings.auto_clean_days, settings.max_history_size),
+        Ok(settings) => (retention_policy(&settings), settings.trash_retention_days),
         Err(poisoned) => {
             error!("Settings lock is poisoned");
             let settings = poisoned.into_inner();
-            (settings.auto_clean_days, settings.max_history_size)
+            (retention_policy(&settings), settings.trash_retention_days)
         }
     }
 }
 
+/// Builds the storage retention policy from user settings.
+fn retention_policy(settings: &Settings) -> RetentionPolicy {
+    RetentionPolicy::new(limits(settings.auto_clean_days, settings.max_history_size)).with_pinned(
+        limits(
+            settings.pinned_auto_clean_days,
+            settings.pinned_max_history_size,
+        ),
+    )
+    .with_rules(settings.retention_rules.clone())
+}
+
+/// Converts `0`-means-disabled settings values into [`RetentionLimits`].
+fn limits(days: u32, max_size: u32) -> RetentionLimits {
+    RetentionLimits {
+        max_age_days: (days > 0).then_some(days),
+        max_count: (max_size > 0).then_some(max_size),
+    }
+}
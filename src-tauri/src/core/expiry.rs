@@ -0,0 +1,71 @@
+// src-tauri/src/core/expiry.rs
+//! Background task deleting expired clips on time.
+//!
+//! Unlike the 6-hour cleanup ticker, the task sleeps until the next clip expires,
+//! so one-time codes and other short-lived clips disappear when they are due.
+//! Services wake it through [`AppState::expiry_wake`](crate::state::AppState::expiry_wake)
+//! whenever an expiry time is added or changed.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use tauri::{async_runtime, AppHandle, Emitter};
+use tokio::{
+    sync::Notify,
+    time::{sleep, Duration},
+};
+use tracing::{error, info};
+
+use crate::{command::EVT_CLIP_DELETED, storage::ClipStore};
+
+/// Longest time the task sleeps before checking again.
+///
+/// Bounds the delay when the system clock jumps or the machine resumes from
+/// sleep, neither of which the monotonic timer notices.
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+/// Spawns a background task that permanently deletes clips when they expire.
+///
+/// Emits [`EVT_CLIP_DELETED`] with the ID of every deleted clip. Expired clips
+/// are deleted even while the encrypted history is locked.
+pub fn spawn_expiry_task(app_handle: AppHandle, clip_store: Arc<ClipStore>, wake: Arc<Notify>) {
+    async_runtime::spawn(async move {
+        loop {
+            let now = Utc::now();
+            let delay = match clip_store.delete_expired_clips(now) {
+                Ok(ids) => {
+                    if !ids.is_empty() {
+                        info!("Deleted {} expired clips", ids.len());
+                    }
+                    for id in ids {
+                        if let Err(e) = app_handle.emit(EVT_CLIP_DELETED, &id) {
+                            error!("Failed to emit '{}': {}", EVT_CLIP_DELETED, e);
+                        }
+                    }
+                    next_delay(&clip_store, now)
+                }
+                Err(e) => {
+                    error!("Failed to delete expired clips: {}", e);
+                    MAX_SLEEP
+                }
+            };
+
+            tokio::select! {
+                _ = sleep(delay) => {}
+                _ = wake.notified() => {}
+            }
+        }
+    });
+}
+
+/// Returns how long to sleep until the next clip expires, at most [`MAX_SLEEP`].
+fn next_delay(clip_store: &ClipStore, now: DateTime<Utc>) -> Duration {
+    match clip_store.next_expiry() {
+        Ok(Some(next)) => (next - now).to_std().unwrap_or_default().min(MAX_SLEEP),
+        Ok(None) => MAX_SLEEP,
+        Err(e) => {
+            error!("Failed to read the next clip expiry: {}", e);
+            MAX_SLEEP
+        }
+    }
+}
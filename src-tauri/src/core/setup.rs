@@ -14,19 +14,22 @@ use tracing::{error, info};
 use crate::core::global_shortcut;
 use crate::{
     clipboard::watcher::{ClipboardWatcher, ClipboardWatcherHandle},
+    command::{EVT_CLIP_BUMPED, EVT_STORAGE_DEGRADED},
     context::{app_info::get_active_app_info, auto_tags::generate_auto_tags},
-    core::{cleanup, system_tray, window_creation},
+    core::{backup, cleanup, expiry, system_tray, window_creation},
     service,
     state::AppState,
-    storage::Clip,
+    storage::{Clip, SaveOutcome, StorageStatus},
 };
 
 /// Performs one-time application setup during Tauri's `setup` hook.
 ///
 /// Initializes:
-/// - Application state (`AppState`)
+/// - Application state (`AppState`), reporting a corrupt or unusable database
 /// - Clipboard watcher
 /// - Auto-cleanup task
+/// - Clip expiry scheduler
+/// - Scheduled backup task
 /// - Global shortcut (desktop only)
 /// - System tray
 /// - First-run onboarding
@@ -38,9 +41,13 @@ pub fn setup(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     let settings = app_state.settings.clone();
     let clip_store = app_state.clip_store.clone();
     let quick_picker_shortcut_arc = app_state.quick_picker_shortcut.clone();
+    let storage_status = app_state.storage_status.clone();
+    let expiry_wake = app_state.expiry_wake.clone();
 
     app.manage(app_state);
 
+    report_storage_status(&app_handle, &storage_status);
+
     if let Err(e) = handle_first_run(&app_handle) {
         error!("Error occurred while handling first run: {}", e);
     }
@@ -48,6 +55,10 @@ pub fn setup(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     start_clipboard_watcher(app_handle.clone(), watcher_handle.clone());
 
     cleanup::spawn_auto_cleanup_task(settings.clone(), clip_store.clone());
+    expiry::spawn_expiry_task(app_handle.clone(), clip_store.clone(), expiry_wake);
+    if !storage_status.is_degraded() {
+        backup::spawn_auto_backup_task(settings.clone(), clip_store.clone());
+    }
 
     #[cfg(desktop)]
     global_shortcut::handle_quick_picker_shortcut(&app_handle, quick_picker_shortcut_arc.clone())?;
@@ -87,6 +98,16 @@ fn handle_first_run(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Er
     Ok(())
 }
 
+/// Emits [`EVT_STORAGE_DEGRADED`] unless the database opened cleanly.
+fn report_storage_status(app_handle: &AppHandle, status: &StorageStatus) {
+    if *status == StorageStatus::Healthy {
+        return;
+    }
+    if let Err(e) = app_handle.emit(EVT_STORAGE_DEGRADED, status) {
+        error!("Failed to emit '{}': {}", EVT_STORAGE_DEGRADED, e);
+    }
+}
+
 fn start_clipboard_watcher(
     app_handle: AppHandle,
     watcher_handle: Arc<Mutex<Option<ClipboardWatcherHandle>>>,
@@ -126,14 +147,22 @@ fn start_clipboard_watcher(
             false,
         );
 
-        match service::clip::save_clip(app_state.inner(), clip) {
-            Ok(saved) => {
+        match service::clip::capture_clip(app_state.inner(), clip) {
+            Ok(Some(SaveOutcome::Inserted(saved))) => {
                 if let Err(e) = app_handle.emit("clip-added", &saved) {
                     error!("Failed to emit 'clip-added': {}", e);
                 } else {
                     info!("New clip captured ({} bytes)", saved.content.len());
                 }
             }
+            Ok(Some(SaveOutcome::Bumped(bumped))) => {
+                if let Err(e) = app_handle.emit(EVT_CLIP_BUMPED, &bumped) {
+                    error!("Failed to emit '{}': {}", EVT_CLIP_BUMPED, e);
+                } else {
+                    info!("Repeated clip bumped (copied {} times)", bumped.copy_count);
+                }
+            }
+            Ok(None) => info!("Clip history is locked; capture deferred"),
             Err(e) => error!("Failed to save clip: {}", e),
         }
     });
@@ -10,12 +10,13 @@
 //! - Ensure it gains focus reliably via a hide/show cycle.
 //! - Auto-hide when losing focus — **unless** the user is dragging the window.
 //!   Drag state is tracked via [`AppState::is_quick_picker_dragging`].
+//! - Remember the app it was opened over, so snippets can expand `{app}`.
 
 use std::{sync::atomic::Ordering, time::Duration};
 use tauri::{Manager, WebviewUrl};
 use tracing::{error, info};
 
-use crate::state;
+use crate::{context::app_info::get_active_app_info, state};
 
 /// Creates and shows the onboarding window.
 ///
@@ -72,10 +73,22 @@ pub fn create_or_show_main_window(app_handle: &tauri::AppHandle) {
 /// Drag state is read from the shared [`AppState::is_quick_picker_dragging`] flag.
 ///
 /// This prevents the window from disappearing during user-initiated movement.
+///
+/// Unless the quick picker already has focus, the focused app is recorded in
+/// [`AppState::paste_target_app`] first.
 pub fn hide_and_show_quick_picker_window(app_handle: &tauri::AppHandle) {
     let app_handle_clone = app_handle.clone();
     tauri::async_runtime::spawn(async move {
         if let Some(window) = app_handle_clone.get_webview_window("quick-picker") {
+            if !window.is_focused().unwrap_or(false) {
+                let target_app = get_active_app_info().app_class;
+                *app_handle_clone
+                    .state::<state::AppState>()
+                    .paste_target_app
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner()) = Some(target_app);
+            }
+
             if let Err(e) = window.hide() {
                 error!("Failed to hide Quick Picker: {}", e);
             }
@@ -9,6 +9,11 @@
 
 use std::fmt::Display;
 
+use crate::{
+    storage::{EncryptionError, QueryError},
+    template::TemplateError,
+};
+
 // ===== Domain Types =====
 
 /// A categorized error type representing failures in different layers of the application.
@@ -19,6 +24,13 @@ use std::fmt::Display;
 /// - `Core`: Application lifecycle or setup failures.
 /// - `Shortcut`: Invalid or unregistrable global hotkeys.
 /// - `Clipboard`: Issues interacting with the system clipboard.
+/// - `Query`: Malformed search queries entered by the user.
+/// - `Encryption`: Locked history, wrong passphrases or failed encryption.
+/// - `Export`: Failures rendering or writing exported history.
+/// - `Import`: History files that cannot be read or parsed.
+/// - `Backup`: Database backups that cannot be written, listed or restored.
+/// - `Template`: Snippet templates that are malformed or missing input values.
+/// - `InvalidArgument`: Command arguments that are missing or contradict each other.
 ///
 /// This design avoids generic strings and enables structured logging or UI feedback
 /// based on error origin.
@@ -34,6 +46,20 @@ pub enum AppError {
     Shortcut(String),
     /// A problem accessing or modifying the system clipboard.
     Clipboard(String),
+    /// A search query that could not be parsed.
+    Query(QueryError),
+    /// An encryption failure, such as a locked history or a wrong passphrase.
+    Encryption(EncryptionError),
+    /// A failure while rendering or writing an export file.
+    Export(String),
+    /// A history file that could not be read or parsed during an import.
+    Import(String),
+    /// A failure while writing, listing or restoring a database backup.
+    Backup(String),
+    /// A snippet template that could not be parsed or expanded.
+    Template(TemplateError),
+    /// A command argument that is missing or contradicts another one.
+    InvalidArgument(String),
 }
 
 // ===== Implementations =====
@@ -46,8 +72,33 @@ impl Display for AppError {
             AppError::Core(e) => write!(f, "Core Error: {}", e),
             AppError::Shortcut(e) => write!(f, "Shortcut Error: {}", e),
             AppError::Clipboard(e) => write!(f, "Clipboard Error: {}", e),
+            AppError::Query(e) => write!(f, "Query Error: {}", e),
+            AppError::Encryption(e) => write!(f, "Encryption Error: {}", e),
+            AppError::Export(e) => write!(f, "Export Error: {}", e),
+            AppError::Import(e) => write!(f, "Import Error: {}", e),
+            AppError::Backup(e) => write!(f, "Backup Error: {}", e),
+            AppError::Template(e) => write!(f, "Template Error: {}", e),
+            AppError::InvalidArgument(e) => write!(f, "Invalid Argument: {}", e),
         }
     }
 }
 
 impl std::error::Error for AppError {}
+
+impl From<QueryError> for AppError {
+    fn from(e: QueryError) -> Self {
+        AppError::Query(e)
+    }
+}
+
+impl From<TemplateError> for AppError {
+    fn from(e: TemplateError) -> Self {
+        AppError::Template(e)
+    }
+}
+
+impl From<EncryptionError> for AppError {
+    fn from(e: EncryptionError) -> Self {
+        AppError::Encryption(e)
+    }
+}
@@ -0,0 +1,133 @@
+// src-tauri/src/export.rs
+//! Export of clipboard history to files.
+//!
+//! Clips can be exported in three formats (see [`ExportFormat`]):
+//!
+//! - **JSON**: A versioned [`ExportDocument`] holding every clip field. This is
+//!   the lossless format, meant for archiving and re-importing.
+//! - **CSV**: One row per clip, for spreadsheets.
+//! - **Markdown**: A readable document grouped by day and source application.
+//!
+//! Files are written atomically: the export is rendered into a temporary file
+//! next to the destination, which then replaces it in one step.
+//!
+//! # Example
+//!
+//! ```rust
+//! use chrono::Utc;
+//! use clipcontex_lib::{export::{render, ExportFormat}, storage::Clip};
+//!
+//! let clip = Clip::new("cargo build".into(), "Terminal".into(), "bash".into(), vec![], false);
+//! let csv = render(&[clip], ExportFormat::Csv, Utc::now()).unwrap();
+//! assert!(csv.contains("cargo build"));
+//! ```
+
+mod csv;
+mod json;
+mod markdown;
+
+// ===== Imports =====
+
+use std::{fs, io::Write, path::Path};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
+
+use crate::{error::AppError, storage::Clip};
+
+pub use json::{ExportDocument, EXPORT_FORMAT_NAME, EXPORT_VERSION};
+
+// ===== Domain Types =====
+
+/// File format of an export.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportFormat {
+    /// A versioned JSON document with every clip field.
+    Json,
+    /// Comma-separated values, one row per clip.
+    Csv,
+    /// A Markdown document grouped by day and application.
+    Markdown,
+}
+
+/// Result of a completed export.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExportSummary {
+    /// Number of clips written.
+    pub count: usize,
+    /// Path of the written file.
+    pub path: String,
+}
+
+// ===== Public API =====
+
+/// Renders `clips` in the given format.
+///
+/// `exported_at` is recorded in the JSON and Markdown headers.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Export`] if the clips cannot be serialized.
+pub fn render(
+    clips: &[Clip],
+    format: ExportFormat,
+    exported_at: DateTime<Utc>,
+) -> Result<String, AppError> {
+    match format {
+        ExportFormat::Json => json::render(clips, exported_at),
+        ExportFormat::Csv => Ok(csv::render(clips)),
+        ExportFormat::Markdown => Ok(markdown::render(clips, exported_at)),
+    }
+}
+
+/// Writes `contents` to `path` atomically, replacing any existing file.
+///
+/// The data is written to a temporary file in the destination directory and then
+/// persisted over `path`, so readers never observe a partially written export.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Export`] if the directory cannot be created or any
+/// write step fails.
+pub fn write_atomically(path: &Path, contents: &[u8]) -> Result<(), AppError> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    if !dir.exists() {
+        fs::create_dir_all(dir)
+            .map_err(|e| AppError::Export(format!("Failed to create directory: {}", e)))?;
+    }
+
+    let mut tmp = NamedTempFile::new_in(dir)
+        .map_err(|e| AppError::Export(format!("Failed to create temporary file: {}", e)))?;
+
+    tmp.write_all(contents)
+        .map_err(|e| AppError::Export(format!("Failed to write export: {}", e)))?;
+
+    tmp.persist(path)
+        .map_err(|e| AppError::Export(format!("Failed to persist export: {}", e)))?;
+
+    Ok(())
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_atomically_replaces_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("export.json");
+
+        write_atomically(&path, b"first").unwrap();
+        write_atomically(&path, b"second").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+        assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,129 @@
+// src-tauri/src/export/csv.rs
+//! CSV export format.
+//!
+//! Follows RFC 4180: fields containing commas, quotes or line breaks are quoted,
+//! with embedded quotes doubled. Auto and user tags are written as JSON arrays,
+//! since tag names may themselves contain commas. Timestamps use RFC 3339.
+
+// ===== Imports =====
+
+use chrono::{DateTime, Utc};
+
+use crate::storage::Clip;
+
+// ===== Constants =====
+
+/// Header row, one column per [`Clip`] field.
+const HEADER: [&str; 21] = [
+    "id",
+    "content",
+    "byte_len",
+    "line_count",
+    "is_truncated",
+    "app_name",
+    "window_title",
+    "tags",
+    "user_tags",
+    "note",
+    "is_pinned",
+    "is_snippet",
+    "delete_after_use",
+    "created_at",
+    "updated_at",
+    "copy_count",
+    "last_copied_at",
+    "use_count",
+    "last_used_at",
+    "deleted_at",
+    "expires_at",
+];
+
+// ===== Helper Functions =====
+
+/// Renders `clips` as CSV with a header row and CRLF line endings.
+pub(super) fn render(clips: &[Clip]) -> String {
+    let mut out = String::new();
+    push_row(&mut out, HEADER.iter().map(|h| h.to_string()));
+
+    for clip in clips {
+        push_row(
+            &mut out,
+            [
+                clip.id.map(|id| id.to_string()).unwrap_or_default(),
+                clip.content.clone(),
+                clip.byte_len.to_string(),
+                clip.line_count.to_string(),
+                clip.is_truncated.to_string(),
+                clip.app_name.clone(),
+                clip.window_title.clone(),
+                serde_json::to_string(&clip.tags).unwrap_or_default(),
+                serde_json::to_string(&clip.user_tags).unwrap_or_default(),
+                clip.note.clone().unwrap_or_default(),
+                clip.is_pinned.to_string(),
+                clip.is_snippet.to_string(),
+                clip.delete_after_use.to_string(),
+                clip.created_at.to_rfc3339(),
+                clip.updated_at.to_rfc3339(),
+                clip.copy_count.to_string(),
+                clip.last_copied_at.to_rfc3339(),
+                clip.use_count.to_string(),
+                optional_timestamp(clip.last_used_at),
+                optional_timestamp(clip.deleted_at),
+                optional_timestamp(clip.expires_at),
+            ],
+        );
+    }
+
+    out
+}
+
+/// Appends one CSV record to `out`.
+fn push_row(out: &mut String, fields: impl IntoIterator<Item = String>) {
+    for (index, field) in fields.into_iter().enumerate() {
+        if index > 0 {
+            out.push(',');
+        }
+        out.push_str(&escape_field(&field));
+    }
+    out.push_str("\r\n");
+}
+
+/// Quotes `field` if it contains a delimiter, quote or line break.
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Formats an optional timestamp, leaving the field empty when absent.
+fn optional_timestamp(timestamp: Option<DateTime<Utc>>) -> String {
+    timestamp.map(|t| t.to_rfc3339()).unwrap_or_default()
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_quotes_special_characters() {
+        let clip = Clip::new(
+            "say \"hi\",\nthen leave".into(),
+            "Chat".into(),
+            "General".into(),
+            vec!["#a,b".into()],
+            false,
+        );
+
+        let csv = render(&[clip]);
+        let mut lines = csv.split("\r\n");
+
+        assert_eq!(lines.next().unwrap(), HEADER.join(","));
+        let record = lines.next().unwrap();
+        assert!(record.starts_with(",\"say \"\"hi\"\",\nthen leave\",20,2,false,Chat,General,"));
+        assert!(record.contains(",\"[\"\"#a,b\"\"]\",[],,false,false,false,"));
+    }
+}
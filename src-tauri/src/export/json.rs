@@ -0,0 +1,76 @@
+// src-tauri/src/export/json.rs
+//! Versioned JSON export format.
+
+// ===== Imports =====
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{error::AppError, storage::Clip};
+
+// ===== Constants =====
+
+/// Value of [`ExportDocument::format`], identifying ClipContex exports.
+pub const EXPORT_FORMAT_NAME: &str = "clipcontex-export";
+
+/// Current version of the JSON export format.
+///
+/// Bump this whenever the document layout changes incompatibly.
+pub const EXPORT_VERSION: u32 = 1;
+
+// ===== Domain Types =====
+
+/// Top-level JSON export document.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExportDocument {
+    /// Always [`EXPORT_FORMAT_NAME`].
+    pub format: String,
+    /// Format version; see [`EXPORT_VERSION`].
+    pub version: u32,
+    /// When the export was written.
+    pub exported_at: DateTime<Utc>,
+    /// Exported clips with their full content, oldest first.
+    pub clips: Vec<Clip>,
+}
+
+// ===== Helper Functions =====
+
+/// Renders `clips` as a pretty-printed [`ExportDocument`].
+pub(super) fn render(clips: &[Clip], exported_at: DateTime<Utc>) -> Result<String, AppError> {
+    let document = ExportDocument {
+        format: EXPORT_FORMAT_NAME.to_string(),
+        version: EXPORT_VERSION,
+        exported_at,
+        clips: clips.to_vec(),
+    };
+
+    serde_json::to_string_pretty(&document)
+        .map_err(|e| AppError::Export(format!("Failed to serialize clips: {}", e)))
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_export_round_trips() {
+        let clip = Clip::new(
+            "line one\nline two".into(),
+            "Editor".into(),
+            "notes.md".into(),
+            vec!["#note".into()],
+            true,
+        );
+        let exported_at = Utc::now();
+
+        let json = render(std::slice::from_ref(&clip), exported_at).unwrap();
+        let document: ExportDocument = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(document.format, EXPORT_FORMAT_NAME);
+        assert_eq!(document.version, EXPORT_VERSION);
+        assert_eq!(document.exported_at, exported_at);
+        assert_eq!(document.clips, vec![clip]);
+    }
+}
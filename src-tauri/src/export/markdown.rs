@@ -0,0 +1,205 @@
+// src-tauri/src/export/markdown.rs
+//! Markdown export format.
+//!
+//! Clips are grouped by the (UTC) day they were captured, then by source
+//! application. Each clip is written as a fenced code block preceded by a line
+//! with its time, window title, tags and counters, and by its note as a quote,
+//! so content is shown verbatim whatever Markdown it contains.
+
+// ===== Imports =====
+
+use std::fmt::Write;
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::storage::Clip;
+
+// ===== Helper Functions =====
+
+/// Renders `clips` as a Markdown document.
+pub(super) fn render(clips: &[Clip], exported_at: DateTime<Utc>) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# ClipContex export");
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "_{} clips, exported {}_",
+        clips.len(),
+        exported_at.format("%Y-%m-%d %H:%M UTC")
+    );
+
+    let mut sorted: Vec<&Clip> = clips.iter().collect();
+    sorted.sort_by(|a, b| {
+        (a.created_at.date_naive(), &a.app_name, a.created_at, a.id).cmp(&(
+            b.created_at.date_naive(),
+            &b.app_name,
+            b.created_at,
+            b.id,
+        ))
+    });
+
+    let mut current_day: Option<NaiveDate> = None;
+    let mut current_app: Option<&str> = None;
+
+    for clip in sorted {
+        let day = clip.created_at.date_naive();
+        if current_day != Some(day) {
+            let _ = writeln!(out);
+            let _ = writeln!(out, "## {}", day.format("%Y-%m-%d"));
+            current_day = Some(day);
+            current_app = None;
+        }
+        if current_app != Some(clip.app_name.as_str()) {
+            let _ = writeln!(out);
+            let _ = writeln!(out, "### {}", heading_text(&clip.app_name));
+            current_app = Some(clip.app_name.as_str());
+        }
+
+        let _ = writeln!(out);
+        let _ = writeln!(out, "{}", metadata_line(clip));
+        if let Some(note) = &clip.note {
+            let _ = writeln!(out);
+            for line in note.lines() {
+                let _ = writeln!(out, "> {line}");
+            }
+        }
+        let _ = writeln!(out);
+        let fence = code_fence(&clip.content);
+        let _ = writeln!(out, "{fence}");
+        let _ = writeln!(out, "{}", clip.content);
+        let _ = writeln!(out, "{fence}");
+    }
+
+    out
+}
+
+/// Builds the line describing a clip: time, window title, tags and flags.
+fn metadata_line(clip: &Clip) -> String {
+    let mut parts = vec![format!("**{}**", clip.created_at.format("%H:%M:%S"))];
+
+    if !clip.window_title.trim().is_empty() {
+        parts.push(inline_code(&clip.window_title));
+    }
+    if !clip.tags.is_empty() || !clip.user_tags.is_empty() {
+        let tags: Vec<String> = clip
+            .tags
+            .iter()
+            .chain(clip.user_tags.iter().filter(|t| !clip.tags.contains(t)))
+            .map(|t| inline_code(t))
+            .collect();
+        parts.push(tags.join(" "));
+    }
+    if clip.is_pinned {
+        parts.push("pinned".to_string());
+    }
+    if clip.is_snippet {
+        parts.push("snippet".to_string());
+    }
+    if let Some(expires_at) = clip.expires_at {
+        parts.push(format!(
+            "expires {}",
+            expires_at.format("%Y-%m-%d %H:%M UTC")
+        ));
+    }
+    if clip.delete_after_use {
+        parts.push("deleted after use".to_string());
+    }
+    if clip.copy_count > 1 {
+        parts.push(format!("copied {}×", clip.copy_count));
+    }
+    if clip.use_count > 0 {
+        parts.push(format!("used {}×", clip.use_count));
+    }
+
+    parts.join(" · ")
+}
+
+/// Returns a backtick fence longer than any backtick run in `content`.
+fn code_fence(content: &str) -> String {
+    "`".repeat(longest_backtick_run(content).max(2) + 1)
+}
+
+/// Wraps `text` in an inline code span that survives embedded backticks.
+fn inline_code(text: &str) -> String {
+    let text = text.replace(['\n', '\r'], " ");
+    let ticks = "`".repeat(longest_backtick_run(&text) + 1);
+    if text.starts_with('`') || text.ends_with('`') {
+        format!("{ticks} {text} {ticks}")
+    } else {
+        format!("{ticks}{text}{ticks}")
+    }
+}
+
+/// Makes an application name safe for use as a heading.
+fn heading_text(app_name: &str) -> String {
+    let name = app_name.replace(['\n', '\r'], " ");
+    if name.trim().is_empty() {
+        "Unknown app".to_string()
+    } else {
+        name
+    }
+}
+
+/// Returns the length of the longest run of consecutive backticks in `text`.
+fn longest_backtick_run(text: &str) -> usize {
+    text.split(|c| c != '`').map(str::len).max().unwrap_or(0)
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn clip_at(content: &str, app: &str, created_at: DateTime<Utc>) -> Clip {
+        let mut clip = Clip::new(content.into(), app.into(), "Win".into(), vec![], false);
+        clip.created_at = created_at;
+        clip
+    }
+
+    #[test]
+    fn test_markdown_groups_by_day_then_app() {
+        let day_one = Utc.with_ymd_and_hms(2024, 3, 1, 9, 0, 0).unwrap();
+        let day_two = Utc.with_ymd_and_hms(2024, 3, 2, 9, 0, 0).unwrap();
+        let clips = vec![
+            clip_at("second day", "Terminal", day_two),
+            clip_at("from firefox", "Firefox", day_one),
+            clip_at("from terminal", "Terminal", day_one),
+            clip_at(
+                "firefox again",
+                "Firefox",
+                day_one + chrono::Duration::hours(1),
+            ),
+        ];
+
+        let markdown = render(&clips, day_two);
+        let headings: Vec<&str> = markdown
+            .lines()
+            .filter(|line| line.starts_with('#'))
+            .collect();
+
+        assert_eq!(
+            headings,
+            vec![
+                "# ClipContex export",
+                "## 2024-03-01",
+                "### Firefox",
+                "### Terminal",
+                "## 2024-03-02",
+                "### Terminal",
+            ]
+        );
+        let first = markdown.find("from firefox").unwrap();
+        let second = markdown.find("firefox again").unwrap();
+        assert!(first < second);
+    }
+
+    #[test]
+    fn test_code_fence_outgrows_content_backticks() {
+        let clip = clip_at("```rust\nfn main() {}\n```", "Editor", Utc::now());
+        let markdown = render(&[clip], Utc::now());
+
+        assert!(markdown.contains("````\n```rust\nfn main() {}\n```\n````"));
+    }
+}
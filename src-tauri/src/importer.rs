@@ -0,0 +1,150 @@
+// src-tauri/src/importer.rs
+//! Import of clipboard history from other clipboard managers.
+//!
+//! Each supported [`ImportSource`] has a parser turning its on-disk history file
+//! into [`Clip`]s:
+//!
+//! - **ClipContex**: JSON exports written by [`crate::export`].
+//! - **CopyQ**: Tab data files (`~/.config/copyq/copyq_tab_*.dat`).
+//! - **Klipper**: KDE's `history2.lst`.
+//! - **Clipboard Indicator**: The GNOME Shell extension's `registry.txt`.
+//! - **GPaste**: `history.xml`.
+//!
+//! Only text entries are imported; images and other binary entries are skipped.
+//! Original timestamps are kept where the format records them. Otherwise clips
+//! are given timestamps one second apart, ending now, that preserve the
+//! history's order. Every imported clip is tagged with
+//! [`generate_auto_tags`], and deduplication against stored clips happens in
+//! [`crate::storage::ClipStore::import_clips`].
+
+mod clipboard_indicator;
+mod clipcontex;
+mod copyq;
+mod gpaste;
+mod klipper;
+mod qdatastream;
+
+// ===== Imports =====
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, Duration, Utc};
+use dirs::{cache_dir, config_dir, data_dir};
+use serde::{Deserialize, Serialize};
+
+use crate::{context::auto_tags::generate_auto_tags, error::AppError, storage::Clip};
+
+// ===== Domain Types =====
+
+/// A clipboard manager whose history can be imported.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportSource {
+    /// A JSON export written by ClipContex itself.
+    #[serde(rename = "clipcontex")]
+    ClipContex,
+    /// CopyQ tab data files.
+    #[serde(rename = "copyq")]
+    CopyQ,
+    /// KDE Klipper.
+    Klipper,
+    /// The GNOME Shell "Clipboard Indicator" extension.
+    ClipboardIndicator,
+    /// GPaste.
+    #[serde(rename = "gpaste")]
+    GPaste,
+}
+
+/// Clips parsed from a history file, before deduplication.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ParsedHistory {
+    /// Text entries converted to clips.
+    pub clips: Vec<Clip>,
+    /// Entries left out because they are not text (e.g., images).
+    pub skipped: usize,
+    /// Entries that could not be decoded.
+    pub failed: usize,
+}
+
+/// A text entry read from a foreign history, before conversion to a [`Clip`].
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    content: String,
+    created_at: Option<DateTime<Utc>>,
+    is_pinned: bool,
+}
+
+// ===== Public API =====
+
+/// Parses a history file of the given source.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Import`] if the file is not in the expected format.
+/// Individual entries that cannot be decoded are counted in
+/// [`ParsedHistory::failed`] instead.
+pub fn parse(source: ImportSource, data: &[u8]) -> Result<ParsedHistory, AppError> {
+    match source {
+        ImportSource::ClipContex => clipcontex::parse(data),
+        ImportSource::CopyQ => copyq::parse(data),
+        ImportSource::Klipper => klipper::parse(data),
+        ImportSource::ClipboardIndicator => clipboard_indicator::parse(data),
+        ImportSource::GPaste => gpaste::parse(data),
+    }
+}
+
+/// Returns where the given source keeps its history by default, if it has a
+/// fixed location.
+///
+/// CopyQ's default location is its built-in "&clipboard" tab.
+/// ClipContex exports have no default location.
+pub fn default_path(source: ImportSource) -> Option<PathBuf> {
+    match source {
+        ImportSource::ClipContex => None,
+        ImportSource::CopyQ => {
+            config_dir().map(|dir| dir.join("copyq").join("copyq_tab_JmNsaXBib2FyZA==.dat"))
+        }
+        ImportSource::Klipper => data_dir().map(|dir| dir.join("klipper").join("history2.lst")),
+        ImportSource::ClipboardIndicator => cache_dir().map(|dir| {
+            dir.join("clipboard-indicator@tudmotu.com")
+                .join("registry.txt")
+        }),
+        ImportSource::GPaste => data_dir().map(|dir| dir.join("gpaste").join("history.xml")),
+    }
+}
+
+// ===== Helper Functions =====
+
+/// Converts entries read from `app_name`'s history into clips.
+///
+/// `entries` must be ordered newest first. Entries without a timestamp are given
+/// one that keeps them in that order. The source application is unknown, so tags
+/// are generated from content alone.
+fn into_clips(entries: Vec<Entry>, app_name: &str, now: DateTime<Utc>) -> Vec<Clip> {
+    entries
+        .into_iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let tags = generate_auto_tags(&entry.content, None);
+            let mut clip = Clip::new(
+                entry.content,
+                app_name.into(),
+                String::new(),
+                tags,
+                entry.is_pinned,
+            );
+            let created_at = entry
+                .created_at
+                .unwrap_or_else(|| now - Duration::seconds(index as i64));
+            clip.created_at = created_at;
+            clip.updated_at = created_at;
+            clip.last_copied_at = created_at;
+            clip
+        })
+        .collect()
+}
+
+/// Returns whether `content` is worth importing (not empty or whitespace).
+fn is_importable(content: &str) -> bool {
+    !content.trim().is_empty()
+}
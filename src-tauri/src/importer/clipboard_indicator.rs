@@ -0,0 +1,106 @@
+// src-tauri/src/importer/clipboard_indicator.rs
+//! Parser for the GNOME Shell "Clipboard Indicator" extension's `registry.txt`.
+//!
+//! The registry is a JSON array ordered oldest first. Older versions of the
+//! extension store plain strings; newer ones store objects with the contents,
+//! a favorite flag and a MIME type. Favorites are imported as pinned clips and
+//! image entries are skipped. The registry records no capture times.
+
+// ===== Imports =====
+
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    error::AppError,
+    importer::{into_clips, is_importable, Entry, ParsedHistory},
+};
+
+// ===== Constants =====
+
+/// Application name given to imported clips.
+const APP_NAME: &str = "Clipboard Indicator";
+
+// ===== Domain Types =====
+
+/// An entry of the newer registry format.
+#[derive(Deserialize)]
+struct RegistryEntry {
+    contents: String,
+    #[serde(default)]
+    favorite: bool,
+    #[serde(default)]
+    mimetype: Option<String>,
+}
+
+// ===== Helper Functions =====
+
+/// Parses a Clipboard Indicator registry.
+pub(super) fn parse(data: &[u8]) -> Result<ParsedHistory, AppError> {
+    let registry: Vec<Value> = serde_json::from_slice(data)
+        .map_err(|e| AppError::Import(format!("Not a Clipboard Indicator registry: {}", e)))?;
+
+    let mut history = ParsedHistory::default();
+    let mut entries = Vec::new();
+    for value in registry.into_iter().rev() {
+        let entry = match value {
+            Value::String(contents) => RegistryEntry {
+                contents,
+                favorite: false,
+                mimetype: None,
+            },
+            value => match serde_json::from_value::<RegistryEntry>(value) {
+                Ok(entry) => entry,
+                Err(_) => {
+                    history.failed += 1;
+                    continue;
+                }
+            },
+        };
+
+        let is_image = entry
+            .mimetype
+            .as_deref()
+            .is_some_and(|mime| mime.starts_with("image/"));
+        if is_image || !is_importable(&entry.contents) {
+            history.skipped += 1;
+            continue;
+        }
+
+        entries.push(Entry {
+            content: entry.contents,
+            created_at: None,
+            is_pinned: entry.favorite,
+        });
+    }
+
+    history.clips = into_clips(entries, APP_NAME, Utc::now());
+    Ok(history)
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_both_registry_formats() {
+        let registry = r#"[
+            "oldest",
+            {"contents": "favorite", "favorite": true, "mimetype": "text/plain"},
+            {"contents": "/tmp/shot.png", "favorite": false, "mimetype": "image/png"},
+            {"favorite": true},
+            {"contents": "newest", "favorite": false}
+        ]"#;
+
+        let history = parse(registry.as_bytes()).unwrap();
+
+        assert_eq!((history.skipped, history.failed), (1, 1));
+        let contents: Vec<&str> = history.clips.iter().map(|c| c.content.as_str()).collect();
+        assert_eq!(contents, vec!["newest", "favorite", "oldest"]);
+        assert!(history.clips[1].is_pinned);
+        assert!(history.clips[0].created_at > history.clips[2].created_at);
+    }
+}
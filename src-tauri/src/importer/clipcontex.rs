@@ -0,0 +1,132 @@
+// src-tauri/src/importer/clipcontex.rs
+//! Parser for ClipContex's own JSON exports.
+//!
+//! Exported clips keep all of their metadata. Their IDs and trash state are
+//! reset, and auto tags are regenerated and merged with the exported tags so
+//! that exports from older versions benefit from newer tag rules.
+
+// ===== Imports =====
+
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::warn;
+
+use crate::{
+    context::auto_tags::generate_auto_tags,
+    error::AppError,
+    export::{EXPORT_FORMAT_NAME, EXPORT_VERSION},
+    importer::{is_importable, ParsedHistory},
+    storage::Clip,
+};
+
+// ===== Domain Types =====
+
+/// An [`crate::export::ExportDocument`] whose clips are decoded one by one, so
+/// a single malformed clip does not reject the whole file.
+#[derive(Deserialize)]
+struct RawDocument {
+    format: String,
+    version: u32,
+    clips: Vec<Value>,
+}
+
+// ===== Helper Functions =====
+
+/// Parses a JSON export document.
+pub(super) fn parse(data: &[u8]) -> Result<ParsedHistory, AppError> {
+    let document: RawDocument = serde_json::from_slice(data)
+        .map_err(|e| AppError::Import(format!("Not a ClipContex JSON export: {}", e)))?;
+
+    if document.format != EXPORT_FORMAT_NAME {
+        return Err(AppError::Import(format!(
+            "Unknown export format '{}'",
+            document.format
+        )));
+    }
+    if document.version > EXPORT_VERSION {
+        return Err(AppError::Import(format!(
+            "Export version {} is newer than supported version {}",
+            document.version, EXPORT_VERSION
+        )));
+    }
+
+    let mut history = ParsedHistory::default();
+    for value in document.clips {
+        match serde_json::from_value::<Clip>(value) {
+            Ok(clip) if !is_importable(&clip.content) => history.skipped += 1,
+            Ok(clip) => history.clips.push(prepare(clip)),
+            Err(e) => {
+                warn!("Skipping malformed clip in export: {}", e);
+                history.failed += 1;
+            }
+        }
+    }
+
+    Ok(history)
+}
+
+/// Resets storage state on an exported clip and refreshes its auto tags.
+///
+/// User tags and the note are kept as exported.
+fn prepare(mut clip: Clip) -> Clip {
+    clip.id = None;
+    clip.deleted_at = None;
+    clip.is_truncated = false;
+
+    for tag in generate_auto_tags(&clip.content, Some(&clip.app_name)) {
+        if !clip.tags.contains(&tag) {
+            clip.tags.push(tag);
+        }
+    }
+    clip
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    use crate::export::{render, ExportFormat};
+
+    #[test]
+    fn test_parses_export_and_keeps_metadata() {
+        let mut clip = Clip::new(
+            "https://example.com".into(),
+            "Firefox".into(),
+            "Example".into(),
+            vec!["#mine".into()],
+            true,
+        );
+        clip.id = Some(42);
+        clip.user_tags = vec!["work".into()];
+        clip.note = Some("for the report".into());
+        clip.created_at = Utc::now() - Duration::days(3);
+        clip.deleted_at = Some(Utc::now());
+        let json = render(&[clip.clone()], ExportFormat::Json, Utc::now()).unwrap();
+
+        let history = parse(json.as_bytes()).unwrap();
+
+        assert_eq!(history.failed, 0);
+        let imported = &history.clips[0];
+        assert_eq!(imported.id, None);
+        assert_eq!(imported.deleted_at, None);
+        assert_eq!(imported.created_at, clip.created_at);
+        assert!(imported.is_pinned);
+        assert!(imported.tags.contains(&"#mine".to_string()));
+        assert!(imported.tags.contains(&"#url".to_string()));
+        assert_eq!(imported.user_tags, vec!["work".to_string()]);
+        assert_eq!(imported.note.as_deref(), Some("for the report"));
+    }
+
+    #[test]
+    fn test_counts_malformed_clips_and_rejects_unknown_formats() {
+        let json = r#"{"format":"clipcontex-export","version":1,"exported_at":"2024-01-01T00:00:00Z","clips":[{"content":1}]}"#;
+        let history = parse(json.as_bytes()).unwrap();
+        assert_eq!((history.clips.len(), history.failed), (0, 1));
+
+        let other = r#"{"format":"something-else","version":1,"clips":[]}"#;
+        assert!(parse(other.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,194 @@
+// src-tauri/src/importer/copyq.rs
+//! Parser for CopyQ tab data files.
+//!
+//! A tab file is a `QDataStream` holding an optional `QString` header, the item
+//! count and the items, newest first. Each item is a map from MIME type to data:
+//!
+//! - **Version 2** items start with `-2`, then the entry count and, per entry, a
+//!   MIME type abbreviated by a leading code digit, a "compressed" flag and the
+//!   data.
+//! - **Version 1** items start with the entry count, then per entry the MIME type
+//!   and `qCompress`ed data.
+//!
+//! CopyQ records no capture times. Compressed data is not supported (no zlib is
+//! available), so such entries are counted as failed. Tabs saved by the
+//! encryption or synchronization plugins are rejected.
+
+// ===== Imports =====
+
+use chrono::Utc;
+
+use crate::{
+    error::AppError,
+    importer::{into_clips, is_importable, qdatastream::Reader, Entry, ParsedHistory},
+};
+
+// ===== Constants =====
+
+/// Application name given to imported clips.
+const APP_NAME: &str = "CopyQ";
+
+/// Item marker of the version 2 item format.
+const ITEM_FORMAT_V2: i32 = -2;
+
+/// MIME types holding an item's plain text.
+const TEXT_MIMES: [&str; 2] = ["text/plain", "text/plain;charset=utf-8"];
+
+/// MIME type present on pinned items.
+const PINNED_MIME: &str = "application/x-copyq-item-pinned";
+
+// ===== Domain Types =====
+
+/// Outcome of reading one item.
+enum Item {
+    Text(Entry),
+    NotText,
+    Compressed,
+}
+
+// ===== Helper Functions =====
+
+/// Parses a CopyQ tab data file.
+pub(super) fn parse(data: &[u8]) -> Result<ParsedHistory, AppError> {
+    let mut reader = Reader::new(data);
+    skip_header(&mut reader, data)?;
+
+    let count = reader.i32()?;
+    if count < 0 {
+        return Err(AppError::Import("Invalid CopyQ item count".into()));
+    }
+
+    let mut history = ParsedHistory::default();
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        // Entries are not self-delimiting, so a decoding error ends the tab.
+        match read_item(&mut reader)? {
+            Item::Text(entry) if is_importable(&entry.content) => entries.push(entry),
+            Item::Text(_) | Item::NotText => history.skipped += 1,
+            Item::Compressed => history.failed += 1,
+        }
+    }
+
+    history.clips = into_clips(entries, APP_NAME, Utc::now());
+    Ok(history)
+}
+
+/// Consumes the tab header, if any, rejecting tabs written by plugins.
+fn skip_header(reader: &mut Reader, data: &[u8]) -> Result<(), AppError> {
+    let mut probe = Reader::new(data);
+    let header = match probe.string() {
+        Ok(header) if header.starts_with("CopyQ") => header,
+        _ => return Ok(()),
+    };
+
+    if header.contains("encrypted") || header.contains("itemsync") {
+        return Err(AppError::Import(format!(
+            "Unsupported CopyQ tab format '{}'",
+            header
+        )));
+    }
+    reader.string()?;
+    Ok(())
+}
+
+/// Reads one item and extracts its text and pin status.
+fn read_item(reader: &mut Reader) -> Result<Item, AppError> {
+    let mut text = None;
+    let mut is_pinned = false;
+    let mut compressed = false;
+
+    let marker = reader.i32()?;
+    let (version, size) = if marker == ITEM_FORMAT_V2 {
+        (2, reader.i32()?)
+    } else {
+        (1, marker)
+    };
+
+    for _ in 0..size.max(0) {
+        let (mime, is_compressed, bytes) = if version == 2 {
+            let mime = expand_mime(&reader.string()?);
+            let is_compressed = reader.bool()?;
+            (mime, is_compressed, reader.byte_array()?)
+        } else {
+            let mime = reader.string()?;
+            let bytes = reader.byte_array()?;
+            (mime, !bytes.is_empty(), bytes)
+        };
+
+        if mime == PINNED_MIME {
+            is_pinned = true;
+        } else if TEXT_MIMES.contains(&mime.as_str()) && text.is_none() {
+            if is_compressed {
+                compressed = true;
+            } else {
+                text = Some(String::from_utf8_lossy(bytes).into_owned());
+            }
+        }
+    }
+
+    Ok(match text {
+        Some(content) => Item::Text(Entry {
+            content,
+            created_at: None,
+            is_pinned,
+        }),
+        None if compressed => Item::Compressed,
+        None => Item::NotText,
+    })
+}
+
+/// Expands a MIME type abbreviated by CopyQ's leading code digit.
+fn expand_mime(mime: &str) -> String {
+    if let Some(rest) = mime.strip_prefix('1') {
+        format!("application/x-copyq-{}", rest)
+    } else if let Some(rest) = mime.strip_prefix('2') {
+        format!("text/{}", rest)
+    } else {
+        mime.strip_prefix('0').unwrap_or(mime).to_string()
+    }
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importer::qdatastream::Writer;
+
+    fn v2_item(writer: &mut Writer, entries: &[(&str, bool, &[u8])]) {
+        writer.i32(ITEM_FORMAT_V2).i32(entries.len() as i32);
+        for (mime, compressed, bytes) in entries {
+            writer.string(mime).bool(*compressed).byte_array(bytes);
+        }
+    }
+
+    #[test]
+    fn test_parses_v2_items_newest_first() {
+        let mut writer = Writer::default();
+        writer.string("CopyQ v3").i32(4);
+        v2_item(
+            &mut writer,
+            &[("2plain", false, b"newest"), ("1item-pinned", false, b"")],
+        );
+        v2_item(&mut writer, &[("0image/png", false, b"\x89PNG")]);
+        v2_item(&mut writer, &[("2plain", true, b"zlib")]);
+        v2_item(&mut writer, &[("2plain", false, b"oldest")]);
+
+        let history = parse(&writer.data).unwrap();
+
+        assert_eq!((history.skipped, history.failed), (1, 1));
+        let contents: Vec<&str> = history.clips.iter().map(|c| c.content.as_str()).collect();
+        assert_eq!(contents, vec!["newest", "oldest"]);
+        assert!(history.clips[0].is_pinned);
+        assert!(history.clips[0].created_at > history.clips[1].created_at);
+        assert_eq!(history.clips[0].app_name, APP_NAME);
+    }
+
+    #[test]
+    fn test_rejects_encrypted_tabs() {
+        let mut writer = Writer::default();
+        writer.string("CopyQ_encrypted_tab v2").i32(0);
+
+        assert!(parse(&writer.data).is_err());
+    }
+}
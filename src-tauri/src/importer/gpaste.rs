@@ -0,0 +1,232 @@
+// src-tauri/src/importer/gpaste.rs
+//! Parser for GPaste's `history.xml`.
+//!
+//! The history is a `<history>` element holding `<item>` elements, newest first.
+//! Each item has a `kind` attribute and its text in a `<value>` element, usually
+//! as CDATA. `Text` and `Uris` items are imported; `Password` and `Image` items
+//! are skipped. Items may carry a `date` attribute (Unix seconds or RFC 3339),
+//! which is kept as the capture time.
+//!
+//! The file format is simple and fixed, so it is read with a small scanner
+//! rather than a full XML parser.
+
+// ===== Imports =====
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    error::AppError,
+    importer::{into_clips, is_importable, Entry, ParsedHistory},
+};
+
+// ===== Constants =====
+
+/// Application name given to imported clips.
+const APP_NAME: &str = "GPaste";
+
+/// Item kinds holding text.
+const TEXT_KINDS: [&str; 2] = ["Text", "Uris"];
+
+// ===== Helper Functions =====
+
+/// Parses a GPaste history file.
+pub(super) fn parse(data: &[u8]) -> Result<ParsedHistory, AppError> {
+    let xml = std::str::from_utf8(data)
+        .map_err(|_| AppError::Import("GPaste history is not valid UTF-8".into()))?;
+    if !xml.contains("<history") {
+        return Err(AppError::Import("Not a GPaste history file".into()));
+    }
+
+    let mut history = ParsedHistory::default();
+    let mut entries = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = find_tag(rest, "item") {
+        rest = &rest[start..];
+        let Some(tag_end) = rest.find('>') else {
+            history.failed += 1;
+            break;
+        };
+        let start_tag = &rest[..tag_end];
+        let self_closing = start_tag.ends_with('/');
+        let body_end = if self_closing {
+            0
+        } else {
+            rest.find("</item>").unwrap_or(rest.len())
+        };
+        let body = if self_closing {
+            ""
+        } else {
+            &rest[tag_end + 1..body_end]
+        };
+        rest = &rest[(tag_end + 1).max(body_end)..];
+
+        let kind = attribute(start_tag, "kind").unwrap_or_default();
+        if !TEXT_KINDS.contains(&kind.as_str()) {
+            history.skipped += 1;
+            continue;
+        }
+
+        let Some(content) = element_text(body, "value") else {
+            history.failed += 1;
+            continue;
+        };
+        if !is_importable(&content) {
+            history.skipped += 1;
+            continue;
+        }
+
+        entries.push(Entry {
+            content,
+            created_at: attribute(start_tag, "date").and_then(|date| parse_date(&date)),
+            is_pinned: false,
+        });
+    }
+
+    history.clips = into_clips(entries, APP_NAME, Utc::now());
+    Ok(history)
+}
+
+/// Returns the offset of the next `<name` start tag in `xml`.
+fn find_tag(xml: &str, name: &str) -> Option<usize> {
+    let pattern = format!("<{}", name);
+    let mut offset = 0;
+    while let Some(found) = xml[offset..].find(&pattern) {
+        let at = offset + found;
+        let next = xml[at + pattern.len()..].chars().next();
+        if matches!(next, Some(c) if c.is_whitespace() || c == '>' || c == '/') {
+            return Some(at);
+        }
+        offset = at + pattern.len();
+    }
+    None
+}
+
+/// Returns the decoded value of attribute `name` in a start tag.
+fn attribute(start_tag: &str, name: &str) -> Option<String> {
+    let mut rest = start_tag;
+    loop {
+        let at = rest.find(name)?;
+        let preceded_by_space = rest[..at].ends_with(char::is_whitespace);
+        let after = rest[at + name.len()..].trim_start();
+        if preceded_by_space {
+            if let Some(value) = after.strip_prefix('=') {
+                let value = value.trim_start();
+                let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+                let end = value[1..].find(quote)?;
+                return Some(decode_entities(&value[1..1 + end]));
+            }
+        }
+        rest = &rest[at + name.len()..];
+    }
+}
+
+/// Returns the text of the first `<name>` element in `xml`, with CDATA
+/// sections unwrapped and entities decoded.
+fn element_text(xml: &str, name: &str) -> Option<String> {
+    let start = find_tag(xml, name)?;
+    let open_end = start + xml[start..].find('>')?;
+    if xml[..open_end].ends_with('/') {
+        return Some(String::new());
+    }
+    let inner = &xml[open_end + 1..];
+    let close = format!("</{}>", name);
+
+    let mut text = String::new();
+    let mut rest = inner;
+    loop {
+        let cdata = rest.find("<![CDATA[");
+        let end = rest.find(&close)?;
+        match cdata {
+            Some(cdata) if cdata < end => {
+                text.push_str(&decode_entities(&rest[..cdata]));
+                let body = &rest[cdata + "<![CDATA[".len()..];
+                let cdata_end = body.find("]]>")?;
+                text.push_str(&body[..cdata_end]);
+                rest = &body[cdata_end + "]]>".len()..];
+            }
+            _ => {
+                text.push_str(&decode_entities(&rest[..end]));
+                return Some(text);
+            }
+        }
+    }
+}
+
+/// Decodes the predefined XML entities and numeric character references.
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').and_then(|semi| {
+            let entity = &rest[1..semi];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => entity
+                    .strip_prefix("#x")
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, semi))
+        });
+        match decoded {
+            Some((c, semi)) => {
+                out.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Parses an item date given as Unix seconds or RFC 3339.
+fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+    match date.trim().parse::<i64>() {
+        Ok(seconds) => DateTime::from_timestamp(seconds, 0),
+        Err(_) => DateTime::parse_from_rfc3339(date.trim())
+            .ok()
+            .map(|d| d.with_timezone(&Utc)),
+    }
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_text_items_and_skips_others() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<history version="2.0">
+  <item kind="Text" uuid="1" date="1700000000"><value><![CDATA[a < b && c]]></value></item>
+  <item kind="Password" uuid="2" name="bank"><value><![CDATA[hunter2]]></value></item>
+  <item kind="Image" uuid="3"><value><![CDATA[/tmp/img.png]]></value></item>
+  <item kind="Uris" uuid="4"><value>file:///a&#10;file:///b&amp;c</value></item>
+</history>"#;
+
+        let history = parse(xml.as_bytes()).unwrap();
+
+        assert_eq!((history.skipped, history.failed), (2, 0));
+        let contents: Vec<&str> = history.clips.iter().map(|c| c.content.as_str()).collect();
+        assert_eq!(contents, vec!["a < b && c", "file:///a\nfile:///b&c"]);
+        assert_eq!(history.clips[0].created_at.timestamp(), 1_700_000_000);
+    }
+
+    #[test]
+    fn test_rejects_other_files() {
+        assert!(parse(b"not xml at all").is_err());
+    }
+}
@@ -0,0 +1,138 @@
+// src-tauri/src/importer/klipper.rs
+//! Parser for KDE Klipper's `history2.lst`.
+//!
+//! The file is a `QDataStream` holding a CRC-32 checksum followed by a
+//! `QByteArray` with the history: Klipper's version string, then the items,
+//! newest first. Each item starts with its type name:
+//!
+//! - `"string"`: A `QString` of text.
+//! - `"url"`: A `QList<QUrl>` (each an encoded `QByteArray`), a
+//!   `QMap<QString, QString>` of metadata and a cut flag.
+//! - `"image"`: A serialized image, which cannot be skipped without decoding
+//!   it, so reading stops there.
+//!
+//! Klipper records no capture times. The checksum is not verified; entries that
+//! cannot be decoded are counted as failed instead.
+
+// ===== Imports =====
+
+use chrono::Utc;
+
+use crate::{
+    error::AppError,
+    importer::{into_clips, is_importable, qdatastream::Reader, Entry, ParsedHistory},
+};
+
+// ===== Constants =====
+
+/// Application name given to imported clips.
+const APP_NAME: &str = "Klipper";
+
+// ===== Helper Functions =====
+
+/// Parses a Klipper history file.
+pub(super) fn parse(data: &[u8]) -> Result<ParsedHistory, AppError> {
+    let mut outer = Reader::new(data);
+    let _crc = outer.u32()?;
+    let mut reader = Reader::new(outer.byte_array()?);
+    let _version = reader.c_string()?;
+
+    let mut history = ParsedHistory::default();
+    let mut entries = Vec::new();
+    while !reader.is_at_end() {
+        let content = match read_item(&mut reader) {
+            Ok(Some(content)) => content,
+            Ok(None) | Err(_) => {
+                // The rest of the history cannot be located past this item.
+                history.failed += 1;
+                break;
+            }
+        };
+
+        if is_importable(&content) {
+            entries.push(Entry {
+                content,
+                created_at: None,
+                is_pinned: false,
+            });
+        } else {
+            history.skipped += 1;
+        }
+    }
+
+    history.clips = into_clips(entries, APP_NAME, Utc::now());
+    Ok(history)
+}
+
+/// Reads one item, returning its text, or `None` for items that end parsing.
+fn read_item(reader: &mut Reader) -> Result<Option<String>, AppError> {
+    match reader.string()?.as_str() {
+        "string" => reader.string().map(Some),
+        "url" => {
+            let count = reader.u32()?;
+            let mut urls = Vec::new();
+            for _ in 0..count {
+                urls.push(String::from_utf8_lossy(reader.byte_array()?).into_owned());
+            }
+            for _ in 0..reader.u32()? {
+                reader.string()?;
+                reader.string()?;
+            }
+            let _cut = reader.i32()?;
+            Ok(Some(urls.join("\n")))
+        }
+        _ => Ok(None),
+    }
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importer::qdatastream::Writer;
+
+    fn history_file(items: impl FnOnce(&mut Writer)) -> Vec<u8> {
+        let mut inner = Writer::default();
+        inner.byte_array(b"5.27.0\0");
+        items(&mut inner);
+
+        let mut outer = Writer::default();
+        outer.u32(0).byte_array(&inner.data);
+        outer.data
+    }
+
+    #[test]
+    fn test_parses_strings_and_urls() {
+        let data = history_file(|w| {
+            w.string("string").string("newest text");
+            w.string("url")
+                .u32(2)
+                .byte_array(b"file:///a")
+                .byte_array(b"file:///b");
+            w.u32(1).string("key").string("value").i32(0);
+            w.string("string").string("   ");
+        });
+
+        let history = parse(&data).unwrap();
+
+        assert_eq!((history.skipped, history.failed), (1, 0));
+        let contents: Vec<&str> = history.clips.iter().map(|c| c.content.as_str()).collect();
+        assert_eq!(contents, vec!["newest text", "file:///a\nfile:///b"]);
+        assert!(history.clips[0].created_at > history.clips[1].created_at);
+    }
+
+    #[test]
+    fn test_stops_at_images() {
+        let data = history_file(|w| {
+            w.string("string").string("kept");
+            w.string("image").byte_array(b"\x89PNG");
+            w.string("string").string("unreachable");
+        });
+
+        let history = parse(&data).unwrap();
+
+        assert_eq!(history.clips.len(), 1);
+        assert_eq!(history.failed, 1);
+    }
+}
@@ -0,0 +1,183 @@
+// src-tauri/src/importer/qdatastream.rs
+//! Minimal reader for Qt's `QDataStream` serialization format.
+//!
+//! CopyQ and Klipper both store their history with `QDataStream`. Only the
+//! big-endian primitives they use are supported: integers, booleans,
+//! `QByteArray` and `QString`.
+
+// ===== Imports =====
+
+use crate::error::AppError;
+
+// ===== Constants =====
+
+/// Length marking a null `QByteArray` or `QString`.
+const NULL_LENGTH: u32 = 0xFFFF_FFFF;
+
+// ===== Domain Types =====
+
+/// Cursor over `QDataStream`-encoded bytes.
+pub(super) struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+// ===== Public API Implementation =====
+
+impl<'a> Reader<'a> {
+    /// Creates a reader positioned at the start of `data`.
+    pub(super) fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    /// Returns whether all bytes have been read.
+    pub(super) fn is_at_end(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    /// Reads `len` raw bytes.
+    pub(super) fn bytes(&mut self, len: usize) -> Result<&'a [u8], AppError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or_else(|| AppError::Import("Unexpected end of data".into()))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    /// Reads a `quint8`.
+    pub(super) fn u8(&mut self) -> Result<u8, AppError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    /// Reads a `bool`, stored as one byte.
+    pub(super) fn bool(&mut self) -> Result<bool, AppError> {
+        Ok(self.u8()? != 0)
+    }
+
+    /// Reads a big-endian `quint32`.
+    pub(super) fn u32(&mut self) -> Result<u32, AppError> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Reads a big-endian `qint32`.
+    pub(super) fn i32(&mut self) -> Result<i32, AppError> {
+        Ok(self.u32()? as i32)
+    }
+
+    /// Reads a `QByteArray`. A null array is returned as empty.
+    pub(super) fn byte_array(&mut self) -> Result<&'a [u8], AppError> {
+        match self.u32()? {
+            NULL_LENGTH => Ok(&[]),
+            len => self.bytes(len as usize),
+        }
+    }
+
+    /// Reads a `QString` (UTF-16BE). A null string is returned as empty.
+    pub(super) fn string(&mut self) -> Result<String, AppError> {
+        let len = match self.u32()? {
+            NULL_LENGTH => return Ok(String::new()),
+            len if len % 2 != 0 => {
+                return Err(AppError::Import("Invalid string length".into()));
+            }
+            len => len as usize,
+        };
+
+        let units: Vec<u16> = self
+            .bytes(len)?
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16(&units).map_err(|_| AppError::Import("Invalid UTF-16 string".into()))
+    }
+
+    /// Reads a length-prefixed `char*`, whose length includes the trailing NUL.
+    pub(super) fn c_string(&mut self) -> Result<String, AppError> {
+        let bytes = self.byte_array()?;
+        let bytes = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+}
+
+// ===== Test Helpers =====
+
+/// Writer producing `QDataStream` bytes, used to build test fixtures.
+#[cfg(test)]
+#[derive(Default)]
+pub(super) struct Writer {
+    pub(super) data: Vec<u8>,
+}
+
+#[cfg(test)]
+impl Writer {
+    pub(super) fn u8(&mut self, value: u8) -> &mut Self {
+        self.data.push(value);
+        self
+    }
+
+    pub(super) fn bool(&mut self, value: bool) -> &mut Self {
+        self.u8(value as u8)
+    }
+
+    pub(super) fn u32(&mut self, value: u32) -> &mut Self {
+        self.data.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    pub(super) fn i32(&mut self, value: i32) -> &mut Self {
+        self.u32(value as u32)
+    }
+
+    pub(super) fn byte_array(&mut self, value: &[u8]) -> &mut Self {
+        self.u32(value.len() as u32);
+        self.data.extend_from_slice(value);
+        self
+    }
+
+    pub(super) fn string(&mut self, value: &str) -> &mut Self {
+        let units: Vec<u16> = value.encode_utf16().collect();
+        self.u32(units.len() as u32 * 2);
+        for unit in units {
+            self.data.extend_from_slice(&unit.to_be_bytes());
+        }
+        self
+    }
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_primitives_written_by_writer() {
+        let mut writer = Writer::default();
+        writer
+            .i32(-2)
+            .bool(true)
+            .string("héllo 👋")
+            .byte_array(b"raw")
+            .u32(NULL_LENGTH);
+
+        let mut reader = Reader::new(&writer.data);
+        assert_eq!(reader.i32().unwrap(), -2);
+        assert!(reader.bool().unwrap());
+        assert_eq!(reader.string().unwrap(), "héllo 👋");
+        assert_eq!(reader.byte_array().unwrap(), b"raw");
+        assert_eq!(reader.string().unwrap(), "");
+        assert!(reader.is_at_end());
+    }
+
+    #[test]
+    fn test_truncated_data_is_an_error() {
+        let mut writer = Writer::default();
+        writer.u32(10).u8(0);
+
+        let mut reader = Reader::new(&writer.data);
+        assert!(reader.byte_array().is_err());
+    }
+}
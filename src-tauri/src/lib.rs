@@ -15,6 +15,10 @@
 //! - **Commands**: Tauri IPC handlers (`command` module).
 //! - **Services**: Background logic (e.g., clipboard monitoring in `service`).
 //! - **Error**: Application-specific error types (`error` module).
+//! - **Export**: Writing clip history to JSON, CSV or Markdown files (`export` module).
+//! - **Importer**: Reading history from other clipboard managers (`importer` module).
+//! - **Backup**: Rotating database backups and restores (`backup` module).
+//! - **Template**: Placeholder expansion for snippet clips (`template` module).
 
 // ===== Global Allocator =====
 
@@ -27,15 +31,19 @@ static GLOBAL: MiMalloc = MiMalloc;
 
 // ===== Modules =====
 
+pub mod backup;
 pub mod clipboard;
 pub mod command;
 pub mod config;
 pub mod context;
 pub mod core;
 pub mod error;
+pub mod export;
+pub mod importer;
 pub mod service;
 pub mod state;
 pub mod storage;
+pub mod template;
 
 // ===== Imports =====
 
@@ -113,10 +121,55 @@ pub fn run() {
         // Register all IPC commands exposed to the frontend.
         .invoke_handler(tauri::generate_handler![
             command::list_recent_clips,
+            command::list_clips_page,
+            command::search_clips,
+            command::list_tags,
+            command::clip_stats,
+            command::list_collections,
+            command::create_collection,
+            command::rename_collection,
+            command::delete_collection,
+            command::add_clip_to_collection,
+            command::add_clips_to_collection,
+            command::remove_clip_from_collection,
+            command::move_clip_in_collection,
+            command::list_collection_clips,
+            command::list_clips_by_tags,
             command::toggle_pin_status,
+            command::set_clips_pinned,
+            command::update_clip_content,
+            command::list_clip_revisions,
+            command::revert_clip_revision,
+            command::add_clip_tag,
+            command::add_clips_tag,
+            command::remove_clip_tag,
+            command::set_clip_note,
+            command::set_snippet,
+            command::set_clip_expiry,
+            command::validate_snippet,
+            command::snippet_inputs,
             command::remove_clip,
+            command::remove_clips,
             command::clear_clip_history,
+            command::list_trash,
+            command::restore_clip,
+            command::restore_all,
+            command::undo_delete,
+            command::empty_trash,
+            command::export_clips,
+            command::import_history,
+            command::list_backups,
+            command::create_backup,
+            command::restore_backup,
+            command::storage_status,
             command::ignore_next_clip,
+            command::use_clip,
+            command::use_snippet,
+            command::get_clip_content,
+            command::encryption_status,
+            command::enable_encryption,
+            command::unlock_history,
+            command::lock_history,
             command::load_settings,
             command::save_settings,
             command::mark_onboarding_complete,
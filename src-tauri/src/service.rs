@@ -7,12 +7,28 @@
 //! - Tauri application state (`AppState`).
 //!
 //! Each submodule encapsulates a specific concern:
+//! - [`backup`]: Creating, listing and restoring database backups.
 //! - [`clip`]: Clipboard history operations (CRUD, pinning).
+//! - [`collection`]: Named, ordered collections of clips.
+//! - [`encryption`]: Encryption at rest, locking and unlocking the history.
+//! - [`expiry`]: Clips that expire or are deleted after use.
+//! - [`export`]: Exporting the history, or a filtered part of it, to a file.
+//! - [`import`]: Importing history from other clipboard managers or exports.
 //! - [`settings`]: Configuration management with side effects
 //!   (e.g., global shortcuts, autostart).
+//! - [`snippet`]: Snippets, whose templates are validated and expanded on paste.
+//! - [`stats`]: Usage statistics over the clip history.
 //!
 //! Services act as an anti-corruption layer between Tauri commands and domain logic,
 //! ensuring clean separation of concerns and testable behavior.
 
+pub mod backup;
 pub mod clip;
+pub mod collection;
+pub mod encryption;
+pub mod expiry;
+pub mod export;
+pub mod import;
 pub mod settings;
+pub mod snippet;
+pub mod stats;
@@ -0,0 +1,103 @@
+// src-tauri/src/service/backup.rs
+//! Service layer for database backups.
+
+use chrono::Utc;
+use tracing::info;
+
+use crate::{
+    backup::{self, backup_dir, BackupInfo},
+    error::AppError,
+    service::{expiry::wake_expiry_scheduler, settings::read_settings_from_app_state},
+    state::AppState,
+};
+
+/// Lists the available backups, newest first.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Backup`] if the backup directory cannot be read.
+pub fn list_backups() -> Result<Vec<BackupInfo>, AppError> {
+    backup::list_backups(&backup_dir())
+}
+
+/// Backs up the database now, then rotates out old backups.
+///
+/// Compression and the number of backups kept follow the user's settings.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Backup`] if the backup cannot be written or the
+/// history is only kept in memory, or a [`AppError::Config`] if the settings
+/// cannot be read.
+pub fn create_backup(app_state: &AppState) -> Result<BackupInfo, AppError> {
+    ensure_persistent(app_state)?;
+    let settings = read_settings_from_app_state(app_state)?;
+    let dir = backup_dir();
+
+    let backup = backup::create_backup(
+        &app_state.clip_store,
+        &dir,
+        settings.compress_backups,
+        Utc::now(),
+    )?;
+    backup::rotate_backups(&dir, settings.backup_keep_count)?;
+
+    Ok(backup)
+}
+
+/// Replaces the database with the backup named `file_name`.
+///
+/// The clipboard watcher is paused during the restore so nothing is captured
+/// into the database while it is being swapped. The current database is
+/// backed up first; that backup is returned. If the restored database is
+/// encrypted, the history is locked afterwards.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Backup`] if the backup does not exist or cannot be
+/// restored, in which case the current database is kept, or if the history is
+/// only kept in memory. Returns a [`AppError::Config`] if the settings cannot be
+/// read.
+pub fn restore_backup(app_state: &AppState, file_name: &str) -> Result<BackupInfo, AppError> {
+    ensure_persistent(app_state)?;
+    let settings = read_settings_from_app_state(app_state)?;
+
+    let watcher = app_state
+        .watcher_handle
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if let Some(handle) = watcher.as_ref() {
+        handle.pause();
+    }
+
+    let result = backup::restore_backup(
+        &app_state.clip_store,
+        &backup_dir(),
+        file_name,
+        settings.compress_backups,
+        Utc::now(),
+    );
+
+    if let Some(handle) = watcher.as_ref() {
+        handle.resume();
+    }
+
+    if result.is_ok() {
+        info!("Clip history restored from backup {}", file_name);
+        wake_expiry_scheduler(app_state);
+    }
+    result
+}
+
+/// Refuses backup operations while the history is kept in memory only.
+///
+/// A backup of the in-memory fallback would rotate out backups of the real
+/// history, and a restore into it would be lost on quit.
+fn ensure_persistent(app_state: &AppState) -> Result<(), AppError> {
+    if app_state.storage_status.is_degraded() {
+        return Err(AppError::Backup(
+            "The clip database could not be opened; history is not being saved".into(),
+        ));
+    }
+    Ok(())
+}
@@ -5,45 +5,352 @@
 //! and the persistence layer (`ClipStore`), translating SQLite errors into
 //! application-level `AppError`s.
 
-use crate::{error::AppError, state::AppState, storage::Clip};
+use tracing::debug;
+
+use crate::{
+    config::LockedCapture,
+    context::auto_tags::generate_auto_tags,
+    error::AppError,
+    service::{
+        encryption::queue_locked_clip,
+        expiry::{apply_expiry_rules, wake_expiry_scheduler},
+        settings::read_settings_from_app_state,
+    },
+    state::AppState,
+    storage::{
+        Clip, ClipCursor, ClipOrder, ClipPage, ClipQuery, ClipRevision, ClipSearchResult,
+        ClipSelection, SaveOutcome, TagCount,
+    },
+};
 
 /// stores clip into storage
 ///
+/// When `is_dedupe_enabled` is set in the settings, copying content that is
+/// already stored bumps the existing clip instead of inserting a new one.
+/// Clips that an expiry rule makes expire or delete after use are always
+/// inserted, so every copy gets its own expiry rather than bumping an earlier
+/// clip that may never expire.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Config`] if the settings cannot be read, or a
+/// [`AppError::Storage`] if the database query fails.
+pub fn save_clip(app_state: &AppState, mut clip: Clip) -> Result<SaveOutcome, AppError> {
+    let settings = read_settings_from_app_state(app_state)?;
+    let expires = apply_expiry_rules(&settings.expiry_rules, &mut clip);
+
+    let result = if settings.is_dedupe_enabled && !expires {
+        app_state.clip_store.save_or_bump_clip(clip)
+    } else {
+        app_state
+            .clip_store
+            .save_clip(clip)
+            .map(SaveOutcome::Inserted)
+    };
+
+    let outcome = result.map_err(|e| AppError::Storage(e.to_string()))?;
+    if expires {
+        wake_expiry_scheduler(app_state);
+    }
+    Ok(outcome)
+}
+
+/// Stores a freshly captured clip, deferring it while the history is locked.
+///
+/// While the encrypted history is locked, the clip is queued until unlock or
+/// discarded, depending on the `locked_capture` setting, and `None` is returned.
+///
+/// # Errors
+///
+/// Same as [`save_clip`].
+pub fn capture_clip(app_state: &AppState, clip: Clip) -> Result<Option<SaveOutcome>, AppError> {
+    if !app_state.clip_store.is_locked() {
+        return save_clip(app_state, clip).map(Some);
+    }
+
+    match read_settings_from_app_state(app_state)?.locked_capture {
+        LockedCapture::Queue => queue_locked_clip(app_state, clip),
+        LockedCapture::Pause => debug!("History is locked; discarding captured clip"),
+    }
+
+    Ok(None)
+}
+
+/// Retrieves clips from storage in the given order, up to the specified limit.
+///
+/// If `query` is provided and non-empty, it is parsed with [`ClipQuery::parse`]
+/// and only matching clips are returned.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Query`] if the query is malformed, or a
+/// [`AppError::Storage`] if the database query fails.
+pub fn list_recent_clips(
+    app_state: &AppState,
+    limit: i32,
+    query: Option<&str>,
+    order: ClipOrder,
+) -> Result<Vec<Clip>, AppError> {
+    let query = match query {
+        Some(q) => ClipQuery::parse(q)?,
+        None => ClipQuery::default(),
+    };
+
+    let result = if query.is_empty() {
+        app_state.clip_store.list_clips(order, limit)
+    } else {
+        app_state.clip_store.query_clips(&query, order, limit)
+    };
+
+    result.map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Retrieves one page of clips, newest first, optionally filtered by `query`.
+///
+/// Pass `None` as `cursor` for the first page, then the previous page's
+/// `next_cursor`.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Query`] if the query is malformed, or a
+/// [`AppError::Storage`] if the database query fails.
+pub fn list_clips_page(
+    app_state: &AppState,
+    query: Option<&str>,
+    cursor: Option<&ClipCursor>,
+    page_size: i32,
+) -> Result<ClipPage, AppError> {
+    let query = match query {
+        Some(q) => ClipQuery::parse(q)?,
+        None => ClipQuery::default(),
+    };
+
+    app_state
+        .clip_store
+        .list_clips_page(&query, cursor, page_size)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Searches clip history using the structured query language.
+///
+/// Free text is matched against the full-text index; results are ranked by
+/// relevance and include a highlighted snippet.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Query`] if the query is malformed, or a
+/// [`AppError::Storage`] if the database query fails.
+pub fn search_clips(
+    app_state: &AppState,
+    query: &str,
+    limit: i32,
+) -> Result<Vec<ClipSearchResult>, AppError> {
+    let query = ClipQuery::parse(query)?;
+
+    app_state
+        .clip_store
+        .search_clips(&query, limit)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Lists every tag in use, with the number of clips carrying it.
+///
 /// # Errors
 ///
 /// Returns a [`AppError::Storage`] if the database query fails.
-pub fn save_clip(app_state: &AppState, clip: Clip) -> Result<Clip, AppError> {
+pub fn list_tags(app_state: &AppState) -> Result<Vec<TagCount>, AppError> {
     app_state
         .clip_store
-        .save_clip(clip)
+        .list_tags()
         .map_err(|e| AppError::Storage(e.to_string()))
 }
 
-/// Retrieves the most recent clips from storage, up to the specified limit.
+/// Retrieves the most recent clips carrying all of the given tags.
 ///
 /// # Errors
 ///
 /// Returns a [`AppError::Storage`] if the database query fails.
-pub fn list_recent_clips(app_state: &AppState, limit: i32) -> Result<Vec<Clip>, AppError> {
+pub fn list_clips_by_tags(
+    app_state: &AppState,
+    tags: &[String],
+    limit: i32,
+) -> Result<Vec<Clip>, AppError> {
+    app_state
+        .clip_store
+        .list_clips_by_tags(tags, limit)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Returns the full content of a clip; listings only carry a preview of long clips.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the clip does not exist or the query fails.
+pub fn get_clip_content(app_state: &AppState, id: i32) -> Result<String, AppError> {
+    app_state
+        .clip_store
+        .get_clip_content(id)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Records that a clip was pasted back out of the history, for frecency ranking.
+///
+/// A clip marked with `delete_after_use` is deleted for good; its last state is
+/// returned.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the update fails (e.g., invalid ID).
+pub fn use_clip(app_state: &AppState, id: i32) -> Result<Clip, AppError> {
     app_state
         .clip_store
-        .list_recent_clips(limit)
+        .record_clip_use(id)
         .map_err(|e| AppError::Storage(e.to_string()))
 }
 
-/// Toggles the pinned status of a clip by ID.
+/// Toggles the pinned status of a clip by ID and returns the updated clip.
 ///
 /// # Errors
 ///
 /// Returns a [`AppError::Storage`] if the update fails (e.g., invalid ID).
-pub fn toggle_pin_status(app_state: &AppState, id: i32, is_pinned: bool) -> Result<(), AppError> {
+pub fn toggle_pin_status(app_state: &AppState, id: i32, is_pinned: bool) -> Result<Clip, AppError> {
     app_state
         .clip_store
         .toggle_pin_status(id, is_pinned)
         .map_err(|e| AppError::Storage(e.to_string()))
 }
 
-/// Deletes a clip by its database ID.
+/// Pins or unpins every selected clip in one transaction.
+///
+/// See [`clip_selection`] for how `ids` and `query` select clips.
+///
+/// # Errors
+///
+/// Returns a [`AppError::InvalidArgument`] if the selection is invalid, a
+/// [`AppError::Query`] if the query is malformed, or a [`AppError::Storage`] if
+/// the update fails.
+pub fn set_clips_pinned(
+    app_state: &AppState,
+    ids: Option<Vec<i32>>,
+    query: Option<&str>,
+    is_pinned: bool,
+) -> Result<Vec<i32>, AppError> {
+    let selection = clip_selection(ids, query)?;
+
+    app_state
+        .clip_store
+        .set_clips_pinned(selection, is_pinned)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Replaces the content of a clip, keeping the old content as a revision, and
+/// returns the updated clip.
+///
+/// Auto tags are regenerated from the new content the same way captured clips
+/// are tagged.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the content is blank, the clip does not
+/// exist, the history is locked or the update fails.
+pub fn update_clip_content(
+    app_state: &AppState,
+    id: i32,
+    content: String,
+) -> Result<Clip, AppError> {
+    app_state
+        .clip_store
+        .update_clip_content(id, content, auto_tags)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Lists the revisions of a clip, most recently replaced first.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the history is locked or the query fails.
+pub fn list_clip_revisions(app_state: &AppState, id: i32) -> Result<Vec<ClipRevision>, AppError> {
+    app_state
+        .clip_store
+        .list_clip_revisions(id)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Restores a clip's content from a revision and returns the updated clip.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the revision or its clip does not exist,
+/// the history is locked or the update fails.
+pub fn revert_clip_revision(app_state: &AppState, revision_id: i64) -> Result<Clip, AppError> {
+    app_state
+        .clip_store
+        .revert_clip_revision(revision_id, auto_tags)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Adds a user tag to a clip and returns the updated clip.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the tag is blank, the clip does not exist
+/// or the update fails.
+pub fn add_user_tag(app_state: &AppState, id: i32, tag: &str) -> Result<Clip, AppError> {
+    app_state
+        .clip_store
+        .add_user_tag(id, tag)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Removes a user tag from a clip and returns the updated clip.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the clip does not exist or the update fails.
+pub fn remove_user_tag(app_state: &AppState, id: i32, tag: &str) -> Result<Clip, AppError> {
+    app_state
+        .clip_store
+        .remove_user_tag(id, tag)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Adds a user tag to every selected clip in one transaction.
+///
+/// See [`clip_selection`] for how `ids` and `query` select clips.
+///
+/// # Errors
+///
+/// Returns a [`AppError::InvalidArgument`] if the selection is invalid, a
+/// [`AppError::Query`] if the query is malformed, or a [`AppError::Storage`] if
+/// the tag is invalid or the update fails.
+pub fn add_user_tag_to_clips(
+    app_state: &AppState,
+    ids: Option<Vec<i32>>,
+    query: Option<&str>,
+    tag: &str,
+) -> Result<Vec<i32>, AppError> {
+    let selection = clip_selection(ids, query)?;
+
+    app_state
+        .clip_store
+        .add_user_tag_to_clips(selection, tag)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Sets or clears the note on a clip and returns the updated clip.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the clip does not exist, the history is
+/// locked or the update fails.
+pub fn set_clip_note(app_state: &AppState, id: i32, note: Option<&str>) -> Result<Clip, AppError> {
+    app_state
+        .clip_store
+        .set_clip_note(id, note)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Moves a clip to the trash by its database ID.
 ///
 /// Silently succeeds if the ID does not exist.
 ///
@@ -57,16 +364,125 @@ pub fn remove_clip(app_state: &AppState, id: i32) -> Result<(), AppError> {
         .map_err(|e| AppError::Storage(e.to_string()))
 }
 
-/// Clears all clips from the history.
+/// Moves every selected clip to the trash in one transaction, as a single
+/// batch that [`undo_delete`] restores together.
 ///
-/// This operation cannot be undone.
+/// See [`clip_selection`] for how `ids` and `query` select clips.
 ///
 /// # Errors
 ///
-/// Returns a [`AppError::Storage`] if the deletion fails.
+/// Returns a [`AppError::InvalidArgument`] if the selection is invalid, a
+/// [`AppError::Query`] if the query is malformed, or a [`AppError::Storage`] if
+/// the update fails.
+pub fn remove_clips(
+    app_state: &AppState,
+    ids: Option<Vec<i32>>,
+    query: Option<&str>,
+) -> Result<Vec<i32>, AppError> {
+    let selection = clip_selection(ids, query)?;
+
+    app_state
+        .clip_store
+        .remove_clips(selection)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Moves all clips from the history to the trash.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the update fails.
 pub fn clear_clip_history(app_state: &AppState) -> Result<(), AppError> {
     app_state
         .clip_store
         .clear_clip_history()
         .map_err(|e| AppError::Storage(e.to_string()))
 }
+
+/// Lists trashed clips, most recently deleted first.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the database query fails.
+pub fn list_trash(app_state: &AppState, limit: i32) -> Result<Vec<Clip>, AppError> {
+    app_state
+        .clip_store
+        .list_trash(limit)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Restores a trashed clip by ID.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the clip is not in the trash or the update fails.
+pub fn restore_clip(app_state: &AppState, id: i32) -> Result<Clip, AppError> {
+    app_state
+        .clip_store
+        .restore_clip(id)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Restores every trashed clip.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the update fails.
+pub fn restore_all(app_state: &AppState) -> Result<Vec<Clip>, AppError> {
+    app_state
+        .clip_store
+        .restore_all()
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Reverses the most recent deletion or history clear.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the update fails.
+pub fn undo_delete(app_state: &AppState) -> Result<Vec<Clip>, AppError> {
+    app_state
+        .clip_store
+        .undo_last_deletion()
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Permanently deletes every trashed clip. Returns the number deleted.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the deletion fails.
+pub fn empty_trash(app_state: &AppState) -> Result<usize, AppError> {
+    app_state
+        .clip_store
+        .empty_trash()
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Generates auto tags for edited content, as for a clip captured from `app_name`.
+fn auto_tags(content: &str, app_name: &str) -> Vec<String> {
+    generate_auto_tags(content, Some(app_name))
+}
+
+/// Builds the selection for a bulk operation from either a list of clip IDs or a
+/// query in the structured query language.
+///
+/// Exactly one of `ids` and `query` must be given. An empty query selects every
+/// clip in the history.
+///
+/// # Errors
+///
+/// Returns a [`AppError::InvalidArgument`] if neither or both of `ids` and
+/// `query` are given, or a [`AppError::Query`] if the query is malformed.
+pub(crate) fn clip_selection(
+    ids: Option<Vec<i32>>,
+    query: Option<&str>,
+) -> Result<ClipSelection, AppError> {
+    match (ids, query) {
+        (Some(ids), None) => Ok(ClipSelection::Ids(ids)),
+        (None, Some(q)) => Ok(ClipSelection::Query(ClipQuery::parse(q)?)),
+        _ => Err(AppError::InvalidArgument(
+            "Select clips either by ids or by query".into(),
+        )),
+    }
+}
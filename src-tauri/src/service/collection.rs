@@ -0,0 +1,158 @@
+// src-tauri/src/service/collection.rs
+//! Service layer for clip collections.
+//!
+//! These functions map storage errors into [`AppError::Storage`] so commands can
+//! report them uniformly.
+
+use crate::{
+    error::AppError,
+    service::clip::clip_selection,
+    state::AppState,
+    storage::{Clip, Collection},
+};
+
+/// Lists every collection, ordered by name.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the database query fails.
+pub fn list_collections(app_state: &AppState) -> Result<Vec<Collection>, AppError> {
+    app_state
+        .clip_store
+        .list_collections()
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Creates an empty collection called `name`.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the name is blank or already taken, or if
+/// the insert fails.
+pub fn create_collection(app_state: &AppState, name: &str) -> Result<Collection, AppError> {
+    app_state
+        .clip_store
+        .create_collection(name)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Renames the collection with the given ID.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the collection does not exist, the name is
+/// blank or already taken, or the update fails.
+pub fn rename_collection(
+    app_state: &AppState,
+    id: i32,
+    name: &str,
+) -> Result<Collection, AppError> {
+    app_state
+        .clip_store
+        .rename_collection(id, name)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Deletes the collection with the given ID, keeping its clips.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the collection does not exist or the
+/// deletion fails.
+pub fn delete_collection(app_state: &AppState, id: i32) -> Result<(), AppError> {
+    app_state
+        .clip_store
+        .delete_collection(id)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Adds a clip to the end of a collection.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the collection or clip does not exist, or
+/// the insert fails.
+pub fn add_clip_to_collection(
+    app_state: &AppState,
+    collection_id: i32,
+    clip_id: i32,
+) -> Result<Collection, AppError> {
+    app_state
+        .clip_store
+        .add_clip_to_collection(collection_id, clip_id)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Appends every selected clip to the end of a collection in one transaction.
+///
+/// Returns the updated collection and the IDs of the clips that were added;
+/// see [`clip_selection`] for how `ids` and `query` select clips.
+///
+/// # Errors
+///
+/// Returns a [`AppError::InvalidArgument`] if the selection is invalid, a
+/// [`AppError::Query`] if the query is malformed, or a [`AppError::Storage`] if
+/// the collection does not exist or the insert fails.
+pub fn add_clips_to_collection(
+    app_state: &AppState,
+    collection_id: i32,
+    ids: Option<Vec<i32>>,
+    query: Option<&str>,
+) -> Result<(Collection, Vec<i32>), AppError> {
+    let selection = clip_selection(ids, query)?;
+
+    app_state
+        .clip_store
+        .add_clips_to_collection(collection_id, selection)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Removes a clip from a collection.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the collection does not exist or the
+/// deletion fails.
+pub fn remove_clip_from_collection(
+    app_state: &AppState,
+    collection_id: i32,
+    clip_id: i32,
+) -> Result<Collection, AppError> {
+    app_state
+        .clip_store
+        .remove_clip_from_collection(collection_id, clip_id)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Moves a clip to `index` within its collection.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the clip is not in the collection or the
+/// update fails.
+pub fn move_clip_in_collection(
+    app_state: &AppState,
+    collection_id: i32,
+    clip_id: i32,
+    index: usize,
+) -> Result<Collection, AppError> {
+    app_state
+        .clip_store
+        .move_clip_in_collection(collection_id, clip_id, index)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Lists the clips in a collection, in collection order.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the database query fails.
+pub fn list_collection_clips(
+    app_state: &AppState,
+    collection_id: i32,
+) -> Result<Vec<Clip>, AppError> {
+    app_state
+        .clip_store
+        .list_collection_clips(collection_id)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
@@ -0,0 +1,97 @@
+// src-tauri/src/service/encryption.rs
+//! Service layer for encryption at rest.
+//!
+//! Wraps enabling, locking and unlocking the encrypted clip history, and manages
+//! the queue of clips captured while the history is locked.
+
+use tracing::{error, info, warn};
+
+use crate::{
+    error::AppError,
+    service::clip::save_clip,
+    state::AppState,
+    storage::{Clip, EncryptionStatus, SaveOutcome},
+};
+
+/// Maximum number of clips kept while the history is locked; the oldest are dropped first.
+pub const MAX_QUEUED_CLIPS: usize = 500;
+
+/// Returns whether encryption is enabled and whether the history is locked.
+pub fn encryption_status(app_state: &AppState) -> EncryptionStatus {
+    app_state.clip_store.encryption_status()
+}
+
+/// Enables encryption with the given passphrase, encrypting existing clips in place.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Encryption`] if encryption is already enabled, the
+/// passphrase is empty, or the existing clips cannot be encrypted.
+pub fn enable_encryption(
+    app_state: &AppState,
+    passphrase: &str,
+) -> Result<EncryptionStatus, AppError> {
+    app_state.clip_store.enable_encryption(passphrase)?;
+    Ok(encryption_status(app_state))
+}
+
+/// Unlocks the history and saves the clips captured while it was locked.
+///
+/// Returns the outcome of each queued clip that was saved, oldest first.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Encryption`] if encryption is not enabled or the
+/// passphrase is wrong. Queued clips that fail to save are logged and dropped.
+pub fn unlock(app_state: &AppState, passphrase: &str) -> Result<Vec<SaveOutcome>, AppError> {
+    app_state.clip_store.unlock(passphrase)?;
+
+    let queued: Vec<Clip> = app_state
+        .locked_clip_queue
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .drain(..)
+        .collect();
+
+    if !queued.is_empty() {
+        info!("Saving {} clips captured while locked", queued.len());
+    }
+
+    let outcomes = queued
+        .into_iter()
+        .filter_map(|clip| match save_clip(app_state, clip) {
+            Ok(outcome) => Some(outcome),
+            Err(e) => {
+                error!("Failed to save queued clip: {}", e);
+                None
+            }
+        })
+        .collect();
+
+    Ok(outcomes)
+}
+
+/// Locks the history, forgetting the encryption key.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Encryption`] if encryption is not enabled.
+pub fn lock(app_state: &AppState) -> Result<EncryptionStatus, AppError> {
+    app_state.clip_store.lock()?;
+    Ok(encryption_status(app_state))
+}
+
+/// Queues a clip captured while the history is locked, dropping the oldest
+/// queued clip once [`MAX_QUEUED_CLIPS`] is reached.
+pub fn queue_locked_clip(app_state: &AppState, clip: Clip) {
+    let mut queue = app_state
+        .locked_clip_queue
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+
+    if queue.len() >= MAX_QUEUED_CLIPS {
+        warn!("Locked capture queue is full; dropping the oldest clip");
+        queue.pop_front();
+    }
+    queue.push_back(clip);
+}
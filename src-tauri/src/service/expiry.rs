@@ -0,0 +1,138 @@
+// src-tauri/src/service/expiry.rs
+//! Service layer for self-destructing clips.
+//!
+//! Clips expire at a set time, are deleted after their first use, or both. The
+//! times are enforced by the scheduler in [`crate::core::expiry`], which these
+//! functions wake whenever an expiry changes.
+
+use chrono::{Duration, Utc};
+
+use crate::{config::ExpiryRule, error::AppError, state::AppState, storage::Clip};
+
+/// Makes a clip expire `ttl_seconds` from now, or never if `None`, and sets
+/// whether it is deleted after its next use. Returns the updated clip.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the clip does not exist, is in the trash,
+/// or the update fails.
+pub fn set_clip_expiry(
+    app_state: &AppState,
+    id: i32,
+    ttl_seconds: Option<u32>,
+    delete_after_use: bool,
+) -> Result<Clip, AppError> {
+    let expires_at = ttl_seconds.map(|ttl| Utc::now() + Duration::seconds(ttl.into()));
+
+    let clip = app_state
+        .clip_store
+        .set_clip_expiry(id, expires_at, delete_after_use)
+        .map_err(|e| AppError::Storage(e.to_string()))?;
+    wake_expiry_scheduler(app_state);
+
+    Ok(clip)
+}
+
+/// Applies the first rule matching the clip's app, counting its TTL from the
+/// time the clip was copied.
+///
+/// Returns whether the clip now expires or is deleted after use. A matching rule
+/// that sets neither leaves the clip to be kept (and deduplicated) as usual.
+pub(crate) fn apply_expiry_rules(rules: &[ExpiryRule], clip: &mut Clip) -> bool {
+    let Some(rule) = rules
+        .iter()
+        .find(|rule| rule.app_name.trim().eq_ignore_ascii_case(&clip.app_name))
+    else {
+        return false;
+    };
+
+    clip.expires_at = rule
+        .ttl_seconds
+        .map(|ttl| clip.created_at + Duration::seconds(ttl.into()));
+    clip.delete_after_use = rule.delete_after_use;
+    clip.expires_at.is_some() || clip.delete_after_use
+}
+
+/// Makes the expiry scheduler recompute when the next clip expires.
+pub(crate) fn wake_expiry_scheduler(app_state: &AppState) {
+    app_state.expiry_wake.notify_one();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Settings;
+    use crate::service::clip::save_clip;
+    use crate::storage::{ClipStore, SaveOutcome, StorageStatus};
+    use std::collections::VecDeque;
+    use std::sync::{atomic::AtomicBool, Arc, Mutex, RwLock};
+    use tokio::sync::Notify;
+
+    fn app_state(expiry_rules: Vec<ExpiryRule>) -> AppState {
+        let settings = Settings {
+            is_dedupe_enabled: true,
+            expiry_rules,
+            ..Settings::default()
+        };
+        AppState {
+            watcher_handle: Arc::new(Mutex::new(None)),
+            settings: Arc::new(RwLock::new(settings)),
+            quick_picker_shortcut: Arc::new(RwLock::new(None)),
+            clip_store: Arc::new(ClipStore::new(":memory:").expect("In-memory store")),
+            storage_status: Arc::new(StorageStatus::Healthy),
+            is_quick_picker_dragging: Arc::new(AtomicBool::new(false)),
+            locked_clip_queue: Arc::new(Mutex::new(VecDeque::new())),
+            paste_target_app: Arc::new(Mutex::new(None)),
+            expiry_wake: Arc::new(Notify::new()),
+        }
+    }
+
+    fn rule(ttl_seconds: Option<u32>, delete_after_use: bool) -> ExpiryRule {
+        ExpiryRule {
+            app_name: "Authenticator".into(),
+            ttl_seconds,
+            delete_after_use,
+        }
+    }
+
+    fn copy(app_state: &AppState, content: &str) -> SaveOutcome {
+        let clip = Clip::new(
+            content.into(),
+            "Authenticator".into(),
+            "Win".into(),
+            vec![],
+            false,
+        );
+        save_clip(app_state, clip).unwrap()
+    }
+
+    #[test]
+    fn test_recopy_under_no_op_rule_is_deduplicated() {
+        let app_state = app_state(vec![rule(None, false)]);
+
+        assert!(matches!(
+            copy(&app_state, "123456"),
+            SaveOutcome::Inserted(_)
+        ));
+        match copy(&app_state, "123456") {
+            SaveOutcome::Bumped(clip) => {
+                assert_eq!(clip.expires_at, None);
+                assert!(!clip.delete_after_use);
+            }
+            SaveOutcome::Inserted(_) => panic!("Expected the clip to be bumped"),
+        }
+    }
+
+    #[test]
+    fn test_recopy_under_expiry_rule_is_inserted() {
+        for rule in [rule(Some(30), false), rule(None, true)] {
+            let app_state = app_state(vec![rule]);
+
+            copy(&app_state, "123456");
+            assert!(matches!(
+                copy(&app_state, "123456"),
+                SaveOutcome::Inserted(_)
+            ));
+        }
+    }
+}
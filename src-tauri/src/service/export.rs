@@ -0,0 +1,53 @@
+// src-tauri/src/service/export.rs
+//! Service layer for exporting clip history.
+
+use std::path::Path;
+
+use chrono::Utc;
+use tracing::info;
+
+use crate::{
+    error::AppError,
+    export::{render, write_atomically, ExportFormat, ExportSummary},
+    state::AppState,
+    storage::ClipQuery,
+};
+
+/// Exports the clips matching `query` (all clips if `None`) to `path`.
+///
+/// Clips are written with their full content, oldest first. Trashed clips are
+/// not exported. An existing file at `path` is replaced atomically.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Query`] if the query is malformed, a [`AppError::Storage`]
+/// if the clips cannot be read (e.g., the history is locked), or a
+/// [`AppError::Export`] if the file cannot be written.
+pub fn export_clips(
+    app_state: &AppState,
+    path: &Path,
+    format: ExportFormat,
+    query: Option<&str>,
+) -> Result<ExportSummary, AppError> {
+    let query = ClipQuery::parse(query.unwrap_or_default())?;
+
+    let clips = app_state
+        .clip_store
+        .query_full_clips(&query)
+        .map_err(|e| AppError::Storage(e.to_string()))?;
+
+    let contents = render(&clips, format, Utc::now())?;
+    write_atomically(path, contents.as_bytes())?;
+
+    info!(
+        "Exported {} clips as {:?} to {}",
+        clips.len(),
+        format,
+        path.display()
+    );
+
+    Ok(ExportSummary {
+        count: clips.len(),
+        path: path.display().to_string(),
+    })
+}
@@ -0,0 +1,62 @@
+// src-tauri/src/service/import.rs
+//! Service layer for importing history from other clipboard managers.
+
+use std::{fs, path::Path};
+
+use tracing::info;
+
+use crate::{
+    error::AppError,
+    importer::{default_path, parse, ImportSource},
+    service::expiry::wake_expiry_scheduler,
+    state::AppState,
+    storage::ImportReport,
+};
+
+/// Imports the history file of `source` at `path`, or at the source's default
+/// location if `path` is `None`.
+///
+/// Clips whose content is already in the history are skipped. The report counts
+/// them together with unsupported entries (e.g., images), and counts entries
+/// that could not be decoded as failed.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Import`] if no path is given for a source without a
+/// default location, or if the file cannot be read or is not in the expected
+/// format, and a [`AppError::Storage`] if the clips cannot be stored (e.g., the
+/// history is locked).
+pub fn import_history(
+    app_state: &AppState,
+    source: ImportSource,
+    path: Option<&Path>,
+) -> Result<ImportReport, AppError> {
+    let path = match path {
+        Some(path) => path.to_path_buf(),
+        None => default_path(source)
+            .ok_or_else(|| AppError::Import(format!("No history file given for {:?}", source)))?,
+    };
+
+    let data = fs::read(&path)
+        .map_err(|e| AppError::Import(format!("Failed to read {}: {}", path.display(), e)))?;
+    let history = parse(source, &data)?;
+
+    let mut report = app_state
+        .clip_store
+        .import_clips(history.clips)
+        .map_err(|e| AppError::Storage(e.to_string()))?;
+    report.skipped += history.skipped;
+    report.failed += history.failed;
+    wake_expiry_scheduler(app_state);
+
+    info!(
+        "Imported {} clips from {:?} history at {} ({} skipped, {} failed)",
+        report.imported,
+        source,
+        path.display(),
+        report.skipped,
+        report.failed
+    );
+
+    Ok(report)
+}
@@ -178,8 +178,10 @@ mod tests {
     use super::*;
     use crate::config::ShortcutConfig;
     use crate::state::AppState;
-    use crate::storage::ClipStore;
+    use crate::storage::{ClipStore, StorageStatus};
+    use std::collections::VecDeque;
     use std::sync::{atomic::AtomicBool, Arc, Mutex, RwLock};
+    use tokio::sync::Notify;
 
     #[test]
     fn test_read_write_settings() {
@@ -189,7 +191,11 @@ mod tests {
             settings: Arc::new(RwLock::new(initial.clone())),
             quick_picker_shortcut: Arc::new(RwLock::new(None)),
             clip_store: Arc::new(ClipStore::new(":memory:").expect("In-memory store")),
+            storage_status: Arc::new(StorageStatus::Healthy),
             is_quick_picker_dragging: Arc::new(AtomicBool::new(false)),
+            locked_clip_queue: Arc::new(Mutex::new(VecDeque::new())),
+            paste_target_app: Arc::new(Mutex::new(None)),
+            expiry_wake: Arc::new(Notify::new()),
         };
 
         let read = read_settings_from_app_state(&app_state).unwrap();
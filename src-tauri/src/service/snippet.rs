@@ -0,0 +1,92 @@
+// src-tauri/src/service/snippet.rs
+//! Service layer for snippets: clips whose content is a template expanded on paste.
+//!
+//! A clip's content is validated as a [`Template`] before it can be marked as a
+//! snippet; see [`crate::template`] for the placeholder syntax.
+
+use std::collections::HashMap;
+
+use chrono::Local;
+
+use crate::{
+    context::app_info::AppInfo,
+    error::AppError,
+    service::clip::{get_clip_content, use_clip},
+    state::AppState,
+    storage::Clip,
+    template::{Template, TemplateContext},
+};
+
+/// Marks a clip as a snippet, or back as a plain clip, and returns the updated clip.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Template`] if the clip's content is not a valid
+/// template, or a [`AppError::Storage`] if the clip does not exist or the
+/// update fails.
+pub fn set_snippet(app_state: &AppState, id: i32, is_snippet: bool) -> Result<Clip, AppError> {
+    if is_snippet {
+        Template::parse(&get_clip_content(app_state, id)?)?;
+    }
+
+    app_state
+        .clip_store
+        .set_snippet(id, is_snippet)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
+
+/// Validates `content` as a snippet template and returns the labels of its
+/// `{input:<label>}` placeholders, in order.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Template`] describing the first syntax error.
+pub fn validate_template(content: &str) -> Result<Vec<String>, AppError> {
+    let template = Template::parse(content)?;
+    Ok(template.inputs().into_iter().map(str::to_string).collect())
+}
+
+/// Returns the labels of the values the user must type in to paste a snippet.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Template`] if the snippet is not a valid template, or
+/// a [`AppError::Storage`] if the clip cannot be loaded.
+pub fn snippet_inputs(app_state: &AppState, id: i32) -> Result<Vec<String>, AppError> {
+    validate_template(&get_clip_content(app_state, id)?)
+}
+
+/// Expands a snippet for pasting and records the use, returning the updated clip
+/// and the expanded text.
+///
+/// `clipboard` is the current clipboard text and `inputs` the values typed in for
+/// the snippet's `{input:<label>}` placeholders. `{app}` expands to the app the
+/// quick picker was opened over.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Template`] if the snippet is not a valid template or an
+/// input is missing, or a [`AppError::Storage`] if the clip cannot be loaded.
+pub fn use_snippet(
+    app_state: &AppState,
+    id: i32,
+    clipboard: String,
+    inputs: HashMap<String, String>,
+) -> Result<(Clip, String), AppError> {
+    let template = Template::parse(&get_clip_content(app_state, id)?)?;
+    let app = app_state
+        .paste_target_app
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_else(AppInfo::unknown_app_class);
+
+    let expanded = template.expand(&TemplateContext {
+        now: Local::now(),
+        clipboard,
+        app,
+        inputs,
+    })?;
+
+    Ok((use_clip(app_state, id)?, expanded))
+}
@@ -0,0 +1,30 @@
+// src-tauri/src/service/stats.rs
+//! Service layer for clip history statistics.
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    error::AppError,
+    state::AppState,
+    storage::{ClipStats, StatsInterval},
+};
+
+/// Computes usage statistics for clips created in `[from, to)`.
+///
+/// Either bound may be `None` to leave that side of the range open.
+///
+/// # Errors
+///
+/// Returns a [`AppError::Storage`] if the statistics cannot be computed (e.g.,
+/// the history is locked).
+pub fn clip_stats(
+    app_state: &AppState,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    interval: StatsInterval,
+) -> Result<ClipStats, AppError> {
+    app_state
+        .clip_store
+        .clip_stats(from, to, interval)
+        .map_err(|e| AppError::Storage(e.to_string()))
+}
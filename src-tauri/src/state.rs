@@ -2,20 +2,27 @@
 //! Application-wide shared state management.
 //!
 //! The [`AppState`] struct holds all long-lived, shared resources required by the application:
-//! - Persistent storage (`ClipStore`)
+//! - Persistent storage (`ClipStore`) and its health at startup
 //! - User configuration (`Settings`)
 //! - Global shortcut registration
 //! - Background clipboard watcher
-//! - UI interaction state (e.g., drag tracking)
+//! - Clips captured while the encrypted history is locked
+//! - Waking the clip expiry scheduler
+//! - UI interaction state (e.g., drag tracking, the app the quick picker pastes into)
 //!
 //! It is designed to be:
 //! - **Thread-safe**: All fields are wrapped in appropriate synchronization primitives.
 //! - **Lifecycle-aware**: Gracefully shuts down background tasks on drop.
-//! - **Resilient**: Falls back to safe defaults on initialization errors.
+//! - **Resilient**: Recovers from a corrupt database, and falls back to safe defaults on
+//!   initialization errors.
 
-use std::sync::{atomic::AtomicBool, Arc, Mutex, RwLock};
+use std::{
+    collections::VecDeque,
+    sync::{atomic::AtomicBool, Arc, Mutex, RwLock},
+};
 
 use tauri_plugin_global_shortcut::Shortcut;
+use tokio::sync::Notify;
 use tracing::{error, info, warn};
 
 use crate::{
@@ -23,7 +30,7 @@ use crate::{
     config::{config_dir, Settings},
     core::global_shortcut::shortcut_from_config,
     service::settings::load_settings,
-    storage::ClipStore,
+    storage::{Clip, ClipStore, StorageStatus},
 };
 
 /// Shared application state accessible from Tauri commands and services.
@@ -33,6 +40,10 @@ use crate::{
 pub struct AppState {
     /// Persistent storage for clipboard history.
     pub clip_store: Arc<ClipStore>,
+    /// Health of the database, as determined when it was opened.
+    ///
+    /// Reported to the frontend so it can warn when the history is not being saved.
+    pub storage_status: Arc<StorageStatus>,
     /// Handle to the background clipboard watcher thread.
     ///
     /// Wrapped in `Mutex<Option<...>>` because the watcher is started after app setup.
@@ -51,6 +62,22 @@ pub struct AppState {
     /// Used to suppress clipboard capture or other logic during user-initiated window movement.
     /// Atomic for lock-free access from UI event handlers and background threads.
     pub is_quick_picker_dragging: Arc<AtomicBool>,
+
+    /// Clips captured while the encrypted history is locked, saved on unlock.
+    ///
+    /// Bounded; see [`crate::service::encryption::MAX_QUEUED_CLIPS`].
+    pub locked_clip_queue: Arc<Mutex<VecDeque<Clip>>>,
+
+    /// Name of the app that was focused when the quick picker was last opened.
+    ///
+    /// Snippets pasted from the quick picker expand `{app}` to it. `None` until
+    /// the quick picker has been opened.
+    pub paste_target_app: Arc<Mutex<Option<String>>>,
+
+    /// Wakes the clip expiry scheduler so it picks up changed expiry times.
+    ///
+    /// See [`crate::core::expiry`].
+    pub expiry_wake: Arc<Notify>,
 }
 
 impl AppState {
@@ -59,7 +86,9 @@ impl AppState {
     /// # Initialization Steps
     ///
     /// 1. Opens or creates the SQLite database at `~/.clipcontex/clipcontex.db`.
-    ///    On failure, falls back to an in-memory database.
+    ///    A corrupt database is quarantined and its readable clips salvaged into a
+    ///    fresh one. On any other failure, falls back to an in-memory database and
+    ///    marks storage as degraded.
     /// 2. Loads user settings from `~/.clipcontex/config.json`, using defaults if missing/invalid.
     /// 3. Prepares global shortcut registration (actual registration happens later).
     /// 4. Initializes UI state flags (e.g., drag tracking) to default values.
@@ -70,17 +99,23 @@ impl AppState {
     pub fn new() -> Self {
         let db_path = config_dir().join("clipcontex.db");
 
-        let store = match ClipStore::new(&db_path) {
-            Ok(store) => {
+        let (store, storage_status) = match ClipStore::open_or_recover(&db_path) {
+            Ok((store, None)) => {
                 info!("ClipStore initialized at {:?}", db_path);
-                store
+                (store, StorageStatus::Healthy)
+            }
+            Ok((store, Some(report))) => {
+                warn!("ClipStore recovered at {:?}: {:?}", db_path, report);
+                (store, StorageStatus::Recovered(report))
             }
             Err(e) => {
                 error!(
                     "Failed to initialize ClipStore at {:?}: {}. Using in-memory fallback.",
                     db_path, e
                 );
-                ClipStore::new(":memory:").expect("In-memory database must always succeed")
+                let store =
+                    ClipStore::new(":memory:").expect("In-memory database must always succeed");
+                (store, StorageStatus::Degraded { reason: e.to_string() })
             }
         };
 
@@ -96,10 +131,14 @@ impl AppState {
 
         Self {
             clip_store: Arc::new(store),
+            storage_status: Arc::new(storage_status),
             watcher_handle: Arc::new(Mutex::new(None)),
             settings: Arc::new(RwLock::new(settings)),
             quick_picker_shortcut: Arc::new(RwLock::new(initial_shortcut)),
             is_quick_picker_dragging: Arc::new(AtomicBool::new(false)),
+            locked_clip_queue: Arc::new(Mutex::new(VecDeque::new())),
+            paste_target_app: Arc::new(Mutex::new(None)),
+            expiry_wake: Arc::new(Notify::new()),
         }
     }
 }
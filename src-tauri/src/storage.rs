@@ -6,10 +6,30 @@
 //!
 //! - [`Clip`]: The domain model representing a single clipboard entry with metadata.
 //! - [`ClipStore`]: A database interface for saving, retrieving, updating, and cleaning up clips.
+//! - [`SaveOutcome`]: Whether a save inserted a new clip or bumped an existing duplicate.
+//! - [`ClipPage`]: One keyset-paginated page of clips, with a [`ClipCursor`] to the next page.
+//! - [`ClipSearchResult`]: A ranked full-text search hit with a highlighted snippet.
+//! - [`ClipQuery`]: A parsed search query (`app:firefox tag:#url after:yesterday "invoice"`).
+//! - [`ClipSelection`]: The clips a bulk delete, pin, tag or collection change applies to.
+//! - [`TagCount`]: A tag with the number of clips carrying it.
+//! - [`ClipRevision`]: An earlier version of an edited clip's content.
+//! - [`Collection`]: A named, ordered set of clips kept out of automatic cleanup.
+//! - [`ClipStats`]: Clips per app, tag and day or hour, top repeated contents and
+//!   storage use over a time range.
+//! - [`ClipOrder`]: Recency or frecency (frequency decayed by recency) ordering for listings.
+//! - [`RetentionPolicy`]: Age and size limits for automatic cleanup, with pinned clips exempt by default.
+//! - [`RetentionRule`]: Per-app, per-tag or per-size limits applied before the default ones.
+//! - [`EncryptionStatus`]: Whether clip content is encrypted at rest and whether the store is locked.
+//! - [`ImportReport`]: Imported, skipped and failed counts of a history import.
+//! - [`StorageStatus`]: Whether the database opened cleanly, was recovered from corruption, or
+//!   could not be used at all.
 //!
 //! The storage layer is designed to be:
-//! - **Efficient**: Uses indexed queries and WAL mode for performance.
+//! - **Efficient**: Uses indexed queries and WAL mode for performance. Writes run one
+//!   at a time on a dedicated writer thread while reads use a small pool of read-only
+//!   connections, so saving a clip never waits behind a long listing or search.
 //! - **Robust**: Handles edge cases like timestamp corruption gracefully.
+//! - **Upgradable**: Schema changes ship as versioned [`migrations`].
 //! - **Resource-conscious**: Supports automatic cleanup by age or maximum history size.
 //!
 //! # Example
@@ -34,11 +54,50 @@
 //! let history = store.list_recent_clips(50).unwrap();
 //! ```
 //!
-//! For automatic maintenance, call [`ClipStore::perform_cleanup`] on application startup
-//! to enforce retention policies.
+//! For automatic maintenance, call [`ClipStore::perform_cleanup`] with a [`RetentionPolicy`]
+//! on application startup to enforce retention limits.
+//!
+//! Deleting clips moves them to the trash, from which they can be restored with
+//! [`ClipStore::restore_clip`] or [`ClipStore::undo_last_deletion`] until
+//! [`ClipStore::purge_trash`] removes them for good. Clips with an expiry time or
+//! marked for deletion after use skip the trash; see [`ClipStore::delete_expired_clips`].
+//!
+//! [`ClipStore::snapshot_to`] and [`ClipStore::restore_from`] back the database up
+//! to a single file and restore it in place.
 
+mod backup;
+mod bulk;
 mod clip;
 mod clip_store;
+mod collections;
+mod encryption;
+mod expiry;
+mod import;
+pub mod migrations;
+mod pagination;
+mod query;
+mod recovery;
+mod retention;
+mod revisions;
+mod search;
+mod stats;
+mod tags;
+mod trash;
+mod usage;
+mod worker;
 
-pub use clip::Clip;
-pub use clip_store::ClipStore;
+pub use bulk::ClipSelection;
+pub use clip::{Clip, PREVIEW_CHARS};
+pub use clip_store::{ClipStore, SaveOutcome};
+pub use collections::Collection;
+pub use encryption::{EncryptionError, EncryptionStatus, KdfParams};
+pub use import::ImportReport;
+pub use pagination::{ClipCursor, ClipPage};
+pub use query::{ClipQuery, QueryError, SearchTerm};
+pub use recovery::{RecoveryReport, StorageStatus};
+pub use retention::{CleanupReport, RetentionLimits, RetentionPolicy, RetentionRule, RuleCleanup};
+pub use revisions::ClipRevision;
+pub use search::{ClipSearchResult, SNIPPET_MATCH_END, SNIPPET_MATCH_START};
+pub use stats::{AppCount, ClipStats, PeriodCount, RepeatedContent, StatsInterval};
+pub use tags::TagCount;
+pub use usage::ClipOrder;
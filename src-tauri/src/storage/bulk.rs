@@ -0,0 +1,322 @@
+// src-tauri/src/storage/bulk.rs
+//! Operations on many clips at once.
+//!
+//! Each operation takes a [`ClipSelection`] (explicit IDs or a [`ClipQuery`]),
+//! resolves it to live clips and applies the change in a single transaction, so
+//! either every selected clip is changed or none is. The IDs of the affected
+//! clips are returned for callers to report in one aggregated event.
+//!
+//! Trashed clips are never selected; unknown IDs are skipped rather than failing
+//! the whole operation.
+
+// ===== Imports =====
+
+use std::collections::HashSet;
+
+use chrono::Utc;
+use rusqlite::{params, params_from_iter, types::Value, Connection, Result as SqliteResult};
+use tracing::debug;
+
+use crate::storage::{
+    collections::fetch_collection, tags::attach_tags, trash::move_to_trash, ClipQuery, ClipStore,
+    Collection,
+};
+
+// ===== Domain Types =====
+
+/// The clips a bulk operation applies to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClipSelection {
+    /// The live clips with these IDs, in the given order.
+    Ids(Vec<i32>),
+    /// The live clips matching a query, newest first. An empty query selects
+    /// every live clip.
+    Query(ClipQuery),
+}
+
+// ===== Public API Implementation =====
+
+impl ClipStore {
+    /// Moves the selected clips to the trash as a single deletion batch, so
+    /// [`ClipStore::undo_last_deletion`] restores all of them together.
+    ///
+    /// Returns the IDs of the trashed clips.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query or update fails.
+    pub fn remove_clips(&self, selection: ClipSelection) -> SqliteResult<Vec<i32>> {
+        let use_fts = !self.is_encrypted();
+
+        self.write(move |conn| {
+            let tx = conn.transaction()?;
+            let ids = selected_ids(&tx, &selection, use_fts)?;
+            if !ids.is_empty() {
+                let (condition, values) = id_list_sql(&ids);
+                move_to_trash(&tx, &condition, values)?;
+            }
+            tx.commit()?;
+
+            debug!("Moved {} selected clips to the trash", ids.len());
+            Ok(ids)
+        })
+    }
+
+    /// Pins or unpins the selected clips.
+    ///
+    /// Returns the IDs of the selected clips.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query or update fails.
+    pub fn set_clips_pinned(
+        &self,
+        selection: ClipSelection,
+        is_pinned: bool,
+    ) -> SqliteResult<Vec<i32>> {
+        let use_fts = !self.is_encrypted();
+
+        self.write(move |conn| {
+            let tx = conn.transaction()?;
+            let ids = selected_ids(&tx, &selection, use_fts)?;
+            if !ids.is_empty() {
+                let (condition, id_values) = id_list_sql(&ids);
+                let mut values = vec![
+                    Value::Integer(is_pinned.into()),
+                    Value::Integer(Utc::now().timestamp_millis()),
+                ];
+                values.extend(id_values);
+                tx.execute(
+                    &format!("UPDATE clips SET is_pinned = ?, updated_at = ? WHERE {condition}"),
+                    params_from_iter(values),
+                )?;
+            }
+            tx.commit()?;
+            Ok(ids)
+        })
+    }
+
+    /// Adds a user tag to the selected clips. Clips already carrying it are
+    /// left as they are but still reported.
+    ///
+    /// Returns the IDs of the selected clips.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `tag` is blank or the update fails.
+    pub fn add_user_tag_to_clips(
+        &self,
+        selection: ClipSelection,
+        tag: &str,
+    ) -> SqliteResult<Vec<i32>> {
+        let tag = tag.trim().to_string();
+        if tag.is_empty() {
+            return Err(rusqlite::Error::ToSqlConversionFailure(
+                "Tags cannot be empty".into(),
+            ));
+        }
+        let use_fts = !self.is_encrypted();
+
+        self.write(move |conn| {
+            let tx = conn.transaction()?;
+            let ids = selected_ids(&tx, &selection, use_fts)?;
+            let now = Utc::now().timestamp_millis();
+            for &id in &ids {
+                attach_tags(&tx, id, std::slice::from_ref(&tag), true)?;
+                tx.execute(
+                    "UPDATE clips SET updated_at = ?1 WHERE id = ?2",
+                    params![now, id],
+                )?;
+            }
+            tx.commit()?;
+            Ok(ids)
+        })
+    }
+
+    /// Appends the selected clips to the end of a collection, in selection order.
+    ///
+    /// Returns the updated collection and the IDs of the clips that were added;
+    /// clips already in the collection keep their position and are not reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`rusqlite::Error::QueryReturnedNoRows`] if no collection has the
+    /// given ID, or another error if the insertion fails.
+    pub fn add_clips_to_collection(
+        &self,
+        collection_id: i32,
+        selection: ClipSelection,
+    ) -> SqliteResult<(Collection, Vec<i32>)> {
+        let use_fts = !self.is_encrypted();
+
+        self.write(move |conn| {
+            let tx = conn.transaction()?;
+            fetch_collection(&tx, collection_id)?;
+
+            let mut added = Vec::new();
+            for id in selected_ids(&tx, &selection, use_fts)? {
+                let inserted = tx.execute(
+                    r#"
+                    INSERT OR IGNORE INTO collection_clips (collection_id, clip_id, position)
+                    SELECT ?1, ?2, coalesce(max(position) + 1, 0)
+                    FROM collection_clips
+                    WHERE collection_id = ?1
+                    "#,
+                    params![collection_id, id],
+                )?;
+                if inserted > 0 {
+                    added.push(id);
+                }
+            }
+
+            let collection = fetch_collection(&tx, collection_id)?;
+            tx.commit()?;
+            Ok((collection, added))
+        })
+    }
+}
+
+// ===== Helper Functions =====
+
+/// Resolves a selection to the IDs of live clips, without duplicates.
+fn selected_ids(
+    conn: &Connection,
+    selection: &ClipSelection,
+    use_fts: bool,
+) -> SqliteResult<Vec<i32>> {
+    match selection {
+        ClipSelection::Ids(ids) if ids.is_empty() => Ok(Vec::new()),
+        ClipSelection::Ids(ids) => {
+            let (condition, values) = id_list_sql(ids);
+            let mut stmt = conn.prepare(&format!(
+                "SELECT id FROM clips WHERE deleted_at IS NULL AND {condition}"
+            ))?;
+            let live = stmt
+                .query_map(params_from_iter(values), |row| row.get::<_, i32>(0))?
+                .collect::<Result<HashSet<_>, _>>()?;
+
+            let mut seen = HashSet::new();
+            Ok(ids
+                .iter()
+                .copied()
+                .filter(|id| live.contains(id) && seen.insert(*id))
+                .collect())
+        }
+        ClipSelection::Query(query) => {
+            let (where_sql, values) = query.where_sql(use_fts);
+            let mut stmt = conn.prepare(&format!(
+                "SELECT clips.id FROM clips WHERE {where_sql} \
+                 ORDER BY clips.created_at DESC, clips.id DESC"
+            ))?;
+            let ids = stmt
+                .query_map(params_from_iter(values), |row| row.get(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(ids)
+        }
+    }
+}
+
+/// Builds an `id IN (...)` condition with one placeholder per ID.
+fn id_list_sql(ids: &[i32]) -> (String, Vec<Value>) {
+    let placeholders = vec!["?"; ids.len()].join(", ");
+    let values = ids.iter().map(|&id| Value::Integer(id.into())).collect();
+    (format!("id IN ({placeholders})"), values)
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{Clip, ClipOrder};
+
+    fn setup_test_store() -> ClipStore {
+        ClipStore::new(":memory:").expect("Failed to create in-memory store")
+    }
+
+    fn save(store: &ClipStore, content: &str, app_name: &str) -> i32 {
+        let clip = Clip::new(content.into(), app_name.into(), "Win".into(), vec![], false);
+        store.save_clip(clip).unwrap().id.unwrap()
+    }
+
+    #[test]
+    fn test_remove_clips_trashes_one_undoable_batch() {
+        let store = setup_test_store();
+        let a = save(&store, "alpha", "Editor");
+        let b = save(&store, "beta", "Editor");
+        let c = save(&store, "gamma", "Editor");
+
+        let removed = store
+            .remove_clips(ClipSelection::Ids(vec![c, a, a, 999]))
+            .unwrap();
+        assert_eq!(removed, vec![c, a]);
+        let live: Vec<i32> = store
+            .list_clips(ClipOrder::Recent, 10)
+            .unwrap()
+            .into_iter()
+            .filter_map(|clip| clip.id)
+            .collect();
+        assert_eq!(live, vec![b]);
+
+        assert_eq!(store.undo_last_deletion().unwrap().len(), 2);
+        assert!(store
+            .remove_clips(ClipSelection::Ids(vec![]))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_query_selection_pins_and_tags_matching_clips() {
+        let store = setup_test_store();
+        let a = save(&store, "one", "Firefox");
+        let b = save(&store, "two", "Firefox");
+        let other = save(&store, "three", "Terminal");
+
+        let query = ClipQuery::parse("app:firefox").unwrap();
+        let pinned = store
+            .set_clips_pinned(ClipSelection::Query(query.clone()), true)
+            .unwrap();
+        assert_eq!(pinned, vec![b, a]);
+
+        let tagged = store
+            .add_user_tag_to_clips(ClipSelection::Query(query), "web")
+            .unwrap();
+        assert_eq!(tagged, vec![b, a]);
+
+        let clips = store.list_clips(ClipOrder::Recent, 10).unwrap();
+        for clip in clips {
+            let is_selected = clip.id != Some(other);
+            assert_eq!(clip.is_pinned, is_selected);
+            assert_eq!(clip.user_tags.contains(&"web".to_string()), is_selected);
+        }
+        assert!(store
+            .add_user_tag_to_clips(ClipSelection::Ids(vec![a]), " ")
+            .is_err());
+    }
+
+    #[test]
+    fn test_add_clips_to_collection_skips_existing_members() {
+        let store = setup_test_store();
+        let a = save(&store, "one", "App");
+        let b = save(&store, "two", "App");
+        let collection = store.create_collection("Work").unwrap();
+        store.add_clip_to_collection(collection.id, b).unwrap();
+
+        let (updated, added) = store
+            .add_clips_to_collection(collection.id, ClipSelection::Ids(vec![a, b]))
+            .unwrap();
+        assert_eq!(added, vec![a]);
+        assert_eq!(updated.clip_count, 2);
+
+        let order: Vec<i32> = store
+            .list_collection_clips(collection.id)
+            .unwrap()
+            .into_iter()
+            .filter_map(|clip| clip.id)
+            .collect();
+        assert_eq!(order, vec![b, a]);
+        assert!(store
+            .add_clips_to_collection(999, ClipSelection::Ids(vec![a]))
+            .is_err());
+    }
+}
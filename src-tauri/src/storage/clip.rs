@@ -3,12 +3,22 @@
 //!
 //! A [`Clip`] represents a single item in the user's clipboard history,
 //! capturing not only the content but also contextual metadata such as
-//! the source application, window title, and auto-generated tags.
+//! the source application, window title, auto-generated tags, and the tags
+//! and note the user added.
 
 // ===== Imports =====
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+// ===== Constants =====
+
+/// Number of characters of a clip's content returned by listings.
+///
+/// Longer clips are returned truncated, with [`Clip::is_truncated`] set; the full
+/// body is loaded on demand with [`crate::storage::ClipStore::get_clip_content`].
+pub const PREVIEW_CHARS: usize = 500;
 
 // ===== Domain Types =====
 
@@ -16,18 +26,19 @@ use serde::{Deserialize, Serialize};
 ///
 /// Each `Clip` is designed to be stored persistently (e.g., in SQLite) and
 /// displayed in the application’s history UI. It includes:
-/// - The actual clipboard `content`.
+/// - The actual clipboard `content` (or a preview of it, in listings).
 /// - Metadata like `app_name` and `window_title` to aid user recall.
-/// - `auto_tags`: a comma-separated string of inferred tags (e.g., "url,email").
+/// - `tags`: inferred tags (e.g., `["#url", "#email"]`).
+/// - `user_tags` and `note`: tags and free text entered by the user.
 /// - Pin status for user-controlled persistence.
-/// - Timestamps for sorting and cleanup.
+/// - Whether the clip is a snippet, a template expanded when pasted.
+/// - When the clip expires, and whether it is deleted once pasted.
+/// - Timestamps and a copy counter for sorting and cleanup.
 ///
 /// # Storage Note
 ///
-/// The `auto_tags` field is stored as a single `String` (comma-delimited)
-/// for simplicity. This avoids schema complexity when full-text or tag-based
-/// querying is not required. If advanced tag operations are needed in the future,
-/// consider migrating to a separate tags table or JSON storage.
+/// Tags are stored in separate `tags` / `clip_tags` tables so they can be
+/// indexed and may contain any character, including commas.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Clip {
     /// The database ID assigned upon persistence. `None` for new, unsaved clips.
@@ -35,9 +46,19 @@ pub struct Clip {
 
     /// The actual clipboard content (text only).
     ///
-    /// Binary or rich content is not supported in this version.
+    /// Clips loaded from storage carry at most [`PREVIEW_CHARS`] characters here;
+    /// see [`Clip::is_truncated`]. Binary or rich content is not supported in this version.
     pub content: String,
 
+    /// Size of the full content in bytes (UTF-8).
+    pub byte_len: u64,
+
+    /// Number of lines in the full content.
+    pub line_count: u32,
+
+    /// Whether `content` holds only a preview of a longer clip.
+    pub is_truncated: bool,
+
     /// Name of the application that owned the clipboard at capture time.
     ///
     /// Example: `"Visual Studio Code"`, `"Google Chrome"`.
@@ -48,23 +69,66 @@ pub struct Clip {
     /// Example: `"main.rs - my-project"`.
     pub window_title: String,
 
-    /// Auto-generated tags as a comma-separated string.
+    /// Auto-generated tags, sorted alphabetically.
     ///
-    /// Tags are derived from content analysis (e.g., detecting URLs, emails).
-    /// Stored as `"tag1,tag2,tag3"` for compactness and ease of display.
-    /// Not intended for efficient querying—use only for UI hints.
-    pub auto_tags: String,
+    /// Tags are derived from content analysis (e.g., detecting URLs, emails)
+    /// and the source application (e.g., `#browser`).
+    pub tags: Vec<String>,
+
+    /// Tags added by the user, sorted alphabetically.
+    ///
+    /// Kept apart from [`Clip::tags`] so refreshing auto tags never touches them.
+    #[serde(default)]
+    pub user_tags: Vec<String>,
+
+    /// Free-text note entered by the user, or `None` if there is none.
+    #[serde(default)]
+    pub note: Option<String>,
 
     /// Whether the user has pinned this clip to prevent automatic cleanup.
     ///
     /// Pinned clips are excluded from age- or size-based deletion policies.
     pub is_pinned: bool,
 
+    /// Whether this clip is a snippet, whose content is a template with
+    /// placeholders expanded on paste (see [`crate::template`]).
+    #[serde(default)]
+    pub is_snippet: bool,
+
+    /// UTC timestamp after which the clip is deleted for good, or `None` if it
+    /// never expires.
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// Whether the clip is deleted for good the first time it is pasted.
+    #[serde(default)]
+    pub delete_after_use: bool,
+
     /// UTC timestamp when the clip was first created.
+    ///
+    /// Timestamps are stored with millisecond precision. Clips whose timestamps
+    /// were malformed in older databases carry the Unix epoch, so they sort last.
     pub created_at: DateTime<Utc>,
 
     /// UTC timestamp when the clip was last modified (e.g., pin status changed).
     pub updated_at: DateTime<Utc>,
+
+    /// Number of times this content has been copied.
+    ///
+    /// Greater than `1` when repeated copies were merged into this clip.
+    pub copy_count: u32,
+
+    /// UTC timestamp of the most recent copy. History is ordered by this field.
+    pub last_copied_at: DateTime<Utc>,
+
+    /// Number of times this clip was pasted back out of the history.
+    pub use_count: u32,
+
+    /// UTC timestamp of the most recent use, or `None` if never used.
+    pub last_used_at: Option<DateTime<Utc>>,
+
+    /// UTC timestamp when the clip was moved to the trash, or `None` if it is live.
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 // ===== Public API Implementation =====
@@ -79,7 +143,7 @@ impl Clip {
     /// - `content`: The clipboard text.
     /// - `app_name`: Name of the source application.
     /// - `window_title`: Title of the source window.
-    /// - `auto_tags`: A list of semantic tags; joined into a comma-separated string internally.
+    /// - `tags`: A list of semantic tags.
     /// - `is_pinned`: Initial pin state (`false` by default for new clips).
     ///
     /// # Example
@@ -93,25 +157,62 @@ impl Clip {
     ///     vec!["url".to_string()],
     ///     false,
     /// );
-    /// assert_eq!(clip.auto_tags, "url");
+    /// assert_eq!(clip.tags, vec!["url".to_string()]);
     /// ```
     pub fn new(
         content: String,
         app_name: String,
         window_title: String,
-        auto_tags: Vec<String>,
+        tags: Vec<String>,
         is_pinned: bool,
     ) -> Self {
         let now = Utc::now();
         Self {
             id: None,
+            byte_len: content.len() as u64,
+            line_count: line_count(&content),
+            is_truncated: false,
             content,
             app_name,
             window_title,
-            auto_tags: auto_tags.join(","),
+            tags,
+            user_tags: Vec::new(),
+            note: None,
             is_pinned,
+            is_snippet: false,
+            expires_at: None,
+            delete_after_use: false,
             created_at: now,
             updated_at: now,
+            copy_count: 1,
+            last_copied_at: now,
+            use_count: 0,
+            last_used_at: None,
+            deleted_at: None,
         }
     }
 }
+
+// ===== Helper Functions =====
+
+/// Returns the first [`PREVIEW_CHARS`] characters of `content`, or `None` if
+/// the content is no longer than that.
+pub(crate) fn content_preview(content: &str) -> Option<&str> {
+    content
+        .char_indices()
+        .nth(PREVIEW_CHARS)
+        .map(|(end, _)| &content[..end])
+}
+
+/// Returns the number of lines in `content`: one more than the number of newlines.
+pub(crate) fn line_count(content: &str) -> u32 {
+    u32::try_from(content.split('\n').count()).unwrap_or(u32::MAX)
+}
+
+/// Returns the hex-encoded SHA-256 digest of `content`, used to find duplicate clips.
+pub(crate) fn content_hash(content: &str) -> String {
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
@@ -2,31 +2,59 @@
 //! Persistent storage for clipboard history using SQLite.
 //!
 //! The [`ClipStore`] provides thread-safe access to a SQLite database that stores
-//! clipboard entries (`Clip`). It supports CRUD operations, pinning, cleanup by age or size,
-//! and automatic schema initialization.
+//! clipboard entries (`Clip`). It supports CRUD operations, pinning, duplicate bumping,
+//! optional encryption at rest, and automatic schema migration.
+//!
+//! Writes are serialized on a dedicated writer thread and reads use a pool of
+//! read-only connections (see the `worker` module), so captures are not
+//! held up by large listings and vice versa.
 
 // ===== Imports =====
 
 use std::{
     fs::create_dir_all,
-    path::Path,
-    sync::{Arc, Mutex},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard,
+    },
 };
 
-use chrono::{DateTime, Duration, Utc};
-use rusqlite::{params, Connection, Result as SqliteResult};
-use tracing::{debug, warn};
+use chrono::{DateTime, SubsecRound, Utc};
+use rusqlite::{params, types::Value, Connection, OptionalExtension, Result as SqliteResult};
+use tracing::debug;
 
 // ===== Modules =====
 
-use crate::storage::Clip;
+use crate::storage::{
+    clip::{content_hash, content_preview, line_count},
+    encryption::{
+        cipher_of, initial_key_state, read_state, register_clip_text_function, ClipCipher, KeyState,
+    },
+    migrations::run_migrations,
+    tags::attach_tags,
+    trash::move_to_trash,
+    worker::{ReadPool, Writer, READ_POOL_SIZE},
+    Clip, ClipOrder,
+};
 
 // ===== Domain Types =====
 
-/// A thread-safe wrapper around an SQLite connection for managing clipboard history.
+/// A thread-safe handle to the SQLite database holding the clipboard history.
 #[derive(Debug)]
 pub struct ClipStore {
-    conn: Arc<Mutex<Connection>>,
+    writer: Writer,
+    readers: ReadPool,
+    key_state: Arc<RwLock<KeyState>>,
+}
+
+/// Result of [`ClipStore::save_or_bump_clip`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SaveOutcome {
+    /// The content was new; a clip was inserted.
+    Inserted(Clip),
+    /// The content was already stored; the existing clip was bumped.
+    Bumped(Clip),
 }
 
 // ===== Public API Implementation =====
@@ -35,19 +63,31 @@ impl ClipStore {
     /// Creates a new `ClipStore` instance backed by a SQLite database at the given path.
     ///
     /// If the parent directory of `db_path` does not exist, it will be created recursively.
-    /// The database schema (table + indexes) is initialized if not already present.
+    /// Pending schema migrations are applied (see [`crate::storage::migrations`]).
+    /// If the database has encryption enabled, the store starts locked.
+    ///
+    /// `":memory:"` opens a private database that is never written to disk and is
+    /// discarded when the store is dropped. It uses a uniquely named shared-cache
+    /// in-memory database, since the writer and the readers need separate
+    /// connections to the same database.
     ///
     /// # Errors
     ///
     /// Returns an error if:
     /// - The database file cannot be opened or created.
     /// - The parent directory cannot be created.
-    /// - Schema initialization fails.
+    /// - A schema migration fails, or the database was created by a newer app version.
     pub fn new(db_path: impl AsRef<Path>) -> SqliteResult<Self> {
-        let db_path = db_path.as_ref();
+        let in_memory = db_path.as_ref() == Path::new(":memory:");
+        let db_path = if in_memory {
+            shared_memory_uri()
+        } else {
+            db_path.as_ref().to_path_buf()
+        };
+        let db_path = db_path.as_path();
 
         // Ensure parent directory exists
-        if let Some(parent) = db_path.parent() {
+        if let Some(parent) = db_path.parent().filter(|_| !in_memory) {
             create_dir_all(parent).map_err(|e| {
                 rusqlite::Error::ToSqlConversionFailure(
                     format!("Failed to create database directory: {}", e).into(),
@@ -55,32 +95,31 @@ impl ClipStore {
             })?;
         }
 
-        let conn = Connection::open(db_path)?;
+        let mut conn = Connection::open(db_path)?;
 
-        // Enable WAL mode for better concurrency and durability
-        conn.execute_batch("PRAGMA journal_mode=WAL;")?;
+        // Enable WAL mode for better concurrency and durability,
+        // and enforce foreign keys so tag links follow their clips
+        conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA foreign_keys=ON;")?;
 
-        // Initialize schema
-        conn.execute_batch(
-            r#"
-            CREATE TABLE IF NOT EXISTS clips (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                content TEXT NOT NULL,
-                app_name TEXT,
-                window_title TEXT,
-                auto_tags TEXT,
-                is_pinned BOOLEAN NOT NULL DEFAULT 0,
-                created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL
-            );
-
-            CREATE INDEX IF NOT EXISTS idx_created_at ON clips(created_at DESC);
-            CREATE INDEX IF NOT EXISTS idx_is_pinned ON clips(is_pinned);
-            "#,
-        )?;
+        // Bring the schema up to date
+        run_migrations(&mut conn)?;
+
+        // Decrypt encrypted columns on read through `clip_text()`
+        let key_state = Arc::new(RwLock::new(initial_key_state(&conn)?));
+        register_clip_text_function(&conn, key_state.clone())?;
+
+        let readers = ReadPool::open(db_path, READ_POOL_SIZE, |reader| {
+            if in_memory {
+                // Shared-cache readers would otherwise wait on the writer's table locks
+                reader.pragma_update(None, "read_uncommitted", true)?;
+            }
+            register_clip_text_function(reader, key_state.clone())
+        })?;
 
         Ok(Self {
-            conn: Arc::new(Mutex::new(conn)),
+            writer: Writer::spawn(conn)?,
+            readers,
+            key_state,
         })
     }
 
@@ -90,209 +129,522 @@ impl ClipStore {
     ///
     /// # Errors
     ///
-    /// Returns an error if the database write fails.
+    /// Returns an error if the database write fails, or if the store is locked.
     pub fn save_clip(&self, clip: Clip) -> SqliteResult<Clip> {
-        let conn = self.get_db_connection();
+        self.write_with_cipher(move |conn, cipher| {
+            let tx = conn.transaction()?;
+            let saved = insert_clip(&tx, clip, cipher)?;
+            tx.commit()?;
+            Ok(saved)
+        })
+    }
 
-        conn.execute(
-            r#"
-            INSERT INTO clips (
-                content, app_name, window_title,
-                auto_tags, is_pinned,
-                created_at, updated_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
-            "#,
-            params![
-                clip.content,
-                clip.app_name,
-                clip.window_title,
-                clip.auto_tags,
-                clip.is_pinned,
-                clip.created_at.to_rfc3339(),
-                clip.updated_at.to_rfc3339(),
-            ],
-        )?;
-
-        let id = conn.last_insert_rowid() as i32;
-        debug!("Saved clip with id={}", id);
-
-        Ok(Clip {
-            id: Some(id),
-            ..clip.clone()
+    /// Saves a clip, or bumps the existing clip if the same content is already stored.
+    ///
+    /// Duplicates are found by content hash (and confirmed by comparing content).
+    /// When encryption is enabled the hash is keyed, so it is computed in Rust.
+    /// Bumping increments `copy_count` and moves `last_copied_at` to the new clip's
+    /// capture time, which brings the clip back to the top of the history. The
+    /// existing clip keeps its metadata, tags and pin status.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database read or write fails.
+    pub fn save_or_bump_clip(&self, clip: Clip) -> SqliteResult<SaveOutcome> {
+        self.write_with_cipher(move |conn, cipher| {
+            let hash = match cipher {
+                Some(cipher) => cipher.content_hash(&clip.content),
+                None => content_hash(&clip.content),
+            };
+
+            let tx = conn.transaction()?;
+            let existing = find_duplicate(&tx, &hash, &clip.content)?;
+
+            let outcome = match existing {
+                Some(id) => {
+                    tx.execute(
+                        r#"
+                        UPDATE clips
+                        SET copy_count = copy_count + 1, last_copied_at = ?1, updated_at = ?2
+                        WHERE id = ?3
+                        "#,
+                        params![
+                            clip.last_copied_at.timestamp_millis(),
+                            Utc::now().timestamp_millis(),
+                            id
+                        ],
+                    )?;
+                    debug!("Bumped existing clip with id={}", id);
+                    SaveOutcome::Bumped(fetch_clip(&tx, id)?)
+                }
+                None => SaveOutcome::Inserted(insert_clip(&tx, clip, cipher)?),
+            };
+
+            tx.commit()?;
+            Ok(outcome)
         })
     }
 
     /// Retrieves the most recent clips, up to the specified limit.
     ///
-    /// Clips are ordered from newest to oldest by `created_at`.
+    /// Clips are ordered from most to least recently copied (`last_copied_at`).
     ///
     /// # Errors
     ///
-    /// Returns an error if the query fails or timestamp parsing fails.
+    /// Returns an error if the query fails.
     pub fn list_recent_clips(&self, limit: i32) -> SqliteResult<Vec<Clip>> {
-        let conn = self.get_db_connection();
+        self.list_clips(ClipOrder::Recent, limit)
+    }
 
-        let mut stmt = conn.prepare(
+    /// Retrieves clips in the given order, up to the specified limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn list_clips(&self, order: ClipOrder, limit: i32) -> SqliteResult<Vec<Clip>> {
+        let conn = self.reader();
+
+        let mut stmt = conn.prepare(&format!(
             r#"
-            SELECT id, content, app_name, window_title,
-                   auto_tags, is_pinned,
-                   created_at, updated_at
+            SELECT {CLIP_COLUMNS}
             FROM clips
-            ORDER BY created_at DESC
+            WHERE clips.deleted_at IS NULL
+            ORDER BY {}
             LIMIT ?1
             "#,
-        )?;
+            order.order_by_sql()
+        ))?;
 
         let clips = stmt
-            .query_map([limit], |row| {
-                let created_raw: String = row.get(6)?;
-                let updated_raw: String = row.get(7)?;
-
-                Ok(Clip {
-                    id: Some(row.get(0)?),
-                    content: row.get(1)?,
-                    app_name: row.get(2)?,
-                    window_title: row.get(3)?,
-                    auto_tags: row.get(4)?,
-                    is_pinned: row.get(5)?,
-                    created_at: parse_timestamp(&created_raw),
-                    updated_at: parse_timestamp(&updated_raw),
-                })
-            })?
+            .query_map([limit], row_to_clip)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(clips)
     }
 
+    /// Returns the full content of a clip.
+    ///
+    /// Listings only carry a preview of long clips (see [`Clip::is_truncated`]);
+    /// use this to load the whole body, e.g. before pasting.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`rusqlite::Error::QueryReturnedNoRows`] if no live clip has the given
+    /// ID, or another error if the query fails or the store is locked.
+    pub fn get_clip_content(&self, id: i32) -> SqliteResult<String> {
+        self.reader().query_row(
+            "SELECT clip_text(content) FROM clips WHERE id = ?1 AND deleted_at IS NULL",
+            [id],
+            |row| row.get(0),
+        )
+    }
+
     /// Updates the pinned status of a clip by its ID.
     ///
-    /// Sets `is_pinned` and updates the `updated_at` timestamp.
+    /// Sets `is_pinned` and updates the `updated_at` timestamp, and returns the
+    /// updated clip.
     ///
     /// # Errors
     ///
     /// Returns an error if the update fails (e.g., invalid ID).
-    pub fn toggle_pin_status(&self, id: i32, is_pinned: bool) -> SqliteResult<()> {
-        let conn = self.get_db_connection();
-
-        conn.execute(
-            "UPDATE clips SET is_pinned = ?1, updated_at = ?2 WHERE id = ?3",
-            params![is_pinned, Utc::now().to_rfc3339(), id],
-        )?;
+    pub fn toggle_pin_status(&self, id: i32, is_pinned: bool) -> SqliteResult<Clip> {
+        self.write(move |conn| {
+            conn.execute(
+                "UPDATE clips SET is_pinned = ?1, updated_at = ?2 WHERE id = ?3",
+                params![is_pinned, Utc::now().timestamp_millis(), id],
+            )?;
+            fetch_clip(conn, id)
+        })
+    }
 
-        Ok(())
+    /// Marks a live clip as a snippet, or back as a plain clip, and returns the
+    /// updated clip.
+    ///
+    /// The content is not checked here; callers validate it as a template first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`rusqlite::Error::QueryReturnedNoRows`] if no live clip has the
+    /// given ID, or another error if the update fails.
+    pub fn set_snippet(&self, id: i32, is_snippet: bool) -> SqliteResult<Clip> {
+        self.write(move |conn| {
+            let updated = conn.execute(
+                "UPDATE clips SET is_snippet = ?1, updated_at = ?2 WHERE id = ?3 AND deleted_at IS NULL",
+                params![is_snippet, Utc::now().timestamp_millis(), id],
+            )?;
+            if updated == 0 {
+                return Err(rusqlite::Error::QueryReturnedNoRows);
+            }
+            fetch_clip(conn, id)
+        })
     }
 
-    /// Deletes a clip by its database ID.
+    /// Sets or clears the note on a live clip and returns the updated clip.
     ///
-    /// No error is returned if the ID does not exist.
+    /// The note is trimmed; a blank note clears it. Notes are encrypted like clip
+    /// content when encryption is enabled.
     ///
     /// # Errors
     ///
-    /// Returns an error only if the database operation fails.
-    pub fn remove_clip(&self, id: i32) -> SqliteResult<()> {
-        let conn = self.get_db_connection();
-        conn.execute("DELETE FROM clips WHERE id = ?1", params![id])?;
-        Ok(())
+    /// Returns [`rusqlite::Error::QueryReturnedNoRows`] if no live clip has the
+    /// given ID, or another error if the update fails or the store is locked.
+    pub fn set_clip_note(&self, id: i32, note: Option<&str>) -> SqliteResult<Clip> {
+        let note = note
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .map(str::to_string);
+
+        self.write_with_cipher(move |conn, cipher| {
+            let stored = match (cipher, note) {
+                (Some(cipher), Some(note)) => Some(cipher.encrypt(&note)?),
+                (_, note) => note,
+            };
+            let updated = conn.execute(
+                "UPDATE clips SET note = ?1, updated_at = ?2 WHERE id = ?3 AND deleted_at IS NULL",
+                params![stored, Utc::now().timestamp_millis(), id],
+            )?;
+            if updated == 0 {
+                return Err(rusqlite::Error::QueryReturnedNoRows);
+            }
+
+            fetch_clip(conn, id)
+        })
     }
 
-    /// Deletes all clips from the database.
+    /// Moves a clip to the trash by its database ID.
     ///
-    /// This operation cannot be undone.
+    /// No error is returned if the ID does not exist or the clip is already trashed.
+    /// The clip can be brought back with [`ClipStore::restore_clip`] or
+    /// [`ClipStore::undo_last_deletion`].
     ///
     /// # Errors
     ///
-    /// Returns an error if the deletion fails.
-    pub fn clear_clip_history(&self) -> SqliteResult<()> {
-        let conn = self.get_db_connection();
-        conn.execute("DELETE FROM clips", [])?;
-        debug!("Cleared all clips from history.");
-        Ok(())
+    /// Returns an error only if the database operation fails.
+    pub fn remove_clip(&self, id: i32) -> SqliteResult<()> {
+        self.write(move |conn| {
+            move_to_trash(conn, "id = ?", vec![Value::Integer(id.into())])?;
+            Ok(())
+        })
     }
 
-    /// Performs automatic cleanup of the clip history.
+    /// Moves all clips, pinned ones included, to the trash.
     ///
-    /// Applies two strategies in sequence:
-    /// 1. Removes clips older than `days`.
-    /// 2. Trims history to at most `max_size` most recent clips.
+    /// The whole history can be brought back with [`ClipStore::undo_last_deletion`].
     ///
     /// # Errors
     ///
-    /// Returns an error if either cleanup step fails.
-    pub fn perform_cleanup(&self, days: i64, max_size: i64) -> SqliteResult<()> {
-        self.remove_clips_older_than_days(days)?;
-        self.enforce_max_size(max_size)?;
-        Ok(())
+    /// Returns an error if the update fails.
+    pub fn clear_clip_history(&self) -> SqliteResult<()> {
+        self.write(|conn| {
+            let trashed = move_to_trash(conn, "1", Vec::new())?;
+            debug!("Moved {} clips from history to the trash.", trashed);
+            Ok(())
+        })
     }
 }
 
 // ===== Private Helper Methods =====
 
 impl ClipStore {
-    /// Acquires a lock on the SQLite connection, recovering from poisoning if necessary.
-    ///
-    /// If a previous thread panicked while holding the mutex, the lock is still acquired
-    /// and a warning is logged. This is safe because SQLite operations are short and atomic.
-    fn get_db_connection(&self) -> std::sync::MutexGuard<'_, Connection> {
-        self.conn.lock().unwrap_or_else(|e| {
-            warn!("Database mutex was poisoned; recovering...");
-            e.into_inner()
-        })
+    /// Acquires a read-only connection from the pool.
+    ///
+    /// Reads on it never wait for, or hold up, writes.
+    pub(super) fn reader(&self) -> MutexGuard<'_, Connection> {
+        self.readers.get()
     }
 
-    /// Deletes clips older than the specified number of days.
-    fn remove_clips_older_than_days(&self, days: i64) -> SqliteResult<usize> {
-        let conn = self.get_db_connection();
-        let cutoff = (Utc::now() - Duration::days(days)).to_rfc3339();
-
-        let deleted = conn.execute(
-            "DELETE FROM clips WHERE datetime(created_at) < datetime(?1)",
-            params![cutoff],
-        )?;
+    /// Runs `job` with the read-write connection on the writer thread and waits for it.
+    ///
+    /// Writes run one at a time, in submission order.
+    pub(super) fn write<T, F>(&self, job: F) -> SqliteResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> SqliteResult<T> + Send + 'static,
+    {
+        self.writer.execute(job)
+    }
 
-        debug!("Deleted {} clips older than {} days", deleted, days);
-        Ok(deleted)
+    /// Like [`ClipStore::write`], also passing the cipher as of when the job runs.
+    ///
+    /// Resolving the cipher on the writer thread orders it after any earlier job
+    /// that enables encryption or restores a backup.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store is locked when the job runs, or the job's error.
+    pub(super) fn write_with_cipher<T, F>(&self, job: F) -> SqliteResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection, Option<&ClipCipher>) -> SqliteResult<T> + Send + 'static,
+    {
+        let key_state = self.key_state.clone();
+        self.write(move |conn| {
+            let cipher = cipher_of(&read_state(&key_state))?;
+            job(conn, cipher.as_deref())
+        })
     }
 
-    /// Ensures the total number of clips does not exceed `max_size`.
+    /// Like [`ClipStore::write`], for jobs that change the encryption key state.
     ///
-    /// Keeps the `max_size` most recent clips (by `created_at`) and deletes the rest.
-    fn enforce_max_size(&self, max_size: i64) -> SqliteResult<usize> {
-        let conn = self.get_db_connection();
+    /// The job returns its result with the new key state, which is installed
+    /// before the next write job runs.
+    pub(super) fn write_key_state<T, F>(&self, job: F) -> SqliteResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> SqliteResult<(T, KeyState)> + Send + 'static,
+    {
+        let key_state = self.key_state.clone();
+        self.write(move |conn| {
+            let (result, state) = job(conn)?;
+            *key_state.write().unwrap_or_else(|e| e.into_inner()) = state;
+            Ok(result)
+        })
+    }
 
-        let deleted = conn.execute(
-            r#"
-            DELETE FROM clips
-            WHERE id NOT IN (
-                SELECT id FROM clips
-                ORDER BY created_at DESC
-                LIMIT ?1
-            )
-            "#,
-            params![max_size],
-        )?;
+    /// Acquires a read lock on the encryption key state.
+    pub(super) fn key_state(&self) -> RwLockReadGuard<'_, KeyState> {
+        self.key_state.read().unwrap_or_else(|e| e.into_inner())
+    }
 
-        debug!(
-            "Trimmed {} old clips to enforce max size of {}",
-            deleted, max_size
-        );
-        Ok(deleted)
+    /// Acquires a write lock on the encryption key state.
+    ///
+    /// Never hold this while waiting for a connection or a write: queries read
+    /// the key state through `clip_text()`, on readers and on the writer thread.
+    pub(super) fn key_state_mut(&self) -> RwLockWriteGuard<'_, KeyState> {
+        self.key_state.write().unwrap_or_else(|e| e.into_inner())
     }
 }
 
 // ===== Standalone Helper Functions =====
 
-/// Parses an RFC3339 timestamp string into a `DateTime<Utc>`.
+/// Returns the ID of the most recently copied live clip with the given content.
 ///
-/// On parsing failure, logs a warning and returns the current time as a fallback.
-/// This ensures robustness when reading potentially corrupted or malformed data.
-fn parse_timestamp(s: &str) -> DateTime<Utc> {
-    match DateTime::parse_from_rfc3339(s) {
-        Ok(dt) => dt.with_timezone(&Utc),
-        Err(e) => {
-            warn!("Failed to parse timestamp: '{}', {}", s, e);
-            Utc::now()
-        }
-    }
+/// `hash` must be computed the same way as the stored `content_hash` (keyed when
+/// encryption is enabled); content is compared too, to rule out collisions.
+pub(super) fn find_duplicate(
+    conn: &Connection,
+    hash: &str,
+    content: &str,
+) -> SqliteResult<Option<i32>> {
+    conn.query_row(
+        r#"
+        SELECT id FROM clips
+        WHERE content_hash = ?1 AND clip_text(content) = ?2 AND deleted_at IS NULL
+        ORDER BY last_copied_at DESC
+        LIMIT 1
+        "#,
+        params![hash, content],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+/// Inserts `clip` with its auto and user tags and returns it with the assigned ID.
+///
+/// A preview is stored alongside content longer than [`crate::storage::PREVIEW_CHARS`]; the
+/// returned clip carries only the preview, like clips loaded by listings.
+/// With a `cipher`, content, preview, window title and note are stored encrypted.
+pub(super) fn insert_clip(
+    conn: &Connection,
+    clip: Clip,
+    cipher: Option<&ClipCipher>,
+) -> SqliteResult<Clip> {
+    let preview = content_preview(&clip.content);
+    let byte_len = clip.content.len() as u64;
+    let line_count = line_count(&clip.content);
+
+    let (content, stored_preview, hash) = encode_content(&clip.content, cipher)?;
+    let (window_title, note) = match cipher {
+        Some(cipher) => (
+            cipher.encrypt(&clip.window_title)?,
+            clip.note
+                .as_deref()
+                .map(|n| cipher.encrypt(n))
+                .transpose()?,
+        ),
+        None => (clip.window_title.clone(), clip.note.clone()),
+    };
+
+    conn.execute(
+        r#"
+        INSERT INTO clips (
+            content, content_hash, app_name, window_title,
+            is_pinned,
+            created_at, updated_at,
+            copy_count, last_copied_at,
+            use_count, last_used_at,
+            preview, byte_len, line_count,
+            note, is_snippet,
+            expires_at, delete_after_use
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)
+        "#,
+        params![
+            content,
+            hash,
+            clip.app_name,
+            window_title,
+            clip.is_pinned,
+            clip.created_at.timestamp_millis(),
+            clip.updated_at.timestamp_millis(),
+            clip.copy_count,
+            clip.last_copied_at.timestamp_millis(),
+            clip.use_count,
+            clip.last_used_at.map(|t| t.timestamp_millis()),
+            stored_preview,
+            byte_len as i64,
+            line_count,
+            note,
+            clip.is_snippet,
+            clip.expires_at.map(|t| t.timestamp_millis()),
+            clip.delete_after_use,
+        ],
+    )?;
+
+    let id = conn.last_insert_rowid() as i32;
+    attach_tags(conn, id, &clip.tags, false)?;
+    attach_tags(conn, id, &clip.user_tags, true)?;
+
+    debug!("Saved clip with id={} ({} bytes)", id, byte_len);
+
+    // Timestamps are stored with millisecond precision; return them as stored
+    Ok(Clip {
+        id: Some(id),
+        content: preview.map_or(clip.content.clone(), str::to_string),
+        byte_len,
+        line_count,
+        is_truncated: preview.is_some(),
+        created_at: clip.created_at.trunc_subsecs(3),
+        updated_at: clip.updated_at.trunc_subsecs(3),
+        last_copied_at: clip.last_copied_at.trunc_subsecs(3),
+        last_used_at: clip.last_used_at.map(|t| t.trunc_subsecs(3)),
+        expires_at: clip.expires_at.map(|t| t.trunc_subsecs(3)),
+        ..clip
+    })
+}
+
+/// Returns `content` as stored, its stored preview and its content hash.
+///
+/// With a `cipher`, content and preview are encrypted and the hash is keyed.
+pub(super) fn encode_content(
+    content: &str,
+    cipher: Option<&ClipCipher>,
+) -> SqliteResult<(String, Option<String>, String)> {
+    let preview = content_preview(content);
+    Ok(match cipher {
+        Some(cipher) => (
+            cipher.encrypt(content)?,
+            preview.map(|p| cipher.encrypt(p)).transpose()?,
+            cipher.content_hash(content),
+        ),
+        None => (
+            content.to_string(),
+            preview.map(str::to_string),
+            content_hash(content),
+        ),
+    })
+}
+
+/// Returns a URI naming a new shared-cache in-memory database.
+///
+/// The database lives as long as a connection to it is open, and is private to
+/// this process.
+fn shared_memory_uri() -> PathBuf {
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+    PathBuf::from(format!(
+        "file:clipcontex-{}-{}?mode=memory&cache=shared",
+        std::process::id(),
+        NEXT_ID.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+/// Loads a single clip by ID.
+pub(super) fn fetch_clip(conn: &Connection, id: i32) -> SqliteResult<Clip> {
+    conn.query_row(
+        &format!("SELECT {CLIP_COLUMNS} FROM clips WHERE clips.id = ?1"),
+        [id],
+        row_to_clip,
+    )
+}
+
+/// Column list matching the field order expected by [`row_to_clip`].
+///
+/// Tags are aggregated into a JSON array so each clip still maps to a single row.
+/// Content is read from the stored preview when there is one. Sizes are computed
+/// from the content for rows that were already encrypted when previews were added.
+pub(super) const CLIP_COLUMNS: &str =
+    "clips.id, clip_text(coalesce(clips.preview, clips.content)), clips.app_name, \
+     clip_text(clips.window_title), \
+     (SELECT json_group_array(name) FROM ( \
+         SELECT tags.name FROM clip_tags JOIN tags ON tags.id = clip_tags.tag_id \
+         WHERE clip_tags.clip_id = clips.id AND clip_tags.is_user = 0 ORDER BY tags.name \
+     )) AS tags, \
+     clips.is_pinned, clips.created_at, clips.updated_at, \
+     clips.copy_count, clips.last_copied_at, clips.use_count, clips.last_used_at, \
+     coalesce(clips.byte_len, length(CAST(clip_text(clips.content) AS BLOB))), \
+     coalesce(clips.line_count, ( \
+         SELECT length(body) - length(replace(body, char(10), '')) + 1 \
+         FROM (SELECT clip_text(clips.content) AS body) \
+     )), \
+     clips.deleted_at, \
+     (SELECT json_group_array(name) FROM ( \
+         SELECT tags.name FROM clip_tags JOIN tags ON tags.id = clip_tags.tag_id \
+         WHERE clip_tags.clip_id = clips.id AND clip_tags.is_user = 1 ORDER BY tags.name \
+     )) AS user_tags, \
+     clip_text(clips.note), clips.is_snippet, clips.expires_at, clips.delete_after_use";
+
+/// Number of columns in [`CLIP_COLUMNS`]; the index of the first appended column.
+pub(super) const CLIP_COLUMN_COUNT: usize = 20;
+
+/// Maps a row selected with [`CLIP_COLUMNS`] into a [`Clip`].
+///
+/// Extra columns after the clip fields are ignored, so callers may append
+/// computed values (e.g., search snippets) to the select list.
+pub(super) fn row_to_clip(row: &rusqlite::Row<'_>) -> SqliteResult<Clip> {
+    let content: String = row.get(1)?;
+    let byte_len = row.get::<_, i64>(12)?.max(0) as u64;
+
+    let tags = json_tags(row, 4)?;
+
+    Ok(Clip {
+        id: Some(row.get(0)?),
+        is_truncated: (content.len() as u64) < byte_len,
+        content,
+        byte_len,
+        line_count: row.get(13)?,
+        app_name: row.get(2)?,
+        window_title: row.get(3)?,
+        tags,
+        user_tags: json_tags(row, 15)?,
+        note: row.get(16)?,
+        is_pinned: row.get(5)?,
+        is_snippet: row.get(17)?,
+        expires_at: row.get::<_, Option<i64>>(18)?.map(from_epoch_ms),
+        delete_after_use: row.get(19)?,
+        created_at: from_epoch_ms(row.get(6)?),
+        updated_at: from_epoch_ms(row.get(7)?),
+        copy_count: row.get(8)?,
+        last_copied_at: from_epoch_ms(row.get(9)?),
+        use_count: row.get(10)?,
+        last_used_at: row.get::<_, Option<i64>>(11)?.map(from_epoch_ms),
+        deleted_at: row.get::<_, Option<i64>>(14)?.map(from_epoch_ms),
+    })
+}
+
+/// Parses the JSON array of tag names in column `idx`.
+fn json_tags(row: &rusqlite::Row<'_>, idx: usize) -> SqliteResult<Vec<String>> {
+    let raw: String = row.get(idx)?;
+    serde_json::from_str(&raw).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(idx, rusqlite::types::Type::Text, Box::new(e))
+    })
+}
+
+/// Converts a stored timestamp, in milliseconds since the epoch, into a `DateTime<Utc>`.
+///
+/// Timestamps that were malformed before the switch to integers are stored as `0`
+/// and read back as the epoch, so such clips sort as the oldest. Out-of-range
+/// values are treated the same way.
+pub(super) fn from_epoch_ms(ms: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(ms).unwrap_or(DateTime::UNIX_EPOCH)
 }
 
 // ===== Integration Tests =====
@@ -300,12 +652,25 @@ fn parse_timestamp(s: &str) -> DateTime<Utc> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::storage::PREVIEW_CHARS;
     use std::time::Instant;
 
     fn setup_test_store() -> ClipStore {
         ClipStore::new(":memory:").expect("Failed to create in-memory store")
     }
 
+    #[test]
+    fn test_memory_store_has_no_file() {
+        let store = setup_test_store();
+        let other = setup_test_store();
+        let clip = Clip::new("secret".into(), "App".into(), "Win".into(), vec![], false);
+        store.save_clip(clip).unwrap();
+
+        assert!(store.reader().path().unwrap_or_default().is_empty());
+        assert_eq!(store.list_recent_clips(10).unwrap().len(), 1);
+        assert!(other.list_recent_clips(10).unwrap().is_empty());
+    }
+
     #[test]
     fn test_save_and_retrieve_clip() {
         let store = setup_test_store();
@@ -338,6 +703,52 @@ mod tests {
         assert!(recent[0].is_pinned);
     }
 
+    #[test]
+    fn test_set_and_clear_clip_note() {
+        let store = setup_test_store();
+        let clip = Clip::new(
+            "Annotate me".into(),
+            "App".into(),
+            "Win".into(),
+            vec![],
+            false,
+        );
+        let id = store.save_clip(clip).unwrap().id.unwrap();
+
+        let noted = store
+            .set_clip_note(id, Some("  from the standup  "))
+            .unwrap();
+        assert_eq!(noted.note.as_deref(), Some("from the standup"));
+        assert_eq!(store.list_recent_clips(1).unwrap()[0], noted);
+
+        let cleared = store.set_clip_note(id, Some("   ")).unwrap();
+        assert_eq!(cleared.note, None);
+
+        store.remove_clip(id).unwrap();
+        assert!(store.set_clip_note(id, Some("too late")).is_err());
+    }
+
+    #[test]
+    fn test_mark_clip_as_snippet() {
+        let store = setup_test_store();
+        let clip = Clip::new(
+            "Regards, {input:Name}".into(),
+            "App".into(),
+            "Win".into(),
+            vec![],
+            false,
+        );
+        let id = store.save_clip(clip).unwrap().id.unwrap();
+
+        let snippet = store.set_snippet(id, true).unwrap();
+        assert!(snippet.is_snippet);
+        assert_eq!(store.list_recent_clips(1).unwrap()[0], snippet);
+        assert!(!store.set_snippet(id, false).unwrap().is_snippet);
+
+        store.remove_clip(id).unwrap();
+        assert!(store.set_snippet(id, true).is_err());
+    }
+
     #[test]
     fn test_remove_clip() {
         let store = setup_test_store();
@@ -369,75 +780,75 @@ mod tests {
     }
 
     #[test]
-    fn test_cleanup_by_age() {
+    fn test_save_or_bump_merges_duplicate_content() {
         let store = setup_test_store();
-
-        let mut old_clip = Clip::new(
-            "Old".into(),
-            "OldApp".into(),
-            "OldWin".into(),
+        let first = Clip::new(
+            "cargo test".into(),
+            "Term".into(),
+            "Win".into(),
             vec![],
             false,
         );
-        old_clip.created_at = Utc::now() - Duration::days(10);
-        old_clip.updated_at = old_clip.created_at;
-        store.save_clip(old_clip).unwrap();
-
-        let new_clip = Clip::new(
-            "New".into(),
-            "NewApp".into(),
-            "NewWin".into(),
+        let SaveOutcome::Inserted(first) = store.save_or_bump_clip(first).unwrap() else {
+            panic!("first copy should be inserted");
+        };
+        let other = Clip::new("other".into(), "App".into(), "Win".into(), vec![], false);
+        store.save_or_bump_clip(other).unwrap();
+
+        let mut again = Clip::new(
+            "cargo test".into(),
+            "Other".into(),
+            "Win".into(),
             vec![],
             false,
         );
-        store.save_clip(new_clip).unwrap();
+        again.last_copied_at = Utc::now() + chrono::Duration::seconds(5);
+        let SaveOutcome::Bumped(bumped) = store.save_or_bump_clip(again).unwrap() else {
+            panic!("repeated copy should bump the existing clip");
+        };
 
-        store.perform_cleanup(5, 100).unwrap();
+        assert_eq!(bumped.id, first.id);
+        assert_eq!(bumped.copy_count, 2);
+        assert_eq!(bumped.app_name, "Term");
+        assert!(bumped.last_copied_at > first.last_copied_at);
 
-        let remaining = store.list_recent_clips(10).unwrap();
-        assert_eq!(remaining.len(), 1);
-        assert_eq!(remaining[0].content, "New");
+        let clips = store.list_recent_clips(10).unwrap();
+        assert_eq!(clips.len(), 2);
+        assert_eq!(clips[0].content, "cargo test");
     }
 
     #[test]
-    fn test_cleanup_by_max_size() {
+    fn test_save_clip_keeps_duplicates() {
         let store = setup_test_store();
-
-        for i in 0..5 {
-            let clip = Clip::new(
-                format!("Clip {}", i),
-                "App".into(),
-                "Win".into(),
-                vec![],
-                false,
-            );
+        for _ in 0..2 {
+            let clip = Clip::new("same".into(), "App".into(), "Win".into(), vec![], false);
             store.save_clip(clip).unwrap();
         }
 
-        store.perform_cleanup(30, 3).unwrap();
-
-        let remaining = store.list_recent_clips(10).unwrap();
-        assert_eq!(remaining.len(), 3);
-        assert_eq!(remaining[0].content, "Clip 4");
-        assert_eq!(remaining[1].content, "Clip 3");
-        assert_eq!(remaining[2].content, "Clip 2");
+        let clips = store.list_recent_clips(10).unwrap();
+        assert_eq!(clips.len(), 2);
+        assert!(clips.iter().all(|c| c.copy_count == 1));
     }
 
     #[test]
-    fn test_timestamp_parsing_failure() {
+    fn test_unknown_timestamps_sort_last() {
         let store = setup_test_store();
-        {
-            let conn = store.get_db_connection();
-            conn.execute(
-                r#"INSERT INTO clips (content, app_name, window_title, auto_tags, is_pinned, created_at, updated_at)
-                   VALUES ('bad', 'app', 'win', '', 0, 'invalid', 'also_invalid')"#,
-                [],
-            )
+        store
+            .write(|conn| {
+                conn.execute(
+                    r#"INSERT INTO clips (content, app_name, window_title, is_pinned, created_at, updated_at, last_copied_at)
+                       VALUES ('legacy', 'app', 'win', 0, 0, 0, 0)"#,
+                    [],
+                )
+            })
             .unwrap();
-        }
+        let fresh = Clip::new("fresh".into(), "App".into(), "Win".into(), vec![], false);
+        store.save_clip(fresh).unwrap();
 
         let clips = store.list_recent_clips(10).unwrap();
-        assert_eq!(clips.len(), 1);
+        assert_eq!(clips[0].content, "fresh");
+        assert_eq!(clips[1].content, "legacy");
+        assert_eq!(clips[1].created_at, DateTime::UNIX_EPOCH);
     }
 
     #[test]
@@ -471,6 +882,25 @@ mod tests {
         assert_eq!(clips.len(), 5);
     }
 
+    #[test]
+    fn test_previews_respect_character_boundaries() {
+        let store = setup_test_store();
+        let content = format!("{}\nsecond line", "é".repeat(PREVIEW_CHARS));
+        let clip = Clip::new(content.clone(), "App".into(), "Win".into(), vec![], false);
+        let id = store.save_clip(clip).unwrap().id.unwrap();
+
+        let listed = &store.list_recent_clips(1).unwrap()[0];
+        assert_eq!(listed.content, "é".repeat(PREVIEW_CHARS));
+        assert_eq!(listed.byte_len, content.len() as u64);
+        assert_eq!(listed.line_count, 2);
+        assert!(listed.is_truncated);
+
+        let short = Clip::new("short".into(), "App".into(), "Win".into(), vec![], false);
+        let short = store.save_clip(short).unwrap();
+        assert!(!short.is_truncated);
+        assert_eq!(store.get_clip_content(id).unwrap(), content);
+    }
+
     #[test]
     fn test_large_content_handling() {
         let store = setup_test_store();
@@ -484,13 +914,21 @@ mod tests {
         );
 
         let start = Instant::now();
-        let _saved = store.save_clip(clip).unwrap();
+        let saved = store.save_clip(clip).unwrap();
         let save_duration = start.elapsed();
 
         let retrieved = store.list_recent_clips(1).unwrap();
         let retrieve_duration = start.elapsed() - save_duration;
 
-        assert_eq!(retrieved[0].content, large_content);
+        // Listings carry only a preview; the full body is loaded on demand
+        assert_eq!(retrieved[0].content, "A".repeat(PREVIEW_CHARS));
+        assert!(retrieved[0].is_truncated);
+        assert_eq!(retrieved[0].byte_len, 10_000_000);
+        assert_eq!(retrieved[0], saved);
+        assert_eq!(
+            store.get_clip_content(saved.id.unwrap()).unwrap(),
+            large_content
+        );
 
         eprintln!(
             "Saved 10MB clip in {:?}, retrieved in {:?}",
@@ -0,0 +1,465 @@
+// src-tauri/src/storage/collections.rs
+//! User-defined collections of clips.
+//!
+//! A [`Collection`] is a named, ordered set of clips, and a clip may belong to
+//! any number of collections. New members are appended; their order only changes
+//! through [`ClipStore::move_clip_in_collection`]. Clips in any collection are
+//! exempt from automatic cleanup.
+//!
+//! Trashed clips keep their memberships, so restoring a clip puts it back in
+//! place. Until then it is left out of listings and member counts.
+
+// ===== Imports =====
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::storage::{
+    clip_store::{from_epoch_ms, row_to_clip, CLIP_COLUMNS},
+    Clip, ClipStore,
+};
+
+// ===== Constants =====
+
+/// Column list matching the field order expected by [`row_to_collection`].
+const COLLECTION_COLUMNS: &str = "collections.id, collections.name, \
+     (SELECT COUNT(*) FROM collection_clips \
+      JOIN clips ON clips.id = collection_clips.clip_id \
+      WHERE collection_clips.collection_id = collections.id AND clips.deleted_at IS NULL), \
+     collections.created_at";
+
+// ===== Domain Types =====
+
+/// A named collection of clips.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Collection {
+    /// Database ID of the collection.
+    pub id: i32,
+    /// The collection name, unique regardless of case.
+    pub name: String,
+    /// Number of live clips in the collection.
+    pub clip_count: i64,
+    /// When the collection was created.
+    pub created_at: DateTime<Utc>,
+}
+
+// ===== Public API Implementation =====
+
+impl ClipStore {
+    /// Lists every collection, ordered by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn list_collections(&self) -> SqliteResult<Vec<Collection>> {
+        let conn = self.reader();
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {COLLECTION_COLUMNS} FROM collections ORDER BY collections.name ASC"
+        ))?;
+
+        let collections = stmt
+            .query_map([], row_to_collection)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(collections)
+    }
+
+    /// Creates an empty collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `name` is blank or already taken, or if the insert fails.
+    pub fn create_collection(&self, name: &str) -> SqliteResult<Collection> {
+        let name = collection_name(name)?;
+
+        self.write(move |conn| {
+            ensure_name_available(conn, &name, None)?;
+            conn.execute(
+                "INSERT INTO collections (name, created_at) VALUES (?1, ?2)",
+                params![name, Utc::now().timestamp_millis()],
+            )?;
+
+            let id = conn.last_insert_rowid() as i32;
+            debug!("Created collection '{}' with id={}", name, id);
+            fetch_collection(conn, id)
+        })
+    }
+
+    /// Renames a collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`rusqlite::Error::QueryReturnedNoRows`] if no collection has the
+    /// given ID, or another error if `name` is blank or taken by another collection.
+    pub fn rename_collection(&self, id: i32, name: &str) -> SqliteResult<Collection> {
+        let name = collection_name(name)?;
+
+        self.write(move |conn| {
+            ensure_name_available(conn, &name, Some(id))?;
+            let updated = conn.execute(
+                "UPDATE collections SET name = ?1 WHERE id = ?2",
+                params![name, id],
+            )?;
+            if updated == 0 {
+                return Err(rusqlite::Error::QueryReturnedNoRows);
+            }
+
+            fetch_collection(conn, id)
+        })
+    }
+
+    /// Deletes a collection. Its clips stay in the history.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`rusqlite::Error::QueryReturnedNoRows`] if no collection has the
+    /// given ID, or another error if the deletion fails.
+    pub fn delete_collection(&self, id: i32) -> SqliteResult<()> {
+        self.write(move |conn| {
+            match conn.execute("DELETE FROM collections WHERE id = ?1", [id])? {
+                0 => Err(rusqlite::Error::QueryReturnedNoRows),
+                _ => {
+                    debug!("Deleted collection id={}", id);
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    /// Adds a clip to the end of a collection. Adding a member again changes nothing.
+    ///
+    /// Returns the updated collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`rusqlite::Error::QueryReturnedNoRows`] if the collection or the
+    /// live clip does not exist, or another error if the insert fails.
+    pub fn add_clip_to_collection(
+        &self,
+        collection_id: i32,
+        clip_id: i32,
+    ) -> SqliteResult<Collection> {
+        self.write(move |conn| {
+            fetch_collection(conn, collection_id)?;
+            let is_live: bool = conn.query_row(
+                "SELECT EXISTS (SELECT 1 FROM clips WHERE id = ?1 AND deleted_at IS NULL)",
+                [clip_id],
+                |row| row.get(0),
+            )?;
+            if !is_live {
+                return Err(rusqlite::Error::QueryReturnedNoRows);
+            }
+
+            conn.execute(
+                r#"
+                INSERT OR IGNORE INTO collection_clips (collection_id, clip_id, position)
+                SELECT ?1, ?2, coalesce(max(position) + 1, 0)
+                FROM collection_clips
+                WHERE collection_id = ?1
+                "#,
+                params![collection_id, clip_id],
+            )?;
+
+            fetch_collection(conn, collection_id)
+        })
+    }
+
+    /// Removes a clip from a collection. Removing a non-member changes nothing.
+    ///
+    /// Returns the updated collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`rusqlite::Error::QueryReturnedNoRows`] if no collection has the
+    /// given ID, or another error if the deletion fails.
+    pub fn remove_clip_from_collection(
+        &self,
+        collection_id: i32,
+        clip_id: i32,
+    ) -> SqliteResult<Collection> {
+        self.write(move |conn| {
+            conn.execute(
+                "DELETE FROM collection_clips WHERE collection_id = ?1 AND clip_id = ?2",
+                params![collection_id, clip_id],
+            )?;
+            fetch_collection(conn, collection_id)
+        })
+    }
+
+    /// Moves a member of a collection to `index` among the collection's live clips.
+    ///
+    /// An `index` past the end moves the clip to the end. Returns the updated collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`rusqlite::Error::QueryReturnedNoRows`] if the clip is not in the
+    /// collection, or another error if the update fails.
+    pub fn move_clip_in_collection(
+        &self,
+        collection_id: i32,
+        clip_id: i32,
+        index: usize,
+    ) -> SqliteResult<Collection> {
+        self.write(move |conn| {
+            let tx = conn.transaction()?;
+
+            let members: Vec<(i32, bool)> = {
+                let mut stmt = tx.prepare(
+                    r#"
+                    SELECT collection_clips.clip_id, clips.deleted_at IS NULL
+                    FROM collection_clips
+                    JOIN clips ON clips.id = collection_clips.clip_id
+                    WHERE collection_clips.collection_id = ?1
+                    ORDER BY collection_clips.position ASC
+                    "#,
+                )?;
+                let rows = stmt
+                    .query_map([collection_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+                    .collect::<Result<Vec<_>, _>>()?;
+                rows
+            };
+            if !members.iter().any(|(id, _)| *id == clip_id) {
+                return Err(rusqlite::Error::QueryReturnedNoRows);
+            }
+
+            // Trashed members are invisible to the caller; place the clip before
+            // the live clip currently at `index` and leave trashed ones where they are
+            let mut order: Vec<i32> = members
+                .iter()
+                .map(|(id, _)| *id)
+                .filter(|id| *id != clip_id)
+                .collect();
+            let at = members
+                .iter()
+                .filter(|(id, live)| *live && *id != clip_id)
+                .nth(index)
+                .and_then(|(before, _)| order.iter().position(|id| id == before))
+                .unwrap_or(order.len());
+            order.insert(at, clip_id);
+
+            {
+                let mut update = tx.prepare(
+                    "UPDATE collection_clips SET position = ?1 WHERE collection_id = ?2 AND clip_id = ?3",
+                )?;
+                for (position, id) in order.iter().enumerate() {
+                    update.execute(params![position as i64, collection_id, id])?;
+                }
+            }
+
+            let collection = fetch_collection(&tx, collection_id)?;
+            tx.commit()?;
+            Ok(collection)
+        })
+    }
+
+    /// Lists the live clips in a collection, in collection order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails or the store is locked.
+    pub fn list_collection_clips(&self, collection_id: i32) -> SqliteResult<Vec<Clip>> {
+        let conn = self.reader();
+
+        let mut stmt = conn.prepare(&format!(
+            r#"
+            SELECT {CLIP_COLUMNS}
+            FROM collection_clips
+            JOIN clips ON clips.id = collection_clips.clip_id
+            WHERE collection_clips.collection_id = ?1 AND clips.deleted_at IS NULL
+            ORDER BY collection_clips.position ASC
+            "#
+        ))?;
+
+        let clips = stmt
+            .query_map([collection_id], row_to_clip)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(clips)
+    }
+}
+
+// ===== Helper Functions =====
+
+/// Loads a single collection by ID.
+pub(super) fn fetch_collection(conn: &Connection, id: i32) -> SqliteResult<Collection> {
+    conn.query_row(
+        &format!("SELECT {COLLECTION_COLUMNS} FROM collections WHERE collections.id = ?1"),
+        [id],
+        row_to_collection,
+    )
+}
+
+/// Maps a row selected with [`COLLECTION_COLUMNS`] into a [`Collection`].
+fn row_to_collection(row: &rusqlite::Row<'_>) -> SqliteResult<Collection> {
+    Ok(Collection {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        clip_count: row.get(2)?,
+        created_at: from_epoch_ms(row.get(3)?),
+    })
+}
+
+/// Trims a collection name, rejecting blank names.
+fn collection_name(name: &str) -> SqliteResult<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(rusqlite::Error::ToSqlConversionFailure(
+            "Collection names cannot be empty".into(),
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// Fails if a collection other than `except` is already called `name`, ignoring case.
+fn ensure_name_available(conn: &Connection, name: &str, except: Option<i32>) -> SqliteResult<()> {
+    let existing: Option<i32> = conn
+        .query_row(
+            "SELECT id FROM collections WHERE name = ?1",
+            [name],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    match existing {
+        Some(id) if Some(id) != except => Err(rusqlite::Error::ToSqlConversionFailure(
+            format!("A collection named '{}' already exists", name).into(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{RetentionLimits, RetentionPolicy};
+
+    fn setup_test_store() -> ClipStore {
+        ClipStore::new(":memory:").expect("Failed to create in-memory store")
+    }
+
+    fn save(store: &ClipStore, content: &str) -> i32 {
+        let clip = Clip::new(content.into(), "App".into(), "Win".into(), vec![], false);
+        store.save_clip(clip).unwrap().id.unwrap()
+    }
+
+    fn contents(clips: Vec<Clip>) -> Vec<String> {
+        clips.into_iter().map(|c| c.content).collect()
+    }
+
+    #[test]
+    fn test_create_rename_and_delete() {
+        let store = setup_test_store();
+        let work = store.create_collection("  Work ").unwrap();
+        store.create_collection("Archive").unwrap();
+
+        assert_eq!(work.name, "Work");
+        assert!(
+            store.create_collection("work").is_err(),
+            "names ignore case"
+        );
+        assert!(store.create_collection("   ").is_err());
+        assert!(store.rename_collection(work.id, "archive").is_err());
+
+        let renamed = store.rename_collection(work.id, "Projects").unwrap();
+        assert_eq!(renamed.name, "Projects");
+        let names: Vec<String> = store
+            .list_collections()
+            .unwrap()
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(names, vec!["Archive", "Projects"]);
+
+        let id = save(&store, "kept");
+        store.add_clip_to_collection(work.id, id).unwrap();
+        store.delete_collection(work.id).unwrap();
+        assert!(store.delete_collection(work.id).is_err());
+        assert_eq!(store.list_recent_clips(10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_membership_and_ordering() {
+        let store = setup_test_store();
+        let board = store.create_collection("Board").unwrap();
+        let a = save(&store, "a");
+        let b = save(&store, "b");
+        let c = save(&store, "c");
+
+        for id in [a, b, c, a] {
+            store.add_clip_to_collection(board.id, id).unwrap();
+        }
+        assert_eq!(
+            contents(store.list_collection_clips(board.id).unwrap()),
+            vec!["a", "b", "c"]
+        );
+
+        store.move_clip_in_collection(board.id, c, 0).unwrap();
+        store.move_clip_in_collection(board.id, a, 99).unwrap();
+        assert_eq!(
+            contents(store.list_collection_clips(board.id).unwrap()),
+            vec!["c", "b", "a"]
+        );
+
+        let updated = store.remove_clip_from_collection(board.id, b).unwrap();
+        assert_eq!(updated.clip_count, 2);
+        assert!(store.move_clip_in_collection(board.id, b, 0).is_err());
+        assert!(store.add_clip_to_collection(board.id, 999).is_err());
+    }
+
+    #[test]
+    fn test_trashed_members_are_hidden_until_restored() {
+        let store = setup_test_store();
+        let board = store.create_collection("Board").unwrap();
+        let a = save(&store, "a");
+        let b = save(&store, "b");
+        store.add_clip_to_collection(board.id, a).unwrap();
+        store.add_clip_to_collection(board.id, b).unwrap();
+
+        store.remove_clip(a).unwrap();
+        assert_eq!(store.list_collections().unwrap()[0].clip_count, 1);
+        assert_eq!(
+            contents(store.list_collection_clips(board.id).unwrap()),
+            vec!["b"]
+        );
+
+        store.restore_clip(a).unwrap();
+        assert_eq!(
+            contents(store.list_collection_clips(board.id).unwrap()),
+            vec!["a", "b"]
+        );
+
+        store.remove_clip(a).unwrap();
+        store.empty_trash().unwrap();
+        assert_eq!(store.list_collections().unwrap()[0].clip_count, 1);
+    }
+
+    #[test]
+    fn test_collected_clips_are_exempt_from_cleanup() {
+        let store = setup_test_store();
+        let board = store.create_collection("Board").unwrap();
+        let kept = save(&store, "collected");
+        save(&store, "loose");
+        store.add_clip_to_collection(board.id, kept).unwrap();
+
+        let policy = RetentionPolicy::new(RetentionLimits {
+            max_age_days: None,
+            max_count: Some(0),
+        })
+        .with_pinned(RetentionLimits {
+            max_age_days: None,
+            max_count: Some(0),
+        });
+        let report = store.perform_cleanup(&policy).unwrap();
+
+        assert_eq!(report.total(), 1);
+        assert_eq!(
+            contents(store.list_recent_clips(10).unwrap()),
+            vec!["collected"]
+        );
+    }
+}
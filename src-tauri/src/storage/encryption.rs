@@ -0,0 +1,768 @@
+// src-tauri/src/storage/encryption.rs
+//! Optional encryption at rest for clip content and window titles.
+//!
+//! When enabled, `clips.content` and `clips.window_title` are stored as
+//! `enc:v1:<base64(nonce ‖ ciphertext)>` using ChaCha20-Poly1305. The key is derived
+//! from a user passphrase with Argon2id; only the salt, KDF parameters and an
+//! encrypted verifier are stored (in the `encryption` table), never the key.
+//!
+//! Decryption happens inside SQLite through the `clip_text()` SQL function, so
+//! queries select `clip_text(clips.content)` and read plaintext transparently.
+//! While the store is locked, `clip_text()` fails for encrypted values and writes
+//! are refused; app names, tags, pin state and timestamps remain readable.
+//!
+//! Clip previews, notes and revisions are encrypted the same way. Encrypted
+//! values are kept out of the full-text index, and duplicate detection uses a
+//! keyed hash (HMAC-SHA256) so content hashes reveal nothing on their own.
+//! Free-text search falls back to scanning decrypted content.
+
+// ===== Imports =====
+
+use std::{
+    fmt::Display,
+    sync::{Arc, RwLock, RwLockReadGuard},
+};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use rusqlite::{
+    functions::FunctionFlags, params, types::Value, Connection, OptionalExtension,
+    Result as SqliteResult,
+};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tracing::{info, warn};
+
+use crate::storage::{
+    clip::{content_preview, line_count},
+    ClipStore,
+};
+
+// ===== Constants =====
+
+/// Prefix marking an encrypted column value.
+pub(crate) const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+/// Length of the random KDF salt, in bytes.
+const SALT_LEN: usize = 16;
+
+/// Length of a ChaCha20-Poly1305 nonce, in bytes.
+const NONCE_LEN: usize = 12;
+
+/// Known plaintext encrypted at setup and decrypted on unlock to check the passphrase.
+const VERIFIER_PLAINTEXT: &str = "clipcontex";
+
+// ===== Domain Types =====
+
+/// Argon2id cost parameters used to derive the encryption key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    /// Memory cost in KiB.
+    pub memory_kib: u32,
+    /// Number of passes over memory.
+    pub iterations: u32,
+    /// Degree of parallelism.
+    pub parallelism: u32,
+}
+
+/// Whether encryption is enabled, and whether the store is currently locked.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct EncryptionStatus {
+    /// Clip content and window titles are encrypted at rest.
+    pub enabled: bool,
+    /// The key is not loaded; clip content cannot be read or written.
+    pub locked: bool,
+}
+
+/// Errors raised while enabling, unlocking or using encryption.
+#[derive(Debug)]
+pub enum EncryptionError {
+    /// Encryption has not been enabled for this database.
+    NotEnabled,
+    /// Encryption is already enabled for this database.
+    AlreadyEnabled,
+    /// The store is locked; unlock it with the passphrase first.
+    Locked,
+    /// The passphrase does not match the one used to enable encryption.
+    WrongPassphrase,
+    /// An empty passphrase was given.
+    EmptyPassphrase,
+    /// An encrypted value could not be decoded or authenticated.
+    Corrupted,
+    /// Key derivation or encryption failed.
+    Crypto(String),
+    /// The underlying database operation failed.
+    Storage(rusqlite::Error),
+}
+
+/// Encryption state shared between [`ClipStore`] and the `clip_text()` SQL function.
+#[derive(Debug, Default)]
+pub(super) enum KeyState {
+    /// Encryption is not enabled; values are stored as plaintext.
+    #[default]
+    Plaintext,
+    /// Encryption is enabled but the key is not loaded.
+    Locked,
+    /// Encryption is enabled and the key is loaded.
+    Unlocked(Arc<ClipCipher>),
+}
+
+/// Keys derived from the user passphrase.
+pub(super) struct ClipCipher {
+    aead: ChaCha20Poly1305,
+    mac_key: [u8; 32],
+}
+
+// ===== Public API Implementation =====
+
+impl Default for KdfParams {
+    /// OWASP-recommended Argon2id parameters (19 MiB, 2 passes, 1 lane).
+    fn default() -> Self {
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+impl Display for EncryptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncryptionError::NotEnabled => write!(f, "encryption is not enabled"),
+            EncryptionError::AlreadyEnabled => write!(f, "encryption is already enabled"),
+            EncryptionError::Locked => write!(f, "clip history is locked"),
+            EncryptionError::WrongPassphrase => write!(f, "wrong passphrase"),
+            EncryptionError::EmptyPassphrase => write!(f, "passphrase must not be empty"),
+            EncryptionError::Corrupted => write!(f, "encrypted value is corrupted"),
+            EncryptionError::Crypto(e) => write!(f, "encryption failed: {}", e),
+            EncryptionError::Storage(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for EncryptionError {}
+
+impl From<rusqlite::Error> for EncryptionError {
+    fn from(e: rusqlite::Error) -> Self {
+        EncryptionError::Storage(e)
+    }
+}
+
+impl From<EncryptionError> for rusqlite::Error {
+    fn from(e: EncryptionError) -> Self {
+        match e {
+            EncryptionError::Storage(e) => e,
+            other => rusqlite::Error::UserFunctionError(Box::new(other)),
+        }
+    }
+}
+
+impl ClipStore {
+    /// Returns whether encryption is enabled and whether the store is locked.
+    pub fn encryption_status(&self) -> EncryptionStatus {
+        match &*self.key_state() {
+            KeyState::Plaintext => EncryptionStatus {
+                enabled: false,
+                locked: false,
+            },
+            KeyState::Locked => EncryptionStatus {
+                enabled: true,
+                locked: true,
+            },
+            KeyState::Unlocked(_) => EncryptionStatus {
+                enabled: true,
+                locked: false,
+            },
+        }
+    }
+
+    /// Returns `true` if clip content is encrypted at rest.
+    pub fn is_encrypted(&self) -> bool {
+        !matches!(&*self.key_state(), KeyState::Plaintext)
+    }
+
+    /// Returns `true` if encryption is enabled and the key is not loaded.
+    pub fn is_locked(&self) -> bool {
+        matches!(&*self.key_state(), KeyState::Locked)
+    }
+
+    /// Enables encryption, encrypting all existing clips in place.
+    ///
+    /// Existing plaintext is removed from the full-text index, content hashes are
+    /// replaced by keyed hashes, and the database is vacuumed so freed pages do not
+    /// retain plaintext. The store is left unlocked.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EncryptionError::AlreadyEnabled`] if encryption is already on,
+    /// [`EncryptionError::EmptyPassphrase`] for an empty passphrase, or another
+    /// error if key derivation or the database update fails. On error no clip is
+    /// left encrypted.
+    pub fn enable_encryption(&self, passphrase: &str) -> Result<(), EncryptionError> {
+        self.enable_encryption_with(passphrase, KdfParams::default())
+    }
+
+    /// Like [`ClipStore::enable_encryption`], with explicit KDF parameters.
+    pub(crate) fn enable_encryption_with(
+        &self,
+        passphrase: &str,
+        kdf: KdfParams,
+    ) -> Result<(), EncryptionError> {
+        if self.is_encrypted() {
+            return Err(EncryptionError::AlreadyEnabled);
+        }
+        if passphrase.is_empty() {
+            return Err(EncryptionError::EmptyPassphrase);
+        }
+
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let cipher = ClipCipher::derive(passphrase, &salt, kdf)?;
+        let verifier = cipher.encrypt(VERIFIER_PLAINTEXT)?;
+
+        let count = self.write_key_state(move |conn| {
+            conn.execute_batch("PRAGMA secure_delete = ON;")?;
+
+            let tx = conn.transaction()?;
+            tx.execute(
+                r#"
+                INSERT INTO encryption (id, salt, memory_kib, iterations, parallelism, verifier, created_at)
+                VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6)
+                "#,
+                params![
+                    BASE64.encode(salt),
+                    kdf.memory_kib,
+                    kdf.iterations,
+                    kdf.parallelism,
+                    verifier,
+                    Utc::now().to_rfc3339(),
+                ],
+            )?;
+
+            let plaintext: Vec<(i64, String, Option<String>, Option<String>)> = {
+                let mut stmt = tx.prepare("SELECT id, content, window_title, note FROM clips")?;
+                let rows = stmt
+                    .query_map([], |row| {
+                        Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+                rows
+            };
+
+            let count = plaintext.len();
+            for (id, content, window_title, note) in plaintext {
+                let window_title = window_title.map(|t| cipher.encrypt(&t)).transpose()?;
+                let note = note.map(|n| cipher.encrypt(&n)).transpose()?;
+                let preview = content_preview(&content)
+                    .map(|p| cipher.encrypt(p))
+                    .transpose()?;
+                // The FTS update trigger blanks encrypted columns in the index
+                tx.execute(
+                    r#"
+                    UPDATE clips
+                    SET content = ?1, window_title = ?2, content_hash = ?3, preview = ?4,
+                        byte_len = ?5, line_count = ?6, note = ?7
+                    WHERE id = ?8
+                    "#,
+                    params![
+                        cipher.encrypt(&content)?,
+                        window_title,
+                        cipher.content_hash(&content),
+                        preview,
+                        content.len() as i64,
+                        line_count(&content),
+                        note,
+                        id
+                    ],
+                )?;
+            }
+
+            let revisions: Vec<(i64, String)> = {
+                let mut stmt = tx.prepare("SELECT id, content FROM clip_revisions")?;
+                let rows = stmt
+                    .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                    .collect::<Result<Vec<_>, _>>()?;
+                rows
+            };
+            for (id, content) in revisions {
+                tx.execute(
+                    "UPDATE clip_revisions SET content = ?1 WHERE id = ?2",
+                    params![cipher.encrypt(&content)?, id],
+                )?;
+            }
+
+            // Updating the index only adds delete markers; the old segments still
+            // hold every plaintext token until they are merged away
+            tx.execute_batch("INSERT INTO clips_fts(clips_fts) VALUES ('rebuild');")?;
+            tx.commit()?;
+
+            Ok((count, KeyState::Unlocked(Arc::new(cipher))))
+        })?;
+
+        // Drop plaintext left in free pages and the write-ahead log
+        let vacuumed =
+            self.write(|conn| conn.execute_batch("VACUUM; PRAGMA wal_checkpoint(TRUNCATE);"));
+        if let Err(e) = vacuumed {
+            warn!("Failed to vacuum database after enabling encryption: {}", e);
+        }
+
+        info!("Encryption enabled; {} clips encrypted", count);
+        Ok(())
+    }
+
+    /// Loads the encryption key derived from `passphrase`, unlocking the store.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EncryptionError::NotEnabled`] if encryption is off,
+    /// [`EncryptionError::WrongPassphrase`] if the passphrase does not match, or
+    /// another error if the key parameters cannot be read.
+    pub fn unlock(&self, passphrase: &str) -> Result<(), EncryptionError> {
+        if !self.is_encrypted() {
+            return Err(EncryptionError::NotEnabled);
+        }
+
+        let (salt, kdf, verifier) = {
+            self.reader().query_row(
+                "SELECT salt, memory_kib, iterations, parallelism, verifier FROM encryption WHERE id = 1",
+                [],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        KdfParams {
+                            memory_kib: row.get(1)?,
+                            iterations: row.get(2)?,
+                            parallelism: row.get(3)?,
+                        },
+                        row.get::<_, String>(4)?,
+                    ))
+                },
+            )?
+        };
+
+        // Derive after releasing the connection; Argon2 is deliberately slow
+        let salt = BASE64
+            .decode(salt)
+            .map_err(|_| EncryptionError::Corrupted)?;
+        let cipher = ClipCipher::derive(passphrase, &salt, kdf)?;
+
+        match cipher.decrypt(&verifier) {
+            Ok(text) if text == VERIFIER_PLAINTEXT => {
+                *self.key_state_mut() = KeyState::Unlocked(Arc::new(cipher));
+                info!("Clip history unlocked");
+                Ok(())
+            }
+            _ => Err(EncryptionError::WrongPassphrase),
+        }
+    }
+
+    /// Forgets the encryption key, locking the store.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EncryptionError::NotEnabled`] if encryption is off.
+    pub fn lock(&self) -> Result<(), EncryptionError> {
+        let mut state = self.key_state_mut();
+        if matches!(&*state, KeyState::Plaintext) {
+            return Err(EncryptionError::NotEnabled);
+        }
+
+        *state = KeyState::Locked;
+        info!("Clip history locked");
+        Ok(())
+    }
+}
+
+impl ClipCipher {
+    /// Derives the encryption and MAC keys from a passphrase with Argon2id.
+    fn derive(passphrase: &str, salt: &[u8], kdf: KdfParams) -> Result<Self, EncryptionError> {
+        let params = Params::new(kdf.memory_kib, kdf.iterations, kdf.parallelism, Some(64))
+            .map_err(|e| EncryptionError::Crypto(e.to_string()))?;
+
+        let mut okm = [0u8; 64];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), salt, &mut okm)
+            .map_err(|e| EncryptionError::Crypto(e.to_string()))?;
+
+        let aead = ChaCha20Poly1305::new(Key::from_slice(&okm[..32]));
+        let mut mac_key = [0u8; 32];
+        mac_key.copy_from_slice(&okm[32..]);
+        okm.fill(0);
+
+        Ok(Self { aead, mac_key })
+    }
+
+    /// Encrypts `plaintext` into a prefixed, base64-encoded column value.
+    pub(super) fn encrypt(&self, plaintext: &str) -> Result<String, EncryptionError> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .aead
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|e| EncryptionError::Crypto(e.to_string()))?;
+
+        let mut blob = nonce.to_vec();
+        blob.extend_from_slice(&ciphertext);
+        Ok(format!("{ENCRYPTED_PREFIX}{}", BASE64.encode(blob)))
+    }
+
+    /// Decrypts a value produced by [`ClipCipher::encrypt`].
+    ///
+    /// Values without the encryption prefix are returned unchanged.
+    pub(super) fn decrypt(&self, value: &str) -> Result<String, EncryptionError> {
+        let Some(encoded) = value.strip_prefix(ENCRYPTED_PREFIX) else {
+            return Ok(value.to_string());
+        };
+
+        let blob = BASE64
+            .decode(encoded)
+            .map_err(|_| EncryptionError::Corrupted)?;
+        if blob.len() < NONCE_LEN {
+            return Err(EncryptionError::Corrupted);
+        }
+
+        let (nonce, ciphertext) = blob.split_at(NONCE_LEN);
+        let plaintext = self
+            .aead
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| EncryptionError::Corrupted)?;
+
+        String::from_utf8(plaintext).map_err(|_| EncryptionError::Corrupted)
+    }
+
+    /// Returns the hex-encoded HMAC-SHA256 of `content`, used to find duplicates.
+    pub(super) fn content_hash(&self, content: &str) -> String {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&self.mac_key)
+            .expect("HMAC accepts keys of any length");
+        mac.update(content.as_bytes());
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for ClipCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClipCipher").finish_non_exhaustive()
+    }
+}
+
+// ===== Helper Functions =====
+
+/// Reads the initial key state: locked if the database has encryption enabled.
+pub(super) fn initial_key_state(conn: &Connection) -> SqliteResult<KeyState> {
+    let enabled = conn
+        .query_row("SELECT 1 FROM encryption WHERE id = 1", [], |_| Ok(()))
+        .optional()?
+        .is_some();
+
+    if enabled {
+        conn.execute_batch("PRAGMA secure_delete = ON;")?;
+        Ok(KeyState::Locked)
+    } else {
+        Ok(KeyState::Plaintext)
+    }
+}
+
+/// Registers the `clip_text(value)` SQL function, which decrypts encrypted values
+/// with the currently loaded key and passes other values through unchanged.
+pub(super) fn register_clip_text_function(
+    conn: &Connection,
+    key_state: Arc<RwLock<KeyState>>,
+) -> SqliteResult<()> {
+    conn.create_scalar_function("clip_text", 1, FunctionFlags::SQLITE_UTF8, move |ctx| {
+        let text = match ctx.get::<Value>(0)? {
+            Value::Text(text) => text,
+            other => return Ok(other),
+        };
+
+        if !text.starts_with(ENCRYPTED_PREFIX) {
+            return Ok(Value::Text(text));
+        }
+
+        match &*read_state(&key_state) {
+            KeyState::Unlocked(cipher) => Ok(Value::Text(cipher.decrypt(&text)?)),
+            KeyState::Locked => Err(EncryptionError::Locked.into()),
+            KeyState::Plaintext => Ok(Value::Text(text)),
+        }
+    })
+}
+
+/// Returns the cipher of `state`, `None` if plaintext, or an error if locked.
+pub(super) fn cipher_of(state: &KeyState) -> SqliteResult<Option<Arc<ClipCipher>>> {
+    match state {
+        KeyState::Plaintext => Ok(None),
+        KeyState::Locked => Err(EncryptionError::Locked.into()),
+        KeyState::Unlocked(cipher) => Ok(Some(cipher.clone())),
+    }
+}
+
+/// Acquires a read lock on the key state, recovering from poisoning.
+pub(super) fn read_state(key_state: &RwLock<KeyState>) -> RwLockReadGuard<'_, KeyState> {
+    key_state.read().unwrap_or_else(|e| e.into_inner())
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{Clip, ClipQuery, SaveOutcome};
+
+    /// Cheap parameters so tests do not spend seconds in Argon2.
+    const TEST_KDF: KdfParams = KdfParams {
+        memory_kib: 64,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    fn setup_test_store() -> ClipStore {
+        ClipStore::new(":memory:").expect("Failed to create in-memory store")
+    }
+
+    fn save(store: &ClipStore, content: &str, window: &str) -> Clip {
+        let clip = Clip::new(content.into(), "App".into(), window.into(), vec![], false);
+        store.save_clip(clip).unwrap()
+    }
+
+    fn raw_columns(store: &ClipStore) -> Vec<(String, String)> {
+        let conn = store.reader();
+        let mut stmt = conn
+            .prepare("SELECT content, window_title FROM clips ORDER BY id")
+            .unwrap();
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        rows
+    }
+
+    #[test]
+    fn test_enable_encrypts_existing_clips_in_place() {
+        let store = setup_test_store();
+        save(&store, "secret token", "Vault");
+
+        store.enable_encryption_with("hunter2", TEST_KDF).unwrap();
+        save(&store, "another secret", "Terminal");
+
+        for (content, window_title) in raw_columns(&store) {
+            assert!(content.starts_with(ENCRYPTED_PREFIX));
+            assert!(window_title.starts_with(ENCRYPTED_PREFIX));
+        }
+
+        let fts_hits: i64 = store
+            .reader()
+            .query_row(
+                "SELECT COUNT(*) FROM clips_fts WHERE clips_fts MATCH 'secret OR vault'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(fts_hits, 0, "plaintext must not remain in the index");
+
+        let clips = store.list_recent_clips(10).unwrap();
+        assert_eq!(clips[0].content, "another secret");
+        assert_eq!(clips[1].content, "secret token");
+        assert_eq!(clips[1].window_title, "Vault");
+    }
+
+    #[test]
+    fn test_previews_of_long_clips_are_encrypted() {
+        let store = setup_test_store();
+        let long = "secret ".repeat(200);
+        let id = save(&store, &long, "Win").id.unwrap();
+
+        store.enable_encryption_with("hunter2", TEST_KDF).unwrap();
+
+        let preview: String = store
+            .reader()
+            .query_row("SELECT preview FROM clips WHERE id = ?1", [id], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert!(preview.starts_with(ENCRYPTED_PREFIX));
+
+        let listed = &store.list_recent_clips(1).unwrap()[0];
+        assert!(listed.is_truncated);
+        assert!(long.starts_with(&listed.content));
+        assert_eq!(store.get_clip_content(id).unwrap(), long);
+    }
+
+    #[test]
+    fn test_locked_store_refuses_content_access() {
+        let store = setup_test_store();
+        save(&store, "secret", "Win");
+        store.enable_encryption_with("hunter2", TEST_KDF).unwrap();
+
+        store.lock().unwrap();
+        assert!(store.is_locked());
+        assert!(store.list_recent_clips(10).is_err());
+        let clip = Clip::new("new".into(), "App".into(), "Win".into(), vec![], false);
+        assert!(store.save_clip(clip).is_err());
+
+        assert!(matches!(
+            store.unlock("wrong"),
+            Err(EncryptionError::WrongPassphrase)
+        ));
+        store.unlock("hunter2").unwrap();
+        assert_eq!(store.list_recent_clips(10).unwrap()[0].content, "secret");
+    }
+
+    #[test]
+    fn test_reopened_database_starts_locked() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("encrypted.db");
+        {
+            let store = ClipStore::new(&db_path).unwrap();
+            save(&store, "persisted secret", "Win");
+            store.enable_encryption_with("hunter2", TEST_KDF).unwrap();
+        }
+
+        let store = ClipStore::new(&db_path).unwrap();
+        assert_eq!(
+            store.encryption_status(),
+            EncryptionStatus {
+                enabled: true,
+                locked: true
+            }
+        );
+
+        store.unlock("hunter2").unwrap();
+        assert_eq!(
+            store.list_recent_clips(1).unwrap()[0].content,
+            "persisted secret"
+        );
+    }
+
+    #[test]
+    fn test_no_plaintext_left_in_database_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("encrypted.db");
+        {
+            let store = ClipStore::new(&db_path).unwrap();
+            for i in 0..50 {
+                save(&store, &format!("zebracorn{i}"), &format!("quokkatitle{i}"));
+            }
+            store.enable_encryption_with("hunter2", TEST_KDF).unwrap();
+        }
+
+        for file in ["encrypted.db", "encrypted.db-wal"] {
+            let bytes = std::fs::read(dir.path().join(file)).unwrap_or_default();
+            for secret in [&b"zebracorn"[..], b"quokkatitle"] {
+                assert!(
+                    !bytes.windows(secret.len()).any(|w| w == secret),
+                    "{file} contains {}",
+                    String::from_utf8_lossy(secret)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_dedupe_and_search_work_when_encrypted() {
+        let store = setup_test_store();
+        store.enable_encryption_with("hunter2", TEST_KDF).unwrap();
+        save(&store, "deploy --prod", "Terminal");
+
+        let again = Clip::new(
+            "deploy --prod".into(),
+            "App".into(),
+            "Win".into(),
+            vec![],
+            false,
+        );
+        assert!(matches!(
+            store.save_or_bump_clip(again).unwrap(),
+            SaveOutcome::Bumped(_)
+        ));
+
+        let query = ClipQuery::parse("deploy").unwrap();
+        let results = store.search_clips(&query, 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].clip.content, "deploy --prod");
+    }
+
+    #[test]
+    fn test_notes_are_encrypted_and_still_searchable() {
+        let store = setup_test_store();
+        let id = save(&store, "plain", "Terminal").id.unwrap();
+        store.set_clip_note(id, Some("before encryption")).unwrap();
+        store.enable_encryption_with("hunter2", TEST_KDF).unwrap();
+        let other = save(&store, "other", "Terminal").id.unwrap();
+        store
+            .set_clip_note(other, Some("after encryption"))
+            .unwrap();
+
+        let notes: Vec<String> = store
+            .reader()
+            .prepare("SELECT note FROM clips ORDER BY id")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert!(notes.iter().all(|n| n.starts_with(ENCRYPTED_PREFIX)));
+
+        let query = ClipQuery::parse("before").unwrap();
+        let results = store.search_clips(&query, 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].clip.note.as_deref(), Some("before encryption"));
+    }
+
+    #[test]
+    fn test_revisions_are_encrypted() {
+        let store = setup_test_store();
+        let id = save(&store, "draft", "Editor").id.unwrap();
+        store
+            .update_clip_content(id, "final".into(), |_, _| vec![])
+            .unwrap();
+        store.enable_encryption_with("hunter2", TEST_KDF).unwrap();
+        store
+            .update_clip_content(id, "final, edited".into(), |_, _| vec![])
+            .unwrap();
+
+        let raw: Vec<String> = store
+            .reader()
+            .prepare("SELECT content FROM clip_revisions ORDER BY id")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(raw.len(), 2);
+        assert!(raw.iter().all(|c| c.starts_with(ENCRYPTED_PREFIX)));
+
+        let contents: Vec<String> = store
+            .list_clip_revisions(id)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.content)
+            .collect();
+        assert_eq!(contents, vec!["final", "draft"]);
+    }
+
+    #[test]
+    fn test_enable_twice_or_with_empty_passphrase_fails() {
+        let store = setup_test_store();
+        assert!(matches!(
+            store.enable_encryption_with("", TEST_KDF),
+            Err(EncryptionError::EmptyPassphrase)
+        ));
+        assert!(matches!(store.lock(), Err(EncryptionError::NotEnabled)));
+
+        store.enable_encryption_with("hunter2", TEST_KDF).unwrap();
+        assert!(matches!(
+            store.enable_encryption_with("again", TEST_KDF),
+            Err(EncryptionError::AlreadyEnabled)
+        ));
+    }
+}
@@ -0,0 +1,258 @@
+// src-tauri/src/storage/expiry.rs
+//! Self-destructing clips.
+//!
+//! A clip may carry an `expires_at` time, after which
+//! [`ClipStore::delete_expired_clips`] deletes it, and a `delete_after_use` flag,
+//! which makes [`ClipStore::record_clip_use`] delete it as soon as it is pasted.
+//! Both are meant for short-lived secrets such as one-time codes, so such clips
+//! are deleted for good rather than moved to the trash. Expiry applies to
+//! trashed clips too.
+//!
+//! Storage only records and enforces expiry times; deleting clips on time is up
+//! to a scheduler that sleeps until [`ClipStore::next_expiry`].
+
+// ===== Imports =====
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, params_from_iter, types::Value, Connection, Result as SqliteResult};
+use tracing::debug;
+
+use crate::storage::{
+    clip_store::{fetch_clip, from_epoch_ms},
+    tags::prune_unused_tags,
+    Clip, ClipStore,
+};
+
+// ===== Constants =====
+
+// Both expiry queries repeat the `expires_at IS NOT NULL` predicate of the
+// partial index `idx_clips_expires_at`; without it SQLite scans every clip.
+
+/// Selects the earliest expiry time.
+const NEXT_EXPIRY_QUERY: &str = "SELECT min(expires_at) FROM clips WHERE expires_at IS NOT NULL";
+
+/// Matches clips that expired at or before the bound time.
+const EXPIRED_CONDITION: &str = "expires_at IS NOT NULL AND expires_at <= ?";
+
+// ===== Public API Implementation =====
+
+impl ClipStore {
+    /// Sets when a live clip expires and whether it is deleted after its next use.
+    ///
+    /// Pass `None` as `expires_at` to keep the clip until it is deleted otherwise.
+    /// Returns the updated clip.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`rusqlite::Error::QueryReturnedNoRows`] if no live clip has the
+    /// given ID, or another error if the update fails.
+    pub fn set_clip_expiry(
+        &self,
+        id: i32,
+        expires_at: Option<DateTime<Utc>>,
+        delete_after_use: bool,
+    ) -> SqliteResult<Clip> {
+        self.write(move |conn| {
+            let updated = conn.execute(
+                r#"
+                UPDATE clips SET expires_at = ?1, delete_after_use = ?2, updated_at = ?3
+                WHERE id = ?4 AND deleted_at IS NULL
+                "#,
+                params![
+                    expires_at.map(|t| t.timestamp_millis()),
+                    delete_after_use,
+                    Utc::now().timestamp_millis(),
+                    id
+                ],
+            )?;
+            if updated == 0 {
+                return Err(rusqlite::Error::QueryReturnedNoRows);
+            }
+            fetch_clip(conn, id)
+        })
+    }
+
+    /// Returns the earliest expiry time of any clip, live or trashed, or `None`
+    /// if no clip expires.
+    ///
+    /// The time may already have passed if expired clips have not been deleted yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn next_expiry(&self) -> SqliteResult<Option<DateTime<Utc>>> {
+        let conn = self.reader();
+        let next: Option<i64> = conn.query_row(NEXT_EXPIRY_QUERY, [], |row| row.get(0))?;
+        Ok(next.map(from_epoch_ms))
+    }
+
+    /// Permanently deletes every clip that expired at or before `now`.
+    ///
+    /// Works while the encrypted history is locked. Returns the IDs of the
+    /// deleted clips.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the deletion fails.
+    pub fn delete_expired_clips(&self, now: DateTime<Utc>) -> SqliteResult<Vec<i32>> {
+        self.write(move |conn| {
+            let tx = conn.transaction()?;
+            let ids = delete_clips(
+                &tx,
+                EXPIRED_CONDITION,
+                vec![Value::Integer(now.timestamp_millis())],
+            )?;
+            tx.commit()?;
+
+            debug!("Deleted {} expired clips", ids.len());
+            Ok(ids)
+        })
+    }
+}
+
+// ===== Helper Functions =====
+
+/// Permanently deletes the clips matching `condition` and returns their IDs, in no
+/// particular order.
+pub(super) fn delete_clips(
+    conn: &Connection,
+    condition: &str,
+    values: Vec<Value>,
+) -> SqliteResult<Vec<i32>> {
+    let mut stmt = conn.prepare(&format!("SELECT id FROM clips WHERE {condition}"))?;
+    let ids = stmt
+        .query_map(params_from_iter(&values), |row| row.get(0))?
+        .collect::<Result<Vec<i32>, _>>()?;
+
+    if !ids.is_empty() {
+        conn.execute(
+            &format!("DELETE FROM clips WHERE {condition}"),
+            params_from_iter(values),
+        )?;
+        prune_unused_tags(conn)?;
+    }
+
+    Ok(ids)
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::KdfParams;
+    use chrono::Duration;
+
+    fn setup_test_store() -> ClipStore {
+        ClipStore::new(":memory:").expect("Failed to create in-memory store")
+    }
+
+    fn save(store: &ClipStore, content: &str) -> i32 {
+        let clip = Clip::new(
+            content.into(),
+            "Authenticator".into(),
+            "Win".into(),
+            vec!["#otp".into()],
+            false,
+        );
+        store.save_clip(clip).unwrap().id.unwrap()
+    }
+
+    #[test]
+    fn test_expired_clips_are_deleted_for_good() {
+        let store = setup_test_store();
+        let now = Utc::now();
+        let soon = save(&store, "123456");
+        let later = save(&store, "654321");
+        let kept = save(&store, "keep me");
+        assert_eq!(store.next_expiry().unwrap(), None);
+
+        let soon_at = now + Duration::seconds(30);
+        let clip = store.set_clip_expiry(soon, Some(soon_at), false).unwrap();
+        assert_eq!(
+            clip.expires_at,
+            Some(from_epoch_ms(soon_at.timestamp_millis()))
+        );
+        store
+            .set_clip_expiry(later, Some(now + Duration::minutes(5)), false)
+            .unwrap();
+        store.remove_clip(later).unwrap();
+        assert_eq!(store.next_expiry().unwrap(), clip.expires_at);
+
+        assert!(store.delete_expired_clips(now).unwrap().is_empty());
+        let mut deleted = store
+            .delete_expired_clips(now + Duration::minutes(10))
+            .unwrap();
+        deleted.sort_unstable();
+        assert_eq!(deleted, vec![soon, later]);
+
+        assert!(store.list_trash(10).unwrap().is_empty());
+        let remaining: Vec<Option<i32>> = store
+            .list_recent_clips(10)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.id)
+            .collect();
+        assert_eq!(remaining, vec![Some(kept)]);
+        assert_eq!(store.next_expiry().unwrap(), None);
+    }
+
+    #[test]
+    fn test_expiry_queries_use_index() {
+        let store = setup_test_store();
+        let conn = store.reader();
+        let plan = |sql: &str, values: Vec<Value>| -> String {
+            let mut stmt = conn.prepare(&format!("EXPLAIN QUERY PLAN {sql}")).unwrap();
+            let details = stmt
+                .query_map(params_from_iter(values), |row| row.get::<_, String>(3))
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            details.join("\n")
+        };
+
+        let next = plan(NEXT_EXPIRY_QUERY, vec![]);
+        assert!(next.contains("idx_clips_expires_at"), "{next}");
+
+        let expired = plan(
+            &format!("SELECT id FROM clips WHERE {EXPIRED_CONDITION}"),
+            vec![Value::Integer(0)],
+        );
+        assert!(expired.contains("idx_clips_expires_at"), "{expired}");
+    }
+
+    #[test]
+    fn test_delete_after_use_deletes_on_first_use() {
+        let store = setup_test_store();
+        let id = save(&store, "temporary password");
+        let clip = store.set_clip_expiry(id, None, true).unwrap();
+        assert!(clip.delete_after_use);
+
+        let used = store.record_clip_use(id).unwrap();
+        assert_eq!(used.use_count, 1);
+        assert!(store.get_clip_content(id).is_err());
+        assert!(store.list_tags().unwrap().is_empty());
+
+        assert!(store.set_clip_expiry(id, None, false).is_err());
+    }
+
+    #[test]
+    fn test_expired_clips_are_deleted_while_locked() {
+        let store = setup_test_store();
+        let id = save(&store, "123456");
+        let expires_at = Utc::now() - Duration::seconds(1);
+        store.set_clip_expiry(id, Some(expires_at), false).unwrap();
+
+        let kdf = KdfParams {
+            memory_kib: 64,
+            iterations: 1,
+            parallelism: 1,
+        };
+        store.enable_encryption_with("hunter2", kdf).unwrap();
+        store.lock().unwrap();
+
+        assert_eq!(store.delete_expired_clips(Utc::now()).unwrap(), vec![id]);
+        store.unlock("hunter2").unwrap();
+        assert!(store.list_recent_clips(10).unwrap().is_empty());
+    }
+}
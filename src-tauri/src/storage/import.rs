@@ -0,0 +1,146 @@
+// src-tauri/src/storage/import.rs
+//! Bulk insertion of clips imported from other histories.
+//!
+//! Imported clips keep their own timestamps, counters and pin status. Clips whose
+//! content is already stored (as a live clip, or earlier in the same import) are
+//! skipped rather than bumped, so importing the same file twice changes nothing.
+
+// ===== Imports =====
+
+use rusqlite::Result as SqliteResult;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::storage::{
+    clip::content_hash,
+    clip_store::{find_duplicate, insert_clip},
+    Clip, ClipStore,
+};
+
+// ===== Domain Types =====
+
+/// Outcome of an import.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Number of clips added to the history.
+    pub imported: usize,
+    /// Number of entries left out: duplicates of stored clips, or unsupported
+    /// entries such as images.
+    pub skipped: usize,
+    /// Number of entries that could not be read or stored.
+    pub failed: usize,
+}
+
+// ===== Public API Implementation =====
+
+impl ClipStore {
+    /// Inserts imported clips, skipping any whose content is already stored.
+    ///
+    /// All clips are inserted in a single transaction. A clip that cannot be
+    /// stored is counted as failed without aborting the others.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be started or committed, or if
+    /// the store is locked.
+    pub fn import_clips(&self, clips: Vec<Clip>) -> SqliteResult<ImportReport> {
+        self.write_with_cipher(move |conn, cipher| {
+            let tx = conn.transaction()?;
+
+            let mut report = ImportReport::default();
+            for clip in clips {
+                let hash = match cipher {
+                    Some(cipher) => cipher.content_hash(&clip.content),
+                    None => content_hash(&clip.content),
+                };
+
+                match find_duplicate(&tx, &hash, &clip.content) {
+                    Ok(Some(_)) => report.skipped += 1,
+                    Ok(None) => match insert_clip(&tx, clip, cipher) {
+                        Ok(_) => report.imported += 1,
+                        Err(e) => {
+                            warn!("Failed to import clip: {}", e);
+                            report.failed += 1;
+                        }
+                    },
+                    Err(e) => {
+                        warn!("Failed to check imported clip for duplicates: {}", e);
+                        report.failed += 1;
+                    }
+                }
+            }
+
+            tx.commit()?;
+
+            debug!(
+                "Imported {} clips ({} skipped, {} failed)",
+                report.imported, report.skipped, report.failed
+            );
+            Ok(report)
+        })
+    }
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn setup_test_store() -> ClipStore {
+        ClipStore::new(":memory:").expect("Failed to create in-memory store")
+    }
+
+    fn clip(content: &str) -> Clip {
+        Clip::new(content.into(), "App".into(), "Win".into(), vec![], false)
+    }
+
+    #[test]
+    fn test_import_skips_stored_and_repeated_content() {
+        let store = setup_test_store();
+        store.save_clip(clip("already here")).unwrap();
+
+        let report = store
+            .import_clips(vec![clip("already here"), clip("new"), clip("new")])
+            .unwrap();
+
+        assert_eq!(
+            report,
+            ImportReport {
+                imported: 1,
+                skipped: 2,
+                failed: 0
+            }
+        );
+        assert_eq!(store.list_recent_clips(10).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_import_keeps_original_metadata() {
+        let store = setup_test_store();
+        let created_at = Utc::now() - Duration::days(400);
+        let mut old = Clip::new(
+            "from last year".into(),
+            "Editor".into(),
+            "notes.txt".into(),
+            vec!["#note".into()],
+            true,
+        );
+        old.created_at = created_at;
+        old.last_copied_at = created_at;
+        old.copy_count = 3;
+        old.use_count = 2;
+        old.last_used_at = Some(created_at);
+
+        store.import_clips(vec![old]).unwrap();
+
+        let stored = &store.list_recent_clips(1).unwrap()[0];
+        assert_eq!(stored.created_at.timestamp(), created_at.timestamp());
+        assert_eq!(stored.copy_count, 3);
+        assert_eq!(stored.use_count, 2);
+        assert!(stored.last_used_at.is_some());
+        assert!(stored.is_pinned);
+        assert_eq!(stored.tags, vec!["#note"]);
+    }
+}
@@ -0,0 +1,1001 @@
+// src-tauri/src/storage/migrations.rs
+//! Versioned schema migrations for the clip database.
+//!
+//! The schema version is tracked with SQLite's `PRAGMA user_version`. Each
+//! [`Migration`] upgrades the schema by exactly one version and runs inside its
+//! own transaction together with the version bump, so a failed step leaves the
+//! database untouched at the previous version.
+//!
+//! Applied migrations are additionally recorded in the `schema_migrations` table
+//! (version, name, timestamp) for diagnostics.
+//!
+//! # Adding a migration
+//!
+//! Append a new entry to [`MIGRATIONS`] with the next version number. Never edit
+//! or reorder migrations that have already shipped; existing databases have
+//! recorded them as applied.
+
+// ===== Imports =====
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, Result as SqliteResult, Transaction};
+use tracing::info;
+
+use crate::storage::clip::{content_hash, PREVIEW_CHARS};
+
+// ===== Domain Types =====
+
+/// A single, ordered schema upgrade step.
+pub(crate) struct Migration {
+    /// The schema version after this migration has been applied.
+    pub version: u32,
+    /// A short, human-readable description stored in `schema_migrations`.
+    pub name: &'static str,
+    /// Applies the migration. Runs inside a transaction owned by the runner.
+    pub up: fn(&Transaction<'_>) -> SqliteResult<()>,
+}
+
+// ===== Migration Registry =====
+
+/// All known migrations, in ascending version order.
+pub(crate) const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "create_clips",
+        up: create_clips,
+    },
+    Migration {
+        version: 2,
+        name: "add_full_text_search",
+        up: add_full_text_search,
+    },
+    Migration {
+        version: 3,
+        name: "normalize_tags",
+        up: normalize_tags,
+    },
+    Migration {
+        version: 4,
+        name: "add_content_hash",
+        up: add_content_hash,
+    },
+    Migration {
+        version: 5,
+        name: "track_clip_usage",
+        up: track_clip_usage,
+    },
+    Migration {
+        version: 6,
+        name: "add_encryption",
+        up: add_encryption,
+    },
+    Migration {
+        version: 7,
+        name: "add_clip_previews",
+        up: add_clip_previews,
+    },
+    Migration {
+        version: 8,
+        name: "add_trash",
+        up: add_trash,
+    },
+    Migration {
+        version: 9,
+        name: "use_epoch_timestamps",
+        up: use_epoch_timestamps,
+    },
+    Migration {
+        version: 10,
+        name: "add_collections",
+        up: add_collections,
+    },
+    Migration {
+        version: 11,
+        name: "add_user_tags_and_notes",
+        up: add_user_tags_and_notes,
+    },
+    Migration {
+        version: 12,
+        name: "add_clip_revisions",
+        up: add_clip_revisions,
+    },
+    Migration {
+        version: 13,
+        name: "add_snippets",
+        up: add_snippets,
+    },
+    Migration {
+        version: 14,
+        name: "add_clip_expiry",
+        up: add_clip_expiry,
+    },
+];
+
+// ===== Public API =====
+
+/// Returns the schema version produced by the newest known migration.
+pub fn latest_version() -> u32 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// Reads the current schema version from `PRAGMA user_version`.
+///
+/// # Errors
+///
+/// Returns an error if the pragma cannot be read.
+pub fn current_version(conn: &Connection) -> SqliteResult<u32> {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+}
+
+/// Upgrades the database to the latest schema version.
+///
+/// Returns the versions that were applied (empty if already up to date).
+///
+/// # Errors
+///
+/// Returns an error if a migration fails, or if the database was written by a
+/// newer version of the application than this one supports.
+pub fn run_migrations(conn: &mut Connection) -> SqliteResult<Vec<u32>> {
+    apply_migrations(conn, MIGRATIONS, latest_version())
+}
+
+// ===== Helper Functions =====
+
+/// Applies every migration in `migrations` above the current version, up to and including `target`.
+pub(crate) fn apply_migrations(
+    conn: &mut Connection,
+    migrations: &[Migration],
+    target: u32,
+) -> SqliteResult<Vec<u32>> {
+    let current = current_version(conn)?;
+    let supported = migrations.last().map(|m| m.version).unwrap_or(0);
+
+    if current > supported {
+        return Err(rusqlite::Error::ToSqlConversionFailure(
+            format!(
+                "Database schema version {} is newer than the supported version {}",
+                current, supported
+            )
+            .into(),
+        ));
+    }
+
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        );
+        "#,
+    )?;
+
+    let mut applied = Vec::new();
+
+    for migration in migrations
+        .iter()
+        .filter(|m| m.version > current && m.version <= target)
+    {
+        let tx = conn.transaction()?;
+
+        (migration.up)(&tx)?;
+        tx.execute(
+            "INSERT INTO schema_migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+            params![migration.version, migration.name, Utc::now().to_rfc3339()],
+        )?;
+        tx.pragma_update(None, "user_version", migration.version)?;
+
+        tx.commit()?;
+
+        info!(
+            "Applied database migration {} ({})",
+            migration.version, migration.name
+        );
+        applied.push(migration.version);
+    }
+
+    Ok(applied)
+}
+
+// ===== Migrations =====
+
+/// v1: The original clips table.
+///
+/// Uses `IF NOT EXISTS` so databases created before migrations existed
+/// (which report `user_version = 0`) are adopted as-is.
+fn create_clips(tx: &Transaction<'_>) -> SqliteResult<()> {
+    tx.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS clips (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            content TEXT NOT NULL,
+            app_name TEXT,
+            window_title TEXT,
+            auto_tags TEXT,
+            is_pinned BOOLEAN NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_created_at ON clips(created_at DESC);
+        CREATE INDEX IF NOT EXISTS idx_is_pinned ON clips(is_pinned);
+        "#,
+    )
+}
+
+/// v2: FTS5 index over clip text and metadata, kept in sync by triggers.
+fn add_full_text_search(tx: &Transaction<'_>) -> SqliteResult<()> {
+    tx.execute_batch(
+        r#"
+        CREATE VIRTUAL TABLE IF NOT EXISTS clips_fts USING fts5(
+            content, app_name, window_title, auto_tags,
+            content = 'clips',
+            content_rowid = 'id'
+        );
+
+        CREATE TRIGGER IF NOT EXISTS clips_fts_after_insert AFTER INSERT ON clips BEGIN
+            INSERT INTO clips_fts(rowid, content, app_name, window_title, auto_tags)
+            VALUES (new.id, new.content, new.app_name, new.window_title, new.auto_tags);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS clips_fts_after_delete AFTER DELETE ON clips BEGIN
+            INSERT INTO clips_fts(clips_fts, rowid, content, app_name, window_title, auto_tags)
+            VALUES ('delete', old.id, old.content, old.app_name, old.window_title, old.auto_tags);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS clips_fts_after_update AFTER UPDATE ON clips BEGIN
+            INSERT INTO clips_fts(clips_fts, rowid, content, app_name, window_title, auto_tags)
+            VALUES ('delete', old.id, old.content, old.app_name, old.window_title, old.auto_tags);
+            INSERT INTO clips_fts(rowid, content, app_name, window_title, auto_tags)
+            VALUES (new.id, new.content, new.app_name, new.window_title, new.auto_tags);
+        END;
+
+        -- Index rows that existed before full-text search was introduced
+        INSERT INTO clips_fts(clips_fts) VALUES ('rebuild');
+        "#,
+    )
+}
+
+/// v3: Moves comma-joined `auto_tags` into `tags` / `clip_tags` tables.
+///
+/// The external-content FTS index cannot read tags from a join, so it is replaced
+/// by a standalone FTS table whose `tags` column is maintained by triggers on `clip_tags`.
+fn normalize_tags(tx: &Transaction<'_>) -> SqliteResult<()> {
+    tx.execute_batch(
+        r#"
+        CREATE TABLE tags (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE
+        );
+
+        CREATE TABLE clip_tags (
+            clip_id INTEGER NOT NULL REFERENCES clips(id) ON DELETE CASCADE,
+            tag_id INTEGER NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+            PRIMARY KEY (clip_id, tag_id)
+        ) WITHOUT ROWID;
+
+        CREATE INDEX idx_clip_tags_tag ON clip_tags(tag_id, clip_id);
+        "#,
+    )?;
+
+    // Split legacy tag strings in Rust; SQLite has no portable string splitting
+    let legacy: Vec<(i64, String)> = {
+        let mut stmt = tx.prepare(
+            "SELECT id, auto_tags FROM clips WHERE auto_tags IS NOT NULL AND auto_tags != ''",
+        )?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows
+    };
+
+    for (clip_id, auto_tags) in legacy {
+        for tag in auto_tags
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+        {
+            tx.execute("INSERT OR IGNORE INTO tags (name) VALUES (?1)", [tag])?;
+            tx.execute(
+                "INSERT OR IGNORE INTO clip_tags (clip_id, tag_id) SELECT ?1, id FROM tags WHERE name = ?2",
+                params![clip_id, tag],
+            )?;
+        }
+    }
+
+    tx.execute_batch(
+        r#"
+        DROP TRIGGER IF EXISTS clips_fts_after_insert;
+        DROP TRIGGER IF EXISTS clips_fts_after_delete;
+        DROP TRIGGER IF EXISTS clips_fts_after_update;
+        DROP TABLE IF EXISTS clips_fts;
+
+        ALTER TABLE clips DROP COLUMN auto_tags;
+
+        CREATE VIRTUAL TABLE clips_fts USING fts5(content, app_name, window_title, tags);
+
+        INSERT INTO clips_fts (rowid, content, app_name, window_title, tags)
+        SELECT clips.id, clips.content, clips.app_name, clips.window_title,
+               COALESCE((
+                   SELECT group_concat(tags.name, ' ')
+                   FROM clip_tags JOIN tags ON tags.id = clip_tags.tag_id
+                   WHERE clip_tags.clip_id = clips.id
+               ), '')
+        FROM clips;
+
+        CREATE TRIGGER clips_fts_after_insert AFTER INSERT ON clips BEGIN
+            INSERT INTO clips_fts (rowid, content, app_name, window_title, tags)
+            VALUES (new.id, new.content, new.app_name, new.window_title, '');
+        END;
+
+        CREATE TRIGGER clips_fts_after_delete AFTER DELETE ON clips BEGIN
+            DELETE FROM clips_fts WHERE rowid = old.id;
+        END;
+
+        CREATE TRIGGER clips_fts_after_update
+        AFTER UPDATE OF content, app_name, window_title ON clips BEGIN
+            UPDATE clips_fts
+            SET content = new.content, app_name = new.app_name, window_title = new.window_title
+            WHERE rowid = new.id;
+        END;
+
+        CREATE TRIGGER clip_tags_fts_after_insert AFTER INSERT ON clip_tags BEGIN
+            UPDATE clips_fts
+            SET tags = COALESCE((
+                SELECT group_concat(tags.name, ' ')
+                FROM clip_tags JOIN tags ON tags.id = clip_tags.tag_id
+                WHERE clip_tags.clip_id = new.clip_id
+            ), '')
+            WHERE rowid = new.clip_id;
+        END;
+
+        CREATE TRIGGER clip_tags_fts_after_delete AFTER DELETE ON clip_tags BEGIN
+            UPDATE clips_fts
+            SET tags = COALESCE((
+                SELECT group_concat(tags.name, ' ')
+                FROM clip_tags JOIN tags ON tags.id = clip_tags.tag_id
+                WHERE clip_tags.clip_id = old.clip_id
+            ), '')
+            WHERE rowid = old.clip_id;
+        END;
+        "#,
+    )
+}
+
+/// v4: Adds `content_hash`, `copy_count` and `last_copied_at` for database-level dedupe.
+///
+/// Existing rows are hashed in Rust and treated as copied once, at creation time.
+fn add_content_hash(tx: &Transaction<'_>) -> SqliteResult<()> {
+    tx.execute_batch(
+        r#"
+        ALTER TABLE clips ADD COLUMN content_hash TEXT;
+        ALTER TABLE clips ADD COLUMN copy_count INTEGER NOT NULL DEFAULT 1;
+        ALTER TABLE clips ADD COLUMN last_copied_at TEXT;
+
+        UPDATE clips SET last_copied_at = created_at;
+        "#,
+    )?;
+
+    let existing: Vec<(i64, String)> = {
+        let mut stmt = tx.prepare("SELECT id, content FROM clips")?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows
+    };
+
+    for (clip_id, content) in existing {
+        tx.execute(
+            "UPDATE clips SET content_hash = ?1 WHERE id = ?2",
+            params![content_hash(&content), clip_id],
+        )?;
+    }
+
+    tx.execute_batch(
+        r#"
+        CREATE INDEX idx_clips_content_hash ON clips(content_hash);
+        CREATE INDEX idx_clips_last_copied_at ON clips(last_copied_at DESC);
+        "#,
+    )
+}
+
+/// v5: Adds `use_count` and `last_used_at` so pasted clips can be ranked by frecency.
+fn track_clip_usage(tx: &Transaction<'_>) -> SqliteResult<()> {
+    tx.execute_batch(
+        r#"
+        ALTER TABLE clips ADD COLUMN use_count INTEGER NOT NULL DEFAULT 0;
+        ALTER TABLE clips ADD COLUMN last_used_at TEXT;
+        "#,
+    )
+}
+
+/// v6: Adds the `encryption` table holding key derivation parameters, and keeps
+/// encrypted values out of the full-text index.
+///
+/// Existing clips stay plaintext until the user enables encryption, which
+/// encrypts them in place (see [`crate::storage::ClipStore::enable_encryption`]).
+fn add_encryption(tx: &Transaction<'_>) -> SqliteResult<()> {
+    tx.execute_batch(
+        r#"
+        CREATE TABLE encryption (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            salt TEXT NOT NULL,
+            memory_kib INTEGER NOT NULL,
+            iterations INTEGER NOT NULL,
+            parallelism INTEGER NOT NULL,
+            verifier TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+
+        DROP TRIGGER clips_fts_after_insert;
+        DROP TRIGGER clips_fts_after_update;
+
+        CREATE TRIGGER clips_fts_after_insert AFTER INSERT ON clips BEGIN
+            INSERT INTO clips_fts (rowid, content, app_name, window_title, tags)
+            VALUES (
+                new.id,
+                CASE WHEN substr(new.content, 1, 7) = 'enc:v1:' THEN '' ELSE new.content END,
+                new.app_name,
+                CASE WHEN substr(new.window_title, 1, 7) = 'enc:v1:' THEN '' ELSE new.window_title END,
+                ''
+            );
+        END;
+
+        CREATE TRIGGER clips_fts_after_update
+        AFTER UPDATE OF content, app_name, window_title ON clips BEGIN
+            UPDATE clips_fts
+            SET content = CASE WHEN substr(new.content, 1, 7) = 'enc:v1:' THEN '' ELSE new.content END,
+                app_name = new.app_name,
+                window_title = CASE WHEN substr(new.window_title, 1, 7) = 'enc:v1:' THEN '' ELSE new.window_title END
+            WHERE rowid = new.id;
+        END;
+        "#,
+    )
+}
+
+/// v7: Adds `preview`, `byte_len` and `line_count` so listings need not load full clip bodies.
+///
+/// `preview` holds the first [`PREVIEW_CHARS`] characters of longer clips and is
+/// `NULL` otherwise. Already-encrypted rows cannot be measured here; they keep
+/// `NULL` sizes, which readers compute from the content instead.
+fn add_clip_previews(tx: &Transaction<'_>) -> SqliteResult<()> {
+    tx.execute_batch(&format!(
+        r#"
+        ALTER TABLE clips ADD COLUMN preview TEXT;
+        ALTER TABLE clips ADD COLUMN byte_len INTEGER;
+        ALTER TABLE clips ADD COLUMN line_count INTEGER;
+
+        UPDATE clips
+        SET byte_len = length(CAST(content AS BLOB)),
+            line_count = length(content) - length(replace(content, char(10), '')) + 1,
+            preview = CASE
+                WHEN length(content) > {PREVIEW_CHARS} THEN substr(content, 1, {PREVIEW_CHARS})
+            END
+        WHERE substr(content, 1, 7) != 'enc:v1:';
+        "#
+    ))
+}
+
+/// v8: Adds `deleted_at` and `deleted_batch` so deleted clips move to the trash
+/// instead of being removed right away.
+///
+/// Clips deleted by one operation share a batch number, which lets undo restore
+/// them together. All existing clips start out live.
+fn add_trash(tx: &Transaction<'_>) -> SqliteResult<()> {
+    tx.execute_batch(
+        r#"
+        ALTER TABLE clips ADD COLUMN deleted_at TEXT;
+        ALTER TABLE clips ADD COLUMN deleted_batch INTEGER;
+
+        CREATE INDEX idx_clips_deleted_at ON clips(deleted_at);
+        "#,
+    )
+}
+
+/// v9: Stores clip timestamps as integer milliseconds since the Unix epoch.
+///
+/// RFC3339 text had to be wrapped in `datetime()` to compare correctly, which kept
+/// range queries from using the indexes. Values are converted in Rust. Malformed
+/// values become `0`, so those clips sort as the oldest instead of as new, and a
+/// missing `last_copied_at` takes the creation time.
+fn use_epoch_timestamps(tx: &Transaction<'_>) -> SqliteResult<()> {
+    tx.execute_batch(
+        r#"
+        DROP INDEX IF EXISTS idx_created_at;
+        DROP INDEX IF EXISTS idx_clips_last_copied_at;
+        DROP INDEX IF EXISTS idx_clips_deleted_at;
+
+        ALTER TABLE clips ADD COLUMN created_at_ms INTEGER NOT NULL DEFAULT 0;
+        ALTER TABLE clips ADD COLUMN updated_at_ms INTEGER NOT NULL DEFAULT 0;
+        ALTER TABLE clips ADD COLUMN last_copied_at_ms INTEGER NOT NULL DEFAULT 0;
+        ALTER TABLE clips ADD COLUMN last_used_at_ms INTEGER;
+        ALTER TABLE clips ADD COLUMN deleted_at_ms INTEGER;
+        "#,
+    )?;
+
+    type Row = (
+        i64,
+        String,
+        String,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+    );
+    let existing: Vec<Row> = {
+        let mut stmt = tx.prepare(
+            "SELECT id, created_at, updated_at, last_copied_at, last_used_at, deleted_at FROM clips",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows
+    };
+
+    for (clip_id, created_at, updated_at, last_copied_at, last_used_at, deleted_at) in existing {
+        let created_at = epoch_ms(&created_at);
+        tx.execute(
+            r#"
+            UPDATE clips
+            SET created_at_ms = ?1, updated_at_ms = ?2, last_copied_at_ms = ?3,
+                last_used_at_ms = ?4, deleted_at_ms = ?5
+            WHERE id = ?6
+            "#,
+            params![
+                created_at,
+                epoch_ms(&updated_at),
+                last_copied_at.as_deref().map_or(created_at, epoch_ms),
+                last_used_at.as_deref().map(epoch_ms),
+                deleted_at.as_deref().map(epoch_ms),
+                clip_id
+            ],
+        )?;
+    }
+
+    tx.execute_batch(
+        r#"
+        ALTER TABLE clips DROP COLUMN created_at;
+        ALTER TABLE clips DROP COLUMN updated_at;
+        ALTER TABLE clips DROP COLUMN last_copied_at;
+        ALTER TABLE clips DROP COLUMN last_used_at;
+        ALTER TABLE clips DROP COLUMN deleted_at;
+
+        ALTER TABLE clips RENAME COLUMN created_at_ms TO created_at;
+        ALTER TABLE clips RENAME COLUMN updated_at_ms TO updated_at;
+        ALTER TABLE clips RENAME COLUMN last_copied_at_ms TO last_copied_at;
+        ALTER TABLE clips RENAME COLUMN last_used_at_ms TO last_used_at;
+        ALTER TABLE clips RENAME COLUMN deleted_at_ms TO deleted_at;
+
+        CREATE INDEX idx_created_at ON clips(created_at DESC);
+        CREATE INDEX idx_clips_last_copied_at ON clips(last_copied_at DESC);
+        CREATE INDEX idx_clips_deleted_at ON clips(deleted_at);
+        "#,
+    )
+}
+
+/// v10: Adds named collections and their many-to-many membership with clips.
+///
+/// Members are ordered by `position` within each collection. Deleting a clip or a
+/// collection removes its memberships.
+fn add_collections(tx: &Transaction<'_>) -> SqliteResult<()> {
+    tx.execute_batch(
+        r#"
+        CREATE TABLE collections (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+            created_at INTEGER NOT NULL
+        );
+
+        CREATE TABLE collection_clips (
+            collection_id INTEGER NOT NULL REFERENCES collections(id) ON DELETE CASCADE,
+            clip_id INTEGER NOT NULL REFERENCES clips(id) ON DELETE CASCADE,
+            position INTEGER NOT NULL,
+            PRIMARY KEY (collection_id, clip_id)
+        ) WITHOUT ROWID;
+
+        CREATE INDEX idx_collection_clips_clip ON collection_clips(clip_id);
+        CREATE INDEX idx_collection_clips_position ON collection_clips(collection_id, position);
+        "#,
+    )
+}
+
+/// v11: Adds user tags, kept apart from auto tags, and a free-text `note` on clips.
+///
+/// `clip_tags` gains an `is_user` flag, part of its key so a user can add a tag
+/// that auto tagging also produced; existing links are auto tags. The full-text
+/// index gains a `note` column, so it is rebuilt, leaving encrypted values blank
+/// as before.
+fn add_user_tags_and_notes(tx: &Transaction<'_>) -> SqliteResult<()> {
+    tx.execute_batch(
+        r#"
+        DROP TRIGGER clips_fts_after_insert;
+        DROP TRIGGER clips_fts_after_delete;
+        DROP TRIGGER clips_fts_after_update;
+        DROP TRIGGER clip_tags_fts_after_insert;
+        DROP TRIGGER clip_tags_fts_after_delete;
+        DROP TABLE clips_fts;
+
+        ALTER TABLE clips ADD COLUMN note TEXT;
+
+        CREATE TABLE clip_tags_v11 (
+            clip_id INTEGER NOT NULL REFERENCES clips(id) ON DELETE CASCADE,
+            tag_id INTEGER NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+            is_user INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (clip_id, tag_id, is_user)
+        ) WITHOUT ROWID;
+
+        INSERT INTO clip_tags_v11 (clip_id, tag_id) SELECT clip_id, tag_id FROM clip_tags;
+        DROP TABLE clip_tags;
+        ALTER TABLE clip_tags_v11 RENAME TO clip_tags;
+
+        CREATE INDEX idx_clip_tags_tag ON clip_tags(tag_id, clip_id);
+
+        CREATE VIRTUAL TABLE clips_fts USING fts5(content, app_name, window_title, tags, note);
+
+        INSERT INTO clips_fts (rowid, content, app_name, window_title, tags, note)
+        SELECT clips.id,
+               CASE WHEN substr(clips.content, 1, 7) = 'enc:v1:' THEN '' ELSE clips.content END,
+               clips.app_name,
+               CASE WHEN substr(clips.window_title, 1, 7) = 'enc:v1:' THEN '' ELSE clips.window_title END,
+               COALESCE((
+                   SELECT group_concat(tags.name, ' ')
+                   FROM clip_tags JOIN tags ON tags.id = clip_tags.tag_id
+                   WHERE clip_tags.clip_id = clips.id
+               ), ''),
+               ''
+        FROM clips;
+
+        CREATE TRIGGER clips_fts_after_insert AFTER INSERT ON clips BEGIN
+            INSERT INTO clips_fts (rowid, content, app_name, window_title, tags, note)
+            VALUES (
+                new.id,
+                CASE WHEN substr(new.content, 1, 7) = 'enc:v1:' THEN '' ELSE new.content END,
+                new.app_name,
+                CASE WHEN substr(new.window_title, 1, 7) = 'enc:v1:' THEN '' ELSE new.window_title END,
+                '',
+                CASE WHEN substr(new.note, 1, 7) = 'enc:v1:' THEN '' ELSE coalesce(new.note, '') END
+            );
+        END;
+
+        CREATE TRIGGER clips_fts_after_delete AFTER DELETE ON clips BEGIN
+            DELETE FROM clips_fts WHERE rowid = old.id;
+        END;
+
+        CREATE TRIGGER clips_fts_after_update
+        AFTER UPDATE OF content, app_name, window_title, note ON clips BEGIN
+            UPDATE clips_fts
+            SET content = CASE WHEN substr(new.content, 1, 7) = 'enc:v1:' THEN '' ELSE new.content END,
+                app_name = new.app_name,
+                window_title = CASE WHEN substr(new.window_title, 1, 7) = 'enc:v1:' THEN '' ELSE new.window_title END,
+                note = CASE WHEN substr(new.note, 1, 7) = 'enc:v1:' THEN '' ELSE coalesce(new.note, '') END
+            WHERE rowid = new.id;
+        END;
+
+        CREATE TRIGGER clip_tags_fts_after_insert AFTER INSERT ON clip_tags BEGIN
+            UPDATE clips_fts
+            SET tags = COALESCE((
+                SELECT group_concat(tags.name, ' ')
+                FROM clip_tags JOIN tags ON tags.id = clip_tags.tag_id
+                WHERE clip_tags.clip_id = new.clip_id
+            ), '')
+            WHERE rowid = new.clip_id;
+        END;
+
+        CREATE TRIGGER clip_tags_fts_after_delete AFTER DELETE ON clip_tags BEGIN
+            UPDATE clips_fts
+            SET tags = COALESCE((
+                SELECT group_concat(tags.name, ' ')
+                FROM clip_tags JOIN tags ON tags.id = clip_tags.tag_id
+                WHERE clip_tags.clip_id = old.clip_id
+            ), '')
+            WHERE rowid = old.clip_id;
+        END;
+        "#,
+    )
+}
+
+/// v12: Adds `clip_revisions`, holding the earlier contents of edited clips.
+///
+/// `replaced_at` is when a version stopped being the clip's content. Revisions
+/// are removed with their clip.
+fn add_clip_revisions(tx: &Transaction<'_>) -> SqliteResult<()> {
+    tx.execute_batch(
+        r#"
+        CREATE TABLE clip_revisions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            clip_id INTEGER NOT NULL REFERENCES clips(id) ON DELETE CASCADE,
+            content TEXT NOT NULL,
+            replaced_at INTEGER NOT NULL
+        );
+
+        CREATE INDEX idx_clip_revisions_clip ON clip_revisions(clip_id, replaced_at DESC);
+        "#,
+    )
+}
+
+/// v13: Adds `clips.is_snippet`, marking clips whose content is a template.
+fn add_snippets(tx: &Transaction<'_>) -> SqliteResult<()> {
+    tx.execute_batch("ALTER TABLE clips ADD COLUMN is_snippet INTEGER NOT NULL DEFAULT 0;")
+}
+
+/// v14: Adds `clips.expires_at` and `clips.delete_after_use` for self-destructing clips.
+///
+/// The partial index lets the expiry scheduler find the next expiring clip
+/// without scanning clips that never expire.
+fn add_clip_expiry(tx: &Transaction<'_>) -> SqliteResult<()> {
+    tx.execute_batch(
+        r#"
+        ALTER TABLE clips ADD COLUMN expires_at INTEGER;
+        ALTER TABLE clips ADD COLUMN delete_after_use INTEGER NOT NULL DEFAULT 0;
+
+        CREATE INDEX idx_clips_expires_at ON clips(expires_at) WHERE expires_at IS NOT NULL;
+        "#,
+    )
+}
+
+/// Converts an RFC3339 timestamp to milliseconds since the epoch, or `0` if malformed.
+fn epoch_ms(raw: &str) -> i64 {
+    DateTime::parse_from_rfc3339(raw).map_or(0, |t| t.timestamp_millis())
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a database in the shape written by releases that predate migrations.
+    fn legacy_fixture() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE clips (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                content TEXT NOT NULL,
+                app_name TEXT,
+                window_title TEXT,
+                auto_tags TEXT,
+                is_pinned BOOLEAN NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+            CREATE INDEX idx_created_at ON clips(created_at DESC);
+            CREATE INDEX idx_is_pinned ON clips(is_pinned);
+
+            INSERT INTO clips (content, app_name, window_title, auto_tags, is_pinned, created_at, updated_at)
+            VALUES
+                ('legacy invoice', 'Firefox', 'Billing', '#url,#browser', 1,
+                 '2024-01-01T00:00:00+00:00', '2024-01-01T00:00:00+00:00'),
+                ('cargo build --release', 'Alacritty', 'shell', '#terminal', 0,
+                 '2024-01-02T00:00:00+00:00', '2024-01-02T00:00:00+00:00');
+            "#,
+        )
+        .unwrap();
+        conn
+    }
+
+    fn recorded_versions(conn: &Connection) -> Vec<u32> {
+        let mut stmt = conn
+            .prepare("SELECT version FROM schema_migrations ORDER BY version")
+            .unwrap();
+        stmt.query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    }
+
+    fn failing_migration(_tx: &Transaction<'_>) -> SqliteResult<()> {
+        Err(rusqlite::Error::InvalidQuery)
+    }
+
+    #[test]
+    fn test_versions_are_ordered_and_contiguous() {
+        for (index, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version as usize, index + 1);
+        }
+    }
+
+    #[test]
+    fn test_fresh_database_reaches_latest_version() {
+        let mut conn = Connection::open_in_memory().unwrap();
+
+        let applied = run_migrations(&mut conn).unwrap();
+
+        let all: Vec<u32> = MIGRATIONS.iter().map(|m| m.version).collect();
+        assert_eq!(applied, all);
+        assert_eq!(current_version(&conn).unwrap(), latest_version());
+        assert_eq!(recorded_versions(&conn), all);
+    }
+
+    #[test]
+    fn test_migrations_are_idempotent() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        run_migrations(&mut conn).unwrap();
+
+        let applied = run_migrations(&mut conn).unwrap();
+
+        assert!(applied.is_empty());
+        assert_eq!(current_version(&conn).unwrap(), latest_version());
+    }
+
+    #[test]
+    fn test_upgrade_from_every_older_version() {
+        for start in 0..latest_version() {
+            let mut conn = legacy_fixture();
+            apply_migrations(&mut conn, MIGRATIONS, start).unwrap();
+            assert_eq!(current_version(&conn).unwrap(), start);
+
+            let applied = run_migrations(&mut conn).unwrap();
+
+            let expected: Vec<u32> = (start + 1..=latest_version()).collect();
+            assert_eq!(applied, expected, "upgrade from v{}", start);
+            assert_eq!(current_version(&conn).unwrap(), latest_version());
+            assert_eq!(
+                recorded_versions(&conn),
+                (1..=latest_version()).collect::<Vec<_>>()
+            );
+
+            let count: i64 = conn
+                .query_row("SELECT COUNT(*) FROM clips", [], |row| row.get(0))
+                .unwrap();
+            assert_eq!(count, 2, "rows lost upgrading from v{}", start);
+
+            let matched: String = conn
+                .query_row(
+                    "SELECT content FROM clips_fts WHERE clips_fts MATCH 'invoice'",
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(matched, "legacy invoice");
+
+            let tags: String = conn
+                .query_row(
+                    r#"
+                    SELECT group_concat(name, ',') FROM (
+                        SELECT tags.name FROM clip_tags
+                        JOIN tags ON tags.id = clip_tags.tag_id
+                        JOIN clips ON clips.id = clip_tags.clip_id
+                        WHERE clips.content = 'legacy invoice'
+                        ORDER BY tags.name
+                    )
+                    "#,
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(tags, "#browser,#url", "tags lost upgrading from v{}", start);
+
+            let user_tags: i64 = conn
+                .query_row(
+                    "SELECT COUNT(*) FROM clip_tags WHERE is_user = 1",
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(user_tags, 0, "existing tags are auto tags");
+
+            let by_tag: i64 = conn
+                .query_row(
+                    "SELECT COUNT(*) FROM clips_fts WHERE clips_fts MATCH 'tags:browser'",
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(by_tag, 1, "tags indexed after upgrading from v{}", start);
+
+            let (hash, copy_count, last_copied_at, use_count): (String, i64, i64, i64) = conn
+                .query_row(
+                    "SELECT content_hash, copy_count, last_copied_at, use_count FROM clips WHERE content = 'legacy invoice'",
+                    [],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+                )
+                .unwrap();
+            assert_eq!(hash, content_hash("legacy invoice"));
+            assert_eq!(copy_count, 1);
+            assert_eq!(
+                last_copied_at, 1_704_067_200_000,
+                "2024-01-01T00:00:00Z in ms"
+            );
+            assert_eq!(use_count, 0);
+
+            let encryption_rows: i64 = conn
+                .query_row("SELECT COUNT(*) FROM encryption", [], |row| row.get(0))
+                .unwrap();
+            assert_eq!(encryption_rows, 0, "plaintext databases stay unencrypted");
+
+            let (byte_len, line_count): (i64, i64) = conn
+                .query_row(
+                    "SELECT byte_len, line_count FROM clips WHERE content = 'legacy invoice'",
+                    [],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .unwrap();
+            assert_eq!((byte_len, line_count), (14, 1));
+
+            let trashed: i64 = conn
+                .query_row(
+                    "SELECT COUNT(*) FROM clips WHERE deleted_at IS NOT NULL",
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(trashed, 0, "existing clips stay live");
+        }
+    }
+
+    #[test]
+    fn test_malformed_timestamps_become_oldest() {
+        let mut conn = legacy_fixture();
+        apply_migrations(&mut conn, MIGRATIONS, 8).unwrap();
+        conn.execute_batch(
+            r#"
+            INSERT INTO clips (content, app_name, window_title, is_pinned, created_at, updated_at, last_copied_at)
+            VALUES ('garbled', 'App', 'Win', 0, 'not a date', 'not a date', NULL);
+            "#,
+        )
+        .unwrap();
+
+        run_migrations(&mut conn).unwrap();
+
+        let order: Vec<String> = conn
+            .prepare("SELECT content FROM clips ORDER BY last_copied_at DESC")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            order,
+            vec!["cargo build --release", "legacy invoice", "garbled"]
+        );
+
+        let created_at: i64 = conn
+            .query_row(
+                "SELECT created_at FROM clips WHERE content = 'garbled'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(created_at, 0);
+    }
+
+    #[test]
+    fn test_failed_migration_rolls_back() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        let migrations = [
+            Migration {
+                version: 1,
+                name: "create_clips",
+                up: create_clips,
+            },
+            Migration {
+                version: 2,
+                name: "broken",
+                up: failing_migration,
+            },
+        ];
+
+        assert!(apply_migrations(&mut conn, &migrations, 2).is_err());
+
+        assert_eq!(current_version(&conn).unwrap(), 1);
+        assert_eq!(recorded_versions(&conn), vec![1]);
+    }
+
+    #[test]
+    fn test_rejects_newer_schema() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", latest_version() + 1)
+            .unwrap();
+
+        assert!(run_migrations(&mut conn).is_err());
+    }
+}
@@ -0,0 +1,270 @@
+// src-tauri/src/storage/pagination.rs
+//! Keyset pagination over clipboard history.
+//!
+//! Pages are ordered by `(last_copied_at, id)`, most recently copied first, like
+//! [`ClipOrder::Recent`](crate::storage::ClipOrder::Recent) listings, so a bumped
+//! clip comes first. Each page carries a [`ClipCursor`] pointing at its last clip;
+//! passing it back returns the clips strictly after it. Unlike `LIMIT/OFFSET`,
+//! this stays fast deep into large histories, and clips captured or bumped while
+//! scrolling move ahead of the cursor instead of shifting later pages.
+
+// ===== Imports =====
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params_from_iter, types::Value, Result as SqliteResult};
+use serde::{Deserialize, Serialize};
+
+use crate::storage::{
+    clip_store::{row_to_clip, CLIP_COLUMNS},
+    Clip, ClipQuery, ClipStore,
+};
+
+// ===== Domain Types =====
+
+/// Position in the history: the `(last_copied_at, id)` key of the last clip on a page.
+///
+/// The key is `last_copied_at` rather than `created_at`: repeated copies bump an
+/// existing clip (see [`ClipStore::save_or_bump_clip`]), so listings follow copy
+/// order, and a `created_at` key would skip or repeat bumped clips between pages.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct ClipCursor {
+    /// Time the last clip returned was last copied.
+    pub last_copied_at: DateTime<Utc>,
+    /// ID of the last clip returned (breaks ties between equal timestamps).
+    pub id: i32,
+}
+
+/// A single page of clips.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ClipPage {
+    /// Clips on this page, newest first.
+    pub clips: Vec<Clip>,
+    /// Cursor for the next page, or `None` if this is the last page.
+    pub next_cursor: Option<ClipCursor>,
+    /// Total number of clips matching the query, across all pages.
+    pub total: i64,
+}
+
+// ===== Public API Implementation =====
+
+impl ClipStore {
+    /// Lists one page of clips matching `query`, most recently copied first.
+    ///
+    /// Pass `None` as `cursor` for the first page, then the previous page's
+    /// `next_cursor` for each following page.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn list_clips_page(
+        &self,
+        query: &ClipQuery,
+        cursor: Option<&ClipCursor>,
+        page_size: i32,
+    ) -> SqliteResult<ClipPage> {
+        let page_size = page_size.max(1);
+        let (where_sql, values) = query.where_sql(!self.is_encrypted());
+
+        let conn = self.reader();
+
+        let total: i64 = conn.query_row(
+            &format!("SELECT COUNT(*) FROM clips WHERE {where_sql}"),
+            params_from_iter(values.iter()),
+            |row| row.get(0),
+        )?;
+
+        let mut page_values = values;
+        let keyset_sql = match cursor {
+            Some(cursor) => {
+                page_values.push(Value::Integer(cursor.last_copied_at.timestamp_millis()));
+                page_values.push(Value::Integer(cursor.id.into()));
+                "(clips.last_copied_at, clips.id) < (?, ?)"
+            }
+            None => "1",
+        };
+        // Fetch one extra row to learn whether another page follows
+        page_values.push(Value::Integer(i64::from(page_size) + 1));
+
+        let mut stmt = conn.prepare(&format!(
+            r#"
+            SELECT {CLIP_COLUMNS}
+            FROM clips
+            WHERE {where_sql} AND {keyset_sql}
+            ORDER BY clips.last_copied_at DESC, clips.id DESC
+            LIMIT ?
+            "#
+        ))?;
+
+        let mut clips = stmt
+            .query_map(params_from_iter(page_values), row_to_clip)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let next_cursor = if clips.len() > page_size as usize {
+            clips.truncate(page_size as usize);
+            clips.last().map(|clip| ClipCursor {
+                last_copied_at: clip.last_copied_at,
+                id: clip.id.unwrap_or_default(),
+            })
+        } else {
+            None
+        };
+
+        Ok(ClipPage {
+            clips,
+            next_cursor,
+            total,
+        })
+    }
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::SaveOutcome;
+    use chrono::Duration;
+
+    fn setup_test_store() -> ClipStore {
+        ClipStore::new(":memory:").expect("Failed to create in-memory store")
+    }
+
+    fn save(store: &ClipStore, content: &str, app: &str, created_at: DateTime<Utc>) {
+        let mut clip = Clip::new(content.into(), app.into(), "Win".into(), vec![], false);
+        clip.created_at = created_at;
+        clip.last_copied_at = created_at;
+        store.save_clip(clip).unwrap();
+    }
+
+    fn collect_all(store: &ClipStore, query: &ClipQuery, page_size: i32) -> Vec<String> {
+        let mut contents = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = store
+                .list_clips_page(query, cursor.as_ref(), page_size)
+                .unwrap();
+            contents.extend(page.clips.into_iter().map(|c| c.content));
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => return contents,
+            }
+        }
+    }
+
+    #[test]
+    fn test_pages_cover_history_without_gaps() {
+        let store = setup_test_store();
+        let now = Utc::now();
+        for i in 0..7 {
+            save(
+                &store,
+                &format!("Clip {}", i),
+                "App",
+                now - Duration::minutes(i),
+            );
+        }
+
+        let first = store
+            .list_clips_page(&ClipQuery::default(), None, 3)
+            .unwrap();
+        assert_eq!(first.total, 7);
+        assert_eq!(first.clips.len(), 3);
+        assert!(first.next_cursor.is_some());
+
+        let all = collect_all(&store, &ClipQuery::default(), 3);
+        let expected: Vec<String> = (0..7).map(|i| format!("Clip {}", i)).collect();
+        assert_eq!(all, expected);
+    }
+
+    #[test]
+    fn test_equal_timestamps_are_ordered_by_id() {
+        let store = setup_test_store();
+        let now = Utc::now();
+        for i in 0..4 {
+            save(&store, &format!("Same {}", i), "App", now);
+        }
+
+        let all = collect_all(&store, &ClipQuery::default(), 1);
+        assert_eq!(all, vec!["Same 3", "Same 2", "Same 1", "Same 0"]);
+    }
+
+    #[test]
+    fn test_pagination_applies_filters_to_pages_and_total() {
+        let store = setup_test_store();
+        let now = Utc::now();
+        for i in 0..5 {
+            let app = if i % 2 == 0 { "Firefox" } else { "Terminal" };
+            save(
+                &store,
+                &format!("note {}", i),
+                app,
+                now - Duration::minutes(i),
+            );
+        }
+
+        let query = ClipQuery::parse("app:firefox note").unwrap();
+        let page = store.list_clips_page(&query, None, 10).unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(page.next_cursor, None);
+        assert_eq!(
+            collect_all(&store, &query, 2),
+            vec!["note 0", "note 2", "note 4"]
+        );
+    }
+
+    #[test]
+    fn test_new_clips_do_not_shift_later_pages() {
+        let store = setup_test_store();
+        let now = Utc::now();
+        for i in 0..4 {
+            save(
+                &store,
+                &format!("Clip {}", i),
+                "App",
+                now - Duration::minutes(i + 1),
+            );
+        }
+
+        let first = store
+            .list_clips_page(&ClipQuery::default(), None, 2)
+            .unwrap();
+        save(&store, "Captured while scrolling", "App", now);
+
+        let second = store
+            .list_clips_page(&ClipQuery::default(), first.next_cursor.as_ref(), 2)
+            .unwrap();
+        let contents: Vec<_> = second.clips.into_iter().map(|c| c.content).collect();
+        assert_eq!(contents, vec!["Clip 2", "Clip 3"]);
+        assert_eq!(second.total, 5);
+    }
+
+    #[test]
+    fn test_bumped_clip_comes_first() {
+        let store = setup_test_store();
+        let now = Utc::now();
+        for i in 0..4 {
+            save(
+                &store,
+                &format!("Clip {}", i),
+                "App",
+                now - Duration::minutes(i + 1),
+            );
+        }
+
+        let oldest = Clip::new("Clip 3".into(), "App".into(), "Win".into(), vec![], false);
+        assert!(matches!(
+            store.save_or_bump_clip(oldest).unwrap(),
+            SaveOutcome::Bumped(_)
+        ));
+
+        let first = store
+            .list_clips_page(&ClipQuery::default(), None, 2)
+            .unwrap();
+        let contents: Vec<_> = first.clips.into_iter().map(|c| c.content).collect();
+        assert_eq!(contents, vec!["Clip 3", "Clip 0"]);
+        assert_eq!(
+            collect_all(&store, &ClipQuery::default(), 2),
+            vec!["Clip 3", "Clip 0", "Clip 1", "Clip 2"]
+        );
+    }
+}
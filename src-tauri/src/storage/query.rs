@@ -0,0 +1,688 @@
+// src-tauri/src/storage/query.rs
+//! Structured query language for clip search.
+//!
+//! Users can combine free text with filters in a single query string:
+//!
+//! ```text
+//! app:firefox tag:#url after:yesterday "invoice"
+//! ```
+//!
+//! Supported filters:
+//! - `app:<name>`: App name contains `<name>` (case-insensitive). Repeat to match any of several apps.
+//! - `tag:<tag>`: Clip carries the auto tag (the leading `#` is optional). Repeat to require all tags.
+//! - `before:<date>` / `after:<date>`: Creation time bounds.
+//! - `pinned:<yes|no>`: Pin status.
+//!
+//! Dates accept `now`, `today`, `yesterday`, `last hour|day|week|month|year`,
+//! relative durations such as `30m`, `3h`, `2d`, `1w`, `6mo`, `1y`,
+//! and absolute `YYYY-MM-DD` or RFC3339 timestamps.
+//!
+//! Values containing spaces can be quoted (`app:"Visual Studio Code"`).
+//! Anything that is not a recognized filter is treated as free text and
+//! matched against the full-text index. Quoted free text is matched as an exact phrase.
+//!
+//! A parsed [`ClipQuery`] only produces parameterized SQL; user input is never
+//! interpolated into statements.
+
+// ===== Imports =====
+
+use std::{fmt::Display, iter::Peekable, str::CharIndices};
+
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc};
+use rusqlite::types::Value;
+
+// ===== Domain Types =====
+
+/// A parsed clip search query.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClipQuery {
+    /// Free-text terms matched against the full-text index.
+    pub terms: Vec<SearchTerm>,
+    /// App name fragments; a clip matches if its app name contains any of them.
+    pub apps: Vec<String>,
+    /// Normalized tags (always `#`-prefixed, lowercase); a clip must carry all of them.
+    pub tags: Vec<String>,
+    /// Only clips created strictly before this instant.
+    pub before: Option<DateTime<Utc>>,
+    /// Only clips created at or after this instant.
+    pub after: Option<DateTime<Utc>>,
+    /// Only clips with this pin status.
+    pub pinned: Option<bool>,
+}
+
+/// A free-text search term.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchTerm {
+    /// The text to search for.
+    pub text: String,
+    /// `true` if the term was quoted and must match as an exact phrase;
+    /// otherwise each word is matched as a prefix.
+    pub is_phrase: bool,
+}
+
+/// An error produced while parsing a query string.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryError {
+    /// A `"` was opened at the given byte offset but never closed.
+    UnterminatedQuote { position: usize },
+    /// A filter was given without a value (e.g., `app:`).
+    MissingValue { key: String },
+    /// A `before:`/`after:` value could not be understood as a date.
+    InvalidDate { key: String, value: String },
+    /// A `pinned:` value was not a recognized boolean.
+    InvalidBool { key: String, value: String },
+}
+
+/// A lexical token of the query language.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Text { text: String, quoted: bool },
+    Filter { key: FilterKey, value: String },
+}
+
+/// Recognized filter keys.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FilterKey {
+    App,
+    Tag,
+    Before,
+    After,
+    Pinned,
+}
+
+// ===== Public API Implementation =====
+
+impl ClipQuery {
+    /// Parses a query string, resolving relative dates against the current local time.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`QueryError`] if the query is malformed.
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        Self::parse_at(input, Local::now())
+    }
+
+    /// Parses a query string, resolving relative dates against `now`.
+    ///
+    /// Calendar-based values (`today`, `yesterday`, `YYYY-MM-DD`) are interpreted
+    /// in the time zone of `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`QueryError`] if the query is malformed.
+    pub fn parse_at<Tz: TimeZone>(input: &str, now: DateTime<Tz>) -> Result<Self, QueryError> {
+        let mut query = ClipQuery::default();
+
+        for token in tokenize(input)? {
+            match token {
+                Token::Text { text, quoted } => query.terms.push(SearchTerm {
+                    text,
+                    is_phrase: quoted,
+                }),
+                Token::Filter { key, value } => match key {
+                    FilterKey::App => query.apps.push(value),
+                    FilterKey::Tag => query.tags.push(normalize_tag(&value)),
+                    FilterKey::Before => {
+                        let date = parse_date(&value, &now).ok_or(QueryError::InvalidDate {
+                            key: key.name().to_string(),
+                            value,
+                        })?;
+                        query.before = Some(date);
+                    }
+                    FilterKey::After => {
+                        let date = parse_date(&value, &now).ok_or(QueryError::InvalidDate {
+                            key: key.name().to_string(),
+                            value,
+                        })?;
+                        query.after = Some(date);
+                    }
+                    FilterKey::Pinned => {
+                        let pinned = parse_bool(&value).ok_or(QueryError::InvalidBool {
+                            key: key.name().to_string(),
+                            value,
+                        })?;
+                        query.pinned = Some(pinned);
+                    }
+                },
+            }
+        }
+
+        Ok(query)
+    }
+
+    /// Returns `true` if the query has neither free text nor filters.
+    pub fn is_empty(&self) -> bool {
+        *self == ClipQuery::default()
+    }
+
+    /// Builds the FTS5 `MATCH` expression for the free-text terms.
+    ///
+    /// Each term is quoted so FTS5 operators and punctuation are treated literally.
+    /// Bare words become prefix queries; quoted phrases must match exactly.
+    /// All terms are implicitly AND-ed together.
+    ///
+    /// Returns `None` when the query has no free text.
+    pub fn match_expression(&self) -> Option<String> {
+        let parts: Vec<String> = self
+            .terms
+            .iter()
+            .flat_map(|term| {
+                if term.is_phrase {
+                    vec![fts_quote(&term.text)]
+                } else {
+                    term.text
+                        .split_whitespace()
+                        .map(|word| format!("{}*", fts_quote(word)))
+                        .collect()
+                }
+            })
+            .filter(|part| part != "\"\"")
+            .collect();
+
+        if parts.is_empty() {
+            None
+        } else {
+            Some(parts.join(" "))
+        }
+    }
+
+    /// Builds SQL conditions for the structured filters (everything except free text).
+    ///
+    /// Conditions reference the `clips` table by name and use anonymous `?` placeholders
+    /// whose values are returned alongside, in order. Trashed clips are always excluded.
+    pub(crate) fn filter_sql(&self) -> (String, Vec<Value>) {
+        let mut conditions = vec!["clips.deleted_at IS NULL".to_string()];
+        let mut values: Vec<Value> = Vec::new();
+
+        if !self.apps.is_empty() {
+            let alternatives = vec!["clips.app_name LIKE ? ESCAPE '\\'"; self.apps.len()];
+            conditions.push(format!("({})", alternatives.join(" OR ")));
+            values.extend(
+                self.apps
+                    .iter()
+                    .map(|app| Value::Text(format!("%{}%", escape_like(app)))),
+            );
+        }
+
+        for tag in &self.tags {
+            conditions.push(
+                "EXISTS (SELECT 1 FROM clip_tags JOIN tags ON tags.id = clip_tags.tag_id \
+                 WHERE clip_tags.clip_id = clips.id AND tags.name = ?)"
+                    .to_string(),
+            );
+            values.push(Value::Text(tag.clone()));
+        }
+
+        if let Some(before) = self.before {
+            conditions.push("clips.created_at < ?".to_string());
+            values.push(Value::Integer(before.timestamp_millis()));
+        }
+
+        if let Some(after) = self.after {
+            conditions.push("clips.created_at >= ?".to_string());
+            values.push(Value::Integer(after.timestamp_millis()));
+        }
+
+        if let Some(pinned) = self.pinned {
+            conditions.push("clips.is_pinned = ?".to_string());
+            values.push(Value::Integer(pinned as i64));
+        }
+
+        (conditions.join(" AND "), values)
+    }
+
+    /// Builds SQL conditions matching the free-text terms by scanning decrypted text.
+    ///
+    /// Used instead of the full-text index when clip content is encrypted, since
+    /// encrypted values are not indexed. Each word (or quoted phrase) must occur in
+    /// the content, app name, window title or note, case-insensitively.
+    ///
+    /// Returns `None` when the query has no free text.
+    pub(crate) fn scan_sql(&self) -> Option<(String, Vec<Value>)> {
+        let needles: Vec<&str> = self
+            .terms
+            .iter()
+            .flat_map(|term| {
+                if term.is_phrase {
+                    vec![term.text.as_str()]
+                } else {
+                    term.text.split_whitespace().collect()
+                }
+            })
+            .filter(|needle| !needle.is_empty())
+            .collect();
+
+        if needles.is_empty() {
+            return None;
+        }
+
+        let condition = "(clip_text(clips.content) || ' ' || coalesce(clips.app_name, '') \
+                         || ' ' || coalesce(clip_text(clips.window_title), '') \
+                         || ' ' || coalesce(clip_text(clips.note), '')) LIKE ? ESCAPE '\\'";
+        let conditions = vec![condition; needles.len()];
+        let values = needles
+            .iter()
+            .map(|needle| Value::Text(format!("%{}%", escape_like(needle))))
+            .collect();
+
+        Some((conditions.join(" AND "), values))
+    }
+
+    /// Builds the full SQL condition for this query: structured filters plus,
+    /// when the query has free text, a full-text match against `clips_fts`
+    /// (or, if `use_fts` is `false`, a scan as built by [`ClipQuery::scan_sql`]).
+    ///
+    /// Placeholders and values follow the same conventions as [`ClipQuery::filter_sql`].
+    pub(crate) fn where_sql(&self, use_fts: bool) -> (String, Vec<Value>) {
+        let (filter_sql, mut values) = self.filter_sql();
+
+        if !use_fts {
+            return match self.scan_sql() {
+                Some((scan_sql, scan_values)) => {
+                    values.extend(scan_values);
+                    (format!("{filter_sql} AND {scan_sql}"), values)
+                }
+                None => (filter_sql, values),
+            };
+        }
+
+        match self.match_expression() {
+            Some(match_expr) => {
+                values.push(Value::Text(match_expr));
+                (
+                    format!(
+                        "{filter_sql} AND clips.id IN \
+                         (SELECT rowid FROM clips_fts WHERE clips_fts MATCH ?)"
+                    ),
+                    values,
+                )
+            }
+            None => (filter_sql, values),
+        }
+    }
+}
+
+impl FilterKey {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "app" => Some(FilterKey::App),
+            "tag" => Some(FilterKey::Tag),
+            "before" => Some(FilterKey::Before),
+            "after" => Some(FilterKey::After),
+            "pinned" => Some(FilterKey::Pinned),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            FilterKey::App => "app",
+            FilterKey::Tag => "tag",
+            FilterKey::Before => "before",
+            FilterKey::After => "after",
+            FilterKey::Pinned => "pinned",
+        }
+    }
+
+    fn is_date(self) -> bool {
+        matches!(self, FilterKey::Before | FilterKey::After)
+    }
+}
+
+impl Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryError::UnterminatedQuote { position } => {
+                write!(f, "Unterminated quote starting at position {}", position)
+            }
+            QueryError::MissingValue { key } => write!(f, "Missing value for '{}:'", key),
+            QueryError::InvalidDate { key, value } => {
+                write!(f, "Invalid date '{}' for '{}:'", value, key)
+            }
+            QueryError::InvalidBool { key, value } => {
+                write!(
+                    f,
+                    "Invalid value '{}' for '{}:' (expected yes or no)",
+                    value, key
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+// ===== Tokenizer =====
+
+/// Splits a query string into free-text and filter tokens.
+fn tokenize(input: &str) -> Result<Vec<Token>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        if c == '"' {
+            chars.next();
+            let text = read_quoted(&mut chars, start)?;
+            tokens.push(Token::Text { text, quoted: true });
+            continue;
+        }
+
+        let word = read_word(&mut chars);
+        let filter = word
+            .split_once(':')
+            .and_then(|(name, rest)| FilterKey::from_name(name).map(|key| (key, rest)));
+
+        let Some((key, rest)) = filter else {
+            tokens.push(Token::Text {
+                text: word,
+                quoted: false,
+            });
+            continue;
+        };
+
+        let mut value = rest.to_string();
+        if value.is_empty() {
+            if let Some(&(quote_start, '"')) = chars.peek() {
+                chars.next();
+                value = read_quoted(&mut chars, quote_start)?;
+            }
+        }
+
+        // Allow unquoted two-word dates such as `after:last week`
+        if key.is_date() && value.eq_ignore_ascii_case("last") {
+            skip_whitespace(&mut chars);
+            let unit = read_word(&mut chars);
+            if !unit.is_empty() {
+                value = format!("{} {}", value, unit);
+            }
+        }
+
+        if value.trim().is_empty() {
+            return Err(QueryError::MissingValue {
+                key: key.name().to_string(),
+            });
+        }
+
+        tokens.push(Token::Filter { key, value });
+    }
+
+    Ok(tokens)
+}
+
+/// Reads characters up to the closing quote. The opening quote must already be consumed.
+fn read_quoted(chars: &mut Peekable<CharIndices<'_>>, start: usize) -> Result<String, QueryError> {
+    let mut text = String::new();
+    for (_, c) in chars.by_ref() {
+        if c == '"' {
+            return Ok(text);
+        }
+        text.push(c);
+    }
+    Err(QueryError::UnterminatedQuote { position: start })
+}
+
+/// Reads a bare word up to the next whitespace or the start of a quoted value.
+fn read_word(chars: &mut Peekable<CharIndices<'_>>) -> String {
+    let mut word = String::new();
+    while let Some(&(_, c)) = chars.peek() {
+        if c.is_whitespace() || (c == '"' && word.ends_with(':')) {
+            break;
+        }
+        word.push(c);
+        chars.next();
+    }
+    word
+}
+
+fn skip_whitespace(chars: &mut Peekable<CharIndices<'_>>) {
+    while chars.next_if(|&(_, c)| c.is_whitespace()).is_some() {}
+}
+
+// ===== Value Parsing =====
+
+/// Resolves an absolute or relative date expression against `now`.
+fn parse_date<Tz: TimeZone>(value: &str, now: &DateTime<Tz>) -> Option<DateTime<Utc>> {
+    let value = value.trim().to_ascii_lowercase();
+    let now_utc = now.with_timezone(&Utc);
+    let today = now.date_naive();
+
+    let start_of_day = |date: NaiveDate| -> Option<DateTime<Utc>> {
+        now.timezone()
+            .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+            .earliest()
+            .map(|dt| dt.with_timezone(&Utc))
+    };
+
+    match value.as_str() {
+        "now" => return Some(now_utc),
+        "today" => return start_of_day(today),
+        "yesterday" => return start_of_day(today.pred_opt()?),
+        _ => {}
+    }
+
+    if let Some(unit) = value.strip_prefix("last ") {
+        let span = match unit.trim() {
+            "hour" => Duration::hours(1),
+            "day" => Duration::days(1),
+            "week" => Duration::weeks(1),
+            "month" => Duration::days(30),
+            "year" => Duration::days(365),
+            _ => return None,
+        };
+        return now_utc.checked_sub_signed(span);
+    }
+
+    if let Some(span) = parse_relative_duration(&value) {
+        return now_utc.checked_sub_signed(span);
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(&value, "%Y-%m-%d") {
+        return start_of_day(date);
+    }
+
+    DateTime::parse_from_rfc3339(&value)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Parses compact durations such as `30m`, `3h`, `2d`, `1w`, `6mo` or `1y`.
+fn parse_relative_duration(value: &str) -> Option<Duration> {
+    let split = value.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = value.split_at(split);
+    let amount: i64 = amount.parse().ok()?;
+
+    match unit {
+        "m" | "min" | "mins" => Duration::try_minutes(amount),
+        "h" | "hr" | "hrs" => Duration::try_hours(amount),
+        "d" | "day" | "days" => Duration::try_days(amount),
+        "w" | "wk" | "wks" => Duration::try_weeks(amount),
+        "mo" => Duration::try_days(amount.checked_mul(30)?),
+        "y" | "yr" | "yrs" => Duration::try_days(amount.checked_mul(365)?),
+        _ => None,
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "yes" | "true" | "1" | "y" => Some(true),
+        "no" | "false" | "0" | "n" => Some(false),
+        _ => None,
+    }
+}
+
+/// Normalizes a tag to the stored `#tag` form.
+pub(super) fn normalize_tag(tag: &str) -> String {
+    let tag = tag.trim().to_lowercase();
+    if tag.starts_with('#') {
+        tag
+    } else {
+        format!("#{}", tag)
+    }
+}
+
+/// Wraps text in double quotes for FTS5, escaping embedded quotes.
+fn fts_quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+/// Escapes `LIKE` wildcards so user input is matched literally (with `ESCAPE '\'`).
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixed_now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 6, 15, 12, 0, 0).unwrap()
+    }
+
+    fn parse(input: &str) -> Result<ClipQuery, QueryError> {
+        ClipQuery::parse_at(input, fixed_now())
+    }
+
+    #[test]
+    fn test_parse_full_example() {
+        let query = parse(r#"app:firefox tag:#url after:yesterday "invoice""#).unwrap();
+        assert_eq!(query.apps, vec!["firefox".to_string()]);
+        assert_eq!(query.tags, vec!["#url".to_string()]);
+        assert_eq!(
+            query.after,
+            Some(Utc.with_ymd_and_hms(2024, 6, 14, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            query.terms,
+            vec![SearchTerm {
+                text: "invoice".into(),
+                is_phrase: true
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_relative_dates() {
+        let now = fixed_now();
+        assert_eq!(
+            parse("after:3h").unwrap().after,
+            Some(now - Duration::hours(3))
+        );
+        assert_eq!(
+            parse("after:2d").unwrap().after,
+            Some(now - Duration::days(2))
+        );
+        assert_eq!(
+            parse("before:30m").unwrap().before,
+            Some(now - Duration::minutes(30))
+        );
+        assert_eq!(
+            parse("after:last week").unwrap().after,
+            Some(now - Duration::weeks(1))
+        );
+        assert_eq!(
+            parse(r#"after:"last month""#).unwrap().after,
+            Some(now - Duration::days(30))
+        );
+        assert_eq!(
+            parse("before:today").unwrap().before,
+            Some(Utc.with_ymd_and_hms(2024, 6, 15, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            parse("after:2024-01-31").unwrap().after,
+            Some(Utc.with_ymd_and_hms(2024, 1, 31, 0, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_pinned_and_tag_normalization() {
+        let query = parse("pinned:yes tag:JSON").unwrap();
+        assert_eq!(query.pinned, Some(true));
+        assert_eq!(query.tags, vec!["#json".to_string()]);
+        assert_eq!(parse("pinned:no").unwrap().pinned, Some(false));
+    }
+
+    #[test]
+    fn test_quoted_filter_values() {
+        let query = parse(r#"app:"Visual Studio Code" main"#).unwrap();
+        assert_eq!(query.apps, vec!["Visual Studio Code".to_string()]);
+        assert_eq!(query.match_expression().unwrap(), "\"main\"*");
+    }
+
+    #[test]
+    fn test_unknown_keys_are_free_text() {
+        let query = parse("https://example.com").unwrap();
+        assert_eq!(query.terms.len(), 1);
+        assert_eq!(query.terms[0].text, "https://example.com");
+        assert!(query.apps.is_empty());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse(r#"foo "bar"#),
+            Err(QueryError::UnterminatedQuote { position: 4 })
+        );
+        assert_eq!(
+            parse("app:"),
+            Err(QueryError::MissingValue { key: "app".into() })
+        );
+        assert_eq!(
+            parse("after:someday"),
+            Err(QueryError::InvalidDate {
+                key: "after".into(),
+                value: "someday".into()
+            })
+        );
+        assert_eq!(
+            parse("pinned:maybe"),
+            Err(QueryError::InvalidBool {
+                key: "pinned".into(),
+                value: "maybe".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_out_of_range_relative_dates_are_errors() {
+        for value in ["100000000d", "99999999999999999m"] {
+            assert_eq!(
+                parse(&format!("after:{value}")),
+                Err(QueryError::InvalidDate {
+                    key: "after".into(),
+                    value: value.into()
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_match_expression() {
+        let query = parse(r#"foo "exact phrase" OR"#).unwrap();
+        assert_eq!(
+            query.match_expression().unwrap(),
+            r#""foo"* "exact phrase" "OR"*"#
+        );
+        assert!(parse("app:x").unwrap().match_expression().is_none());
+        assert!(parse("   ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_filter_sql_is_parameterized() {
+        let query = parse("app:fire%fox app:chrome tag:url pinned:no").unwrap();
+        let (sql, values) = query.filter_sql();
+        assert!(!sql.contains("fire"));
+        assert_eq!(sql.matches('?').count(), values.len());
+        assert_eq!(values[0], Value::Text("%fire\\%fox%".into()));
+        assert_eq!(values[2], Value::Text("#url".into()));
+        assert_eq!(values[3], Value::Integer(0));
+    }
+}
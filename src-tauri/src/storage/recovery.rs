@@ -0,0 +1,567 @@
+// src-tauri/src/storage/recovery.rs
+//! Startup integrity checks and recovery from a corrupt database.
+//!
+//! [`ClipStore::open_or_recover`] runs `PRAGMA quick_check` on a read-only
+//! connection before opening the database for writing, so a damaged file is never
+//! switched to WAL or migrated. If the file turns out to be corrupt, or opening it
+//! fails with a corruption error, it is moved aside ("quarantined") together
+//! with its WAL and shared-memory files, a fresh database is created in its place,
+//! and every clip that can still be read is copied over row by row.
+//!
+//! Rows are copied verbatim rather than through [`crate::storage::Clip`], so
+//! encrypted clips are salvaged without the passphrase, along with the key
+//! derivation parameters needed to unlock them later. A database written with an
+//! older schema is first copied into memory and upgraded by the regular
+//! [`migrations`](crate::storage::migrations), so its rows are converted to the
+//! current formats.
+//!
+//! The quarantined file is only ever opened read-only, so it is kept exactly as
+//! it was found.
+
+// ===== Imports =====
+
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::Utc;
+use rusqlite::{
+    ffi, params_from_iter, types::Value, Connection, ErrorCode, OpenFlags, Result as SqliteResult,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, warn};
+
+use crate::storage::{
+    encryption::initial_key_state,
+    migrations::{current_version, latest_version, run_migrations},
+    ClipStore,
+};
+
+// ===== Domain Types =====
+
+/// Outcome of recovering from a corrupt database at startup.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RecoveryReport {
+    /// Where the corrupt database file was moved.
+    pub quarantined_path: String,
+    /// Number of clips copied into the fresh database.
+    pub salvaged: usize,
+    /// Number of clips found in the corrupt database that could not be read.
+    ///
+    /// Clips in damaged parts of the file may not be found at all, so this is a
+    /// lower bound.
+    pub lost: usize,
+}
+
+/// Health of the clip database, as determined at startup.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum StorageStatus {
+    /// The database opened and passed its integrity check.
+    Healthy,
+    /// The database was corrupt and has been replaced by a fresh one holding the
+    /// clips that could be salvaged.
+    Recovered(RecoveryReport),
+    /// The database could not be opened or recovered. The history is kept in
+    /// memory only and is lost when the app quits.
+    Degraded {
+        /// Why the database could not be used.
+        reason: String,
+    },
+}
+
+impl StorageStatus {
+    /// Returns whether the history is kept in memory only.
+    pub fn is_degraded(&self) -> bool {
+        matches!(self, StorageStatus::Degraded { .. })
+    }
+}
+
+// ===== Public API Implementation =====
+
+impl ClipStore {
+    /// Opens the database at `db_path`, recovering from corruption if needed.
+    ///
+    /// Returns the store, and a [`RecoveryReport`] if the database was corrupt
+    /// and had to be replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be opened for a reason other than
+    /// corruption (e.g., permissions, or a schema written by a newer version),
+    /// in which case the file is left untouched, or if recovery itself fails.
+    pub fn open_or_recover(db_path: &Path) -> SqliteResult<(Self, Option<RecoveryReport>)> {
+        let error = match open_checked(db_path) {
+            Ok(store) => return Ok((store, None)),
+            Err(e) if is_corruption(&e) => e,
+            Err(e) => return Err(e),
+        };
+
+        error!("Database at {} is corrupt: {}", db_path.display(), error);
+
+        let quarantined = quarantine(db_path).map_err(|e| {
+            rusqlite::Error::ToSqlConversionFailure(
+                format!("Failed to quarantine corrupt database: {}", e).into(),
+            )
+        })?;
+        let store = ClipStore::new(db_path)?;
+        let (salvaged, lost) = store.salvage_from(&quarantined)?;
+
+        info!(
+            "Recovered {} clips from corrupt database ({} lost); original kept at {}",
+            salvaged,
+            lost,
+            quarantined.display()
+        );
+
+        Ok((
+            store,
+            Some(RecoveryReport {
+                quarantined_path: quarantined.display().to_string(),
+                salvaged,
+                lost,
+            }),
+        ))
+    }
+
+    /// Checks the database for corruption.
+    ///
+    /// `full` runs `PRAGMA integrity_check`, which also verifies indexes against
+    /// their tables; otherwise the faster `PRAGMA quick_check` is used.
+    ///
+    /// Returns the problems found; empty if the database is intact.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the check cannot run, which usually means the
+    /// database is damaged too.
+    pub fn check_integrity(&self, full: bool) -> SqliteResult<Vec<String>> {
+        let pragma = if full {
+            "integrity_check"
+        } else {
+            "quick_check"
+        };
+        integrity_problems(&self.reader(), pragma)
+    }
+
+    /// Copies every readable clip, with its tags, collections and revisions, and the
+    /// encryption parameters, from the database at `source` into this store.
+    ///
+    /// Returns the number of clips salvaged and the number found but unreadable.
+    fn salvage_from(&self, source: &Path) -> SqliteResult<(usize, usize)> {
+        let source = Connection::open_with_flags(source, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+
+        let (source, staging_lost) = match current_version(&source) {
+            Ok(version) if version < latest_version() => match upgraded_copy(&source, version) {
+                Ok(upgraded) => upgraded,
+                Err(e) => {
+                    warn!("Failed to upgrade salvaged schema v{}: {}", version, e);
+                    (source, 0)
+                }
+            },
+            _ => (source, 0),
+        };
+
+        self.write_key_state(move |conn| {
+            let tx = conn.transaction()?;
+            let (salvaged, lost) = copy_history(&source, &tx);
+            tx.commit()?;
+
+            // Salvaged encrypted clips need the passphrase again
+            Ok(((salvaged, lost + staging_lost), initial_key_state(conn)?))
+        })
+    }
+}
+
+// ===== Helper Functions =====
+
+/// Opens the store, failing with a corruption error if `quick_check` finds problems.
+///
+/// An existing file is checked read-only first, before [`ClipStore::new`] changes
+/// its journal mode or migrates it.
+fn open_checked(db_path: &Path) -> SqliteResult<ClipStore> {
+    if db_path.exists() {
+        let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let problems = integrity_problems(&conn, "quick_check")?;
+        if !problems.is_empty() {
+            return Err(rusqlite::Error::SqliteFailure(
+                ffi::Error::new(ffi::SQLITE_CORRUPT),
+                Some(problems.join("; ")),
+            ));
+        }
+    }
+
+    ClipStore::new(db_path)
+}
+
+/// Runs an integrity check `pragma` and returns the problems it reports.
+fn integrity_problems(conn: &Connection, pragma: &str) -> SqliteResult<Vec<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA {pragma}"))?;
+    let messages = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<SqliteResult<Vec<_>>>()?;
+
+    Ok(messages.into_iter().filter(|m| m != "ok").collect())
+}
+
+/// Returns whether `error` means the database file is damaged.
+fn is_corruption(error: &rusqlite::Error) -> bool {
+    matches!(
+        error.sqlite_error_code(),
+        Some(ErrorCode::DatabaseCorrupt | ErrorCode::NotADatabase)
+    )
+}
+
+/// Moves the database at `db_path`, and its `-wal` and `-shm` files, aside.
+///
+/// Returns the quarantined database path, `<name>.corrupt-<timestamp>`.
+fn quarantine(db_path: &Path) -> std::io::Result<PathBuf> {
+    let file_name = db_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let target = db_path.with_file_name(format!(
+        "{}.corrupt-{}",
+        file_name,
+        Utc::now().format("%Y%m%d-%H%M%S")
+    ));
+
+    fs::rename(db_path, &target)?;
+    for suffix in ["-wal", "-shm"] {
+        let sidecar = PathBuf::from(format!("{}{}", db_path.display(), suffix));
+        if sidecar.exists() {
+            let moved = PathBuf::from(format!("{}{}", target.display(), suffix));
+            if let Err(e) = fs::rename(&sidecar, &moved) {
+                warn!("Failed to quarantine {}: {}", sidecar.display(), e);
+            }
+        }
+    }
+
+    warn!("Corrupt database moved to {}", target.display());
+    Ok(target)
+}
+
+/// Copies the readable contents of `source`, written with schema `version`, into
+/// an in-memory database with the same schema, then upgrades it to the latest one.
+///
+/// Returns the upgraded copy and the number of clips that could not be read.
+fn upgraded_copy(source: &Connection, version: u32) -> SqliteResult<(Connection, usize)> {
+    let mut staging = Connection::open_in_memory()?;
+    for sql in schema_statements(source) {
+        // Full-text search shadow tables already exist once their virtual table does
+        if let Err(e) = staging.execute_batch(&sql) {
+            debug!("Skipping salvaged schema statement: {}", e);
+        }
+    }
+    staging.pragma_update(None, "user_version", version)?;
+
+    let (_, lost) = copy_history(source, &staging);
+    run_migrations(&mut staging)?;
+    Ok((staging, lost))
+}
+
+/// Lists the readable `CREATE` statements of `source`'s schema, in creation order.
+fn schema_statements(source: &Connection) -> Vec<String> {
+    let Ok(mut stmt) = source.prepare(
+        "SELECT sql FROM sqlite_master \
+         WHERE sql IS NOT NULL AND name NOT LIKE 'sqlite_%' ORDER BY rowid",
+    ) else {
+        return Vec::new();
+    };
+    let Ok(mut rows) = stmt.query([]) else {
+        return Vec::new();
+    };
+
+    let mut statements = Vec::new();
+    while let Ok(Some(row)) = rows.next() {
+        if let Ok(sql) = row.get(0) {
+            statements.push(sql);
+        }
+    }
+    statements
+}
+
+/// Copies every readable clip, with its tags, collections and revisions, and the
+/// encryption parameters, from `source` into `dest`.
+///
+/// Returns the number of clips copied and the number found but unreadable.
+fn copy_history(source: &Connection, dest: &Connection) -> (usize, usize) {
+    copy_rows(source, dest, "encryption", "1 = 1", &[]);
+    copy_rows(source, dest, "tags", "1 = 1", &[]);
+    copy_rows(source, dest, "collections", "1 = 1", &[]);
+
+    let mut copied = 0;
+    let mut lost = 0;
+    for id in readable_clip_ids(source) {
+        let id = Value::Integer(id);
+        if copy_rows(source, dest, "clips", "id = ?1", std::slice::from_ref(&id)) == 1 {
+            copy_rows(
+                source,
+                dest,
+                "clip_tags",
+                "clip_id = ?1",
+                std::slice::from_ref(&id),
+            );
+            copy_rows(
+                source,
+                dest,
+                "collection_clips",
+                "clip_id = ?1",
+                std::slice::from_ref(&id),
+            );
+            copy_rows(source, dest, "clip_revisions", "clip_id = ?1", &[id]);
+            copied += 1;
+        } else {
+            lost += 1;
+        }
+    }
+    (copied, lost)
+}
+
+/// Collects the IDs of clips that can be enumerated in `source`.
+///
+/// Scanning stops at the first damaged page, so the table is scanned in both
+/// directions to reach clips on either side of the damage.
+fn readable_clip_ids(source: &Connection) -> BTreeSet<i64> {
+    let mut ids = BTreeSet::new();
+    for order in ["ASC", "DESC"] {
+        let Ok(mut stmt) = source.prepare(&format!("SELECT id FROM clips ORDER BY id {order}"))
+        else {
+            continue;
+        };
+        let Ok(mut rows) = stmt.query([]) else {
+            continue;
+        };
+        while let Ok(Some(row)) = rows.next() {
+            if let Ok(id) = row.get(0) {
+                ids.insert(id);
+            }
+        }
+    }
+    ids
+}
+
+/// Copies the rows of `table` matching `condition` from `source` into `dest`,
+/// using the columns both databases have.
+///
+/// Rows that cannot be read or inserted are skipped. Returns the number copied.
+fn copy_rows(
+    source: &Connection,
+    dest: &Connection,
+    table: &str,
+    condition: &str,
+    values: &[Value],
+) -> usize {
+    let columns = match common_columns(source, dest, table) {
+        Ok(columns) if !columns.is_empty() => columns,
+        _ => return 0,
+    };
+    let column_list = columns.join(", ");
+    let placeholders = vec!["?"; columns.len()].join(", ");
+
+    let rows: Vec<Vec<Value>> = {
+        let Ok(mut stmt) = source.prepare(&format!(
+            "SELECT {column_list} FROM {table} WHERE {condition}"
+        )) else {
+            return 0;
+        };
+        let Ok(mut cursor) = stmt.query(params_from_iter(values)) else {
+            return 0;
+        };
+
+        let mut rows = Vec::new();
+        while let Ok(Some(row)) = cursor.next() {
+            let row: SqliteResult<Vec<Value>> = (0..columns.len()).map(|i| row.get(i)).collect();
+            if let Ok(row) = row {
+                rows.push(row);
+            }
+        }
+        rows
+    };
+
+    let insert = format!("INSERT OR IGNORE INTO {table} ({column_list}) VALUES ({placeholders})");
+    rows.into_iter()
+        .filter(|row| matches!(dest.execute(&insert, params_from_iter(row)), Ok(1)))
+        .count()
+}
+
+/// Returns the columns `table` has in both databases.
+fn common_columns(
+    source: &Connection,
+    dest: &Connection,
+    table: &str,
+) -> SqliteResult<Vec<String>> {
+    let dest_columns = table_columns(dest, table)?;
+    Ok(table_columns(source, table)?
+        .into_iter()
+        .filter(|column| dest_columns.contains(column))
+        .collect())
+}
+
+/// Lists the columns of `table`.
+fn table_columns(conn: &Connection, table: &str) -> SqliteResult<Vec<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({table})"))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<SqliteResult<Vec<_>>>()?;
+    Ok(columns)
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{
+        migrations::{apply_migrations, MIGRATIONS},
+        Clip, ClipQuery,
+    };
+    use chrono::DateTime;
+    use tempfile::tempdir;
+
+    fn clip(content: &str) -> Clip {
+        Clip::new(
+            content.into(),
+            "App".into(),
+            "Win".into(),
+            vec!["#tag".into()],
+            false,
+        )
+    }
+
+    #[test]
+    fn test_healthy_database_opens_without_recovery() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("clips.db");
+        ClipStore::new(&path)
+            .unwrap()
+            .save_clip(clip("fine"))
+            .unwrap();
+
+        let (store, report) = ClipStore::open_or_recover(&path).unwrap();
+
+        assert!(report.is_none());
+        assert!(store.check_integrity(true).unwrap().is_empty());
+        assert_eq!(store.list_recent_clips(10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_unreadable_file_is_quarantined() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("clips.db");
+        fs::write(&path, vec![0xAB; 8192]).unwrap();
+
+        let (store, report) = ClipStore::open_or_recover(&path).unwrap();
+
+        let report = report.expect("corrupt database must be recovered");
+        assert_eq!((report.salvaged, report.lost), (0, 0));
+        assert_eq!(
+            fs::read(&report.quarantined_path).unwrap(),
+            vec![0xAB; 8192]
+        );
+        store.save_clip(clip("fresh start")).unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_database_on_older_schema_is_checked_before_migrating() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("clips.db");
+        let index_page: i64 = {
+            let mut old = Connection::open(&path).unwrap();
+            apply_migrations(&mut old, MIGRATIONS, 1).unwrap();
+            old.execute_batch(
+                r#"
+                INSERT INTO clips (content, app_name, window_title, auto_tags, is_pinned, created_at, updated_at)
+                VALUES ('kept despite damage', 'App', 'Win', '', 0,
+                        '2024-01-01T00:00:00+00:00', '2024-01-01T00:00:00+00:00');
+                "#,
+            )
+            .unwrap();
+            old.query_row(
+                "SELECT rootpage FROM sqlite_master WHERE name = 'idx_is_pinned'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+
+        // Overwrite an index page; the clips table itself stays readable
+        let mut bytes = fs::read(&path).unwrap();
+        let page_size = u16::from_be_bytes([bytes[16], bytes[17]]) as usize;
+        let start = (index_page as usize - 1) * page_size;
+        bytes[start..start + page_size].fill(0xAB);
+        fs::write(&path, &bytes).unwrap();
+
+        let (store, report) = ClipStore::open_or_recover(&path).unwrap();
+
+        let report = report.expect("corrupt database must be recovered");
+        assert!(
+            fs::read(&report.quarantined_path).unwrap() == bytes,
+            "quarantined database was modified"
+        );
+        assert_eq!((report.salvaged, report.lost), (1, 0));
+        assert_eq!(
+            store.list_recent_clips(10).unwrap()[0].content,
+            "kept despite damage"
+        );
+    }
+
+    #[test]
+    fn test_salvages_clips_tags_and_search_index() {
+        let dir = tempdir().unwrap();
+        let source_path = dir.path().join("old.db");
+        {
+            let old = ClipStore::new(&source_path).unwrap();
+            old.save_clip(clip("first salvaged clip")).unwrap();
+            old.save_clip(clip("second salvaged clip")).unwrap();
+        }
+
+        let store = ClipStore::new(dir.path().join("new.db")).unwrap();
+        let (salvaged, lost) = store.salvage_from(&source_path).unwrap();
+
+        assert_eq!((salvaged, lost), (2, 0));
+        let clips = store.list_recent_clips(10).unwrap();
+        assert_eq!(clips.len(), 2);
+        assert_eq!(clips[0].tags, vec!["#tag"]);
+        let query = ClipQuery::parse("tag:#tag salvaged").unwrap();
+        assert_eq!(store.search_clips(&query, 10).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_salvages_v1_database_into_current_formats() {
+        let dir = tempdir().unwrap();
+        let source_path = dir.path().join("v1.db");
+        {
+            let mut old = Connection::open(&source_path).unwrap();
+            apply_migrations(&mut old, MIGRATIONS, 1).unwrap();
+            old.execute_batch(
+                r#"
+                INSERT INTO clips (content, app_name, window_title, auto_tags, is_pinned, created_at, updated_at)
+                VALUES ('legacy invoice', 'Firefox', 'Billing', '#url,#browser', 1,
+                        '2024-01-01T00:00:00+00:00', '2024-01-01T00:00:00+00:00');
+                "#,
+            )
+            .unwrap();
+        }
+        let original = fs::read(&source_path).unwrap();
+
+        let store = ClipStore::new(dir.path().join("new.db")).unwrap();
+        let (salvaged, lost) = store.salvage_from(&source_path).unwrap();
+
+        assert_eq!((salvaged, lost), (1, 0));
+        assert_eq!(fs::read(&source_path).unwrap(), original);
+        let clips = store.list_recent_clips(10).unwrap();
+        assert_eq!(clips.len(), 1);
+        assert_eq!(clips[0].content, "legacy invoice");
+        assert!(clips[0].is_pinned);
+        assert_eq!(clips[0].tags, vec!["#browser", "#url"]);
+        assert_eq!(
+            clips[0].created_at,
+            DateTime::parse_from_rfc3339("2024-01-01T00:00:00+00:00").unwrap()
+        );
+        let query = ClipQuery::parse("invoice").unwrap();
+        assert_eq!(store.search_clips(&query, 10).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,645 @@
+// src-tauri/src/storage/retention.rs
+//! Retention policies for automatic history cleanup.
+//!
+//! A [`RetentionPolicy`] holds separate [`RetentionLimits`] for pinned and
+//! unpinned clips. By default pinned clips carry no limits at all, so they are
+//! never removed by automatic cleanup.
+//!
+//! Unpinned clips can additionally be governed by an ordered list of
+//! [`RetentionRule`]s. Each clip is governed by the **first** rule it matches;
+//! clips matching no rule fall back to the policy's unpinned limits.
+//!
+//! Snippets and clips that belong to a collection are never removed by
+//! automatic cleanup, and do not count towards any limit.
+
+// ===== Imports =====
+
+use chrono::{Duration, Utc};
+use rusqlite::{params_from_iter, types::Value, Connection, Result as SqliteResult};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::storage::{query::normalize_tag, tags::prune_unused_tags, ClipStore};
+
+// ===== Domain Types =====
+
+/// Age and count limits applied to one group of clips.
+///
+/// A `None` limit is not enforced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionLimits {
+    /// Clips not copied for this many days are removed.
+    pub max_age_days: Option<u32>,
+    /// Only the newest this-many clips are kept.
+    pub max_count: Option<u32>,
+}
+
+/// Retention limits for unpinned clips matching specific criteria.
+///
+/// All criteria that are set must match. A rule without criteria matches
+/// every unpinned clip not claimed by an earlier rule.
+///
+/// Example (JSON, as stored in settings):
+/// `{ "name": "Terminal", "appName": "Alacritty", "maxAgeDays": 1 }`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionRule {
+    /// Label used in cleanup reports and logs.
+    #[serde(default)]
+    pub name: String,
+    /// Matches clips copied from this application (exact name, case-insensitive).
+    #[serde(default)]
+    pub app_name: Option<String>,
+    /// Matches clips carrying this tag (e.g., `"#url"`; the leading `#` is optional).
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// Matches clips whose content is at least this many bytes long.
+    #[serde(default)]
+    pub min_content_bytes: Option<u64>,
+    /// Limits applied to the clips this rule governs.
+    #[serde(flatten)]
+    pub limits: RetentionLimits,
+}
+
+/// Describes which clips automatic cleanup may remove.
+///
+/// Pinned and unpinned clips are limited independently: the size pass keeps the
+/// newest `max_count` clips *of each group*, so pinned clips never push unpinned
+/// ones out of history (or vice versa). Likewise, each rule counts only the clips
+/// it governs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RetentionPolicy {
+    /// Limits for unpinned clips not governed by any rule.
+    pub unpinned: RetentionLimits,
+    /// Limits for pinned clips. Unlimited by default (pinned clips are exempt).
+    pub pinned: RetentionLimits,
+    /// Ordered rules for unpinned clips. The first matching rule wins.
+    pub rules: Vec<RetentionRule>,
+}
+
+/// Number of clips removed by a single retention rule.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleCleanup {
+    /// The rule's name.
+    pub name: String,
+    /// Clips removed under this rule.
+    pub removed: usize,
+}
+
+/// Number of clips removed by a cleanup run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CleanupReport {
+    /// Unpinned clips removed by the default (non-rule) limits.
+    pub unpinned_removed: usize,
+    /// Pinned clips removed by the age and size passes.
+    pub pinned_removed: usize,
+    /// Clips removed by each rule, in rule order.
+    pub rules: Vec<RuleCleanup>,
+}
+
+/// A group of clips selected by an SQL condition over `clips`.
+struct Scope {
+    condition: String,
+    values: Vec<Value>,
+}
+
+// ===== Public API Implementation =====
+
+impl RetentionPolicy {
+    /// Creates a policy that limits unpinned clips and exempts pinned clips.
+    pub fn new(unpinned: RetentionLimits) -> Self {
+        Self {
+            unpinned,
+            ..Self::default()
+        }
+    }
+
+    /// Sets limits for pinned clips.
+    pub fn with_pinned(mut self, pinned: RetentionLimits) -> Self {
+        self.pinned = pinned;
+        self
+    }
+
+    /// Sets the ordered retention rules for unpinned clips.
+    pub fn with_rules(mut self, rules: Vec<RetentionRule>) -> Self {
+        self.rules = rules;
+        self
+    }
+}
+
+impl CleanupReport {
+    /// Total number of clips removed.
+    pub fn total(&self) -> usize {
+        self.unpinned_removed
+            + self.pinned_removed
+            + self.rules.iter().map(|r| r.removed).sum::<usize>()
+    }
+}
+
+impl ClipStore {
+    /// Performs automatic cleanup of the clip history according to `policy`.
+    ///
+    /// For each rule, for unpinned clips matching no rule, and for pinned clips,
+    /// applies two passes in sequence:
+    /// 1. Removes clips last copied more than `max_age_days` ago.
+    /// 2. Trims the group to its `max_count` most recently copied clips.
+    ///
+    /// All passes run in a single transaction. Tags left without any clip are removed afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any cleanup step fails; no clips are removed in that case.
+    pub fn perform_cleanup(&self, policy: &RetentionPolicy) -> SqliteResult<CleanupReport> {
+        let policy = policy.clone();
+        self.write(move |conn| {
+            let tx = conn.transaction()?;
+
+            let matchers: Vec<Scope> = policy.rules.iter().map(rule_matcher).collect();
+
+            let mut rules = Vec::with_capacity(policy.rules.len());
+            for (index, rule) in policy.rules.iter().enumerate() {
+                let scope = governed_scope(&matchers[..index], Some(&matchers[index]));
+                rules.push(RuleCleanup {
+                    name: rule.name.clone(),
+                    removed: apply_limits(&tx, &scope, &rule.limits)?,
+                });
+            }
+
+            let report = CleanupReport {
+                unpinned_removed: apply_limits(
+                    &tx,
+                    &governed_scope(&matchers, None),
+                    &policy.unpinned,
+                )?,
+                pinned_removed: apply_limits(&tx, &pinned_scope(), &policy.pinned)?,
+                rules,
+            };
+            prune_unused_tags(&tx)?;
+
+            tx.commit()?;
+
+            debug!(
+                "Cleanup removed {} clips ({} unpinned, {} pinned, {:?} by rule)",
+                report.total(),
+                report.unpinned_removed,
+                report.pinned_removed,
+                report.rules
+            );
+            Ok(report)
+        })
+    }
+}
+
+// ===== Helper Functions =====
+
+/// Builds the match condition for a single rule.
+fn rule_matcher(rule: &RetentionRule) -> Scope {
+    let mut conditions = Vec::new();
+    let mut values = Vec::new();
+
+    if let Some(app_name) = &rule.app_name {
+        conditions.push("coalesce(clips.app_name, '') = ? COLLATE NOCASE".to_string());
+        values.push(Value::Text(app_name.trim().to_string()));
+    }
+    if let Some(tag) = &rule.tag {
+        conditions.push(
+            "EXISTS (SELECT 1 FROM clip_tags JOIN tags ON tags.id = clip_tags.tag_id \
+             WHERE clip_tags.clip_id = clips.id AND tags.name = ?)"
+                .to_string(),
+        );
+        values.push(Value::Text(normalize_tag(tag)));
+    }
+    if let Some(min_bytes) = rule.min_content_bytes {
+        conditions.push(
+            "coalesce(clips.byte_len, length(CAST(clip_text(clips.content) AS BLOB))) >= ?"
+                .to_string(),
+        );
+        values.push(Value::Integer(i64::try_from(min_bytes).unwrap_or(i64::MAX)));
+    }
+
+    if conditions.is_empty() {
+        conditions.push("1".to_string());
+    }
+
+    Scope {
+        condition: format!("({})", conditions.join(" AND ")),
+        values,
+    }
+}
+
+/// Condition excluding clips that belong to a collection from cleanup.
+const NOT_IN_COLLECTION: &str =
+    "NOT EXISTS (SELECT 1 FROM collection_clips WHERE collection_clips.clip_id = clips.id)";
+
+/// Selects live unpinned clips matching `matcher` (or all, if `None`) but none of `earlier`.
+fn governed_scope(earlier: &[Scope], matcher: Option<&Scope>) -> Scope {
+    let mut conditions = vec![
+        "clips.deleted_at IS NULL".to_string(),
+        "clips.is_pinned = 0".to_string(),
+        "clips.is_snippet = 0".to_string(),
+        NOT_IN_COLLECTION.to_string(),
+    ];
+    let mut values = Vec::new();
+
+    if let Some(matcher) = matcher {
+        conditions.push(matcher.condition.clone());
+        values.extend(matcher.values.iter().cloned());
+    }
+    for scope in earlier {
+        conditions.push(format!("NOT {}", scope.condition));
+        values.extend(scope.values.iter().cloned());
+    }
+
+    Scope {
+        condition: conditions.join(" AND "),
+        values,
+    }
+}
+
+/// Selects all live pinned clips.
+fn pinned_scope() -> Scope {
+    Scope {
+        condition: format!(
+            "clips.deleted_at IS NULL AND clips.is_pinned = 1 AND clips.is_snippet = 0 AND {NOT_IN_COLLECTION}"
+        ),
+        values: Vec::new(),
+    }
+}
+
+/// Applies `limits` to the clips selected by `scope`.
+fn apply_limits(conn: &Connection, scope: &Scope, limits: &RetentionLimits) -> SqliteResult<usize> {
+    let mut deleted = 0;
+
+    if let Some(days) = limits.max_age_days {
+        deleted += remove_older_than_days(conn, scope, days)?;
+    }
+    if let Some(max_count) = limits.max_count {
+        deleted += enforce_max_count(conn, scope, max_count)?;
+    }
+
+    Ok(deleted)
+}
+
+/// Deletes clips in `scope` last copied more than the specified number of days ago.
+fn remove_older_than_days(conn: &Connection, scope: &Scope, days: u32) -> SqliteResult<usize> {
+    // A limit reaching past the earliest representable date removes nothing
+    let Some(cutoff) = Utc::now().checked_sub_signed(Duration::days(days.into())) else {
+        return Ok(0);
+    };
+
+    let mut values = scope.values.clone();
+    values.push(Value::Integer(cutoff.timestamp_millis()));
+
+    let deleted = conn.execute(
+        &format!(
+            "DELETE FROM clips WHERE {} AND last_copied_at < ?",
+            scope.condition
+        ),
+        params_from_iter(values),
+    )?;
+
+    debug!(
+        "Deleted {} clips ({}) older than {} days",
+        deleted, scope.condition, days
+    );
+    Ok(deleted)
+}
+
+/// Keeps the `max_count` most recently copied clips in `scope` and deletes the rest.
+fn enforce_max_count(conn: &Connection, scope: &Scope, max_count: u32) -> SqliteResult<usize> {
+    let mut values = scope.values.clone();
+    values.extend(scope.values.iter().cloned());
+    values.push(Value::Integer(max_count.into()));
+
+    let deleted = conn.execute(
+        &format!(
+            r#"
+            DELETE FROM clips
+            WHERE {condition}
+              AND id NOT IN (
+                  SELECT id FROM clips
+                  WHERE {condition}
+                  ORDER BY last_copied_at DESC, id DESC
+                  LIMIT ?
+              )
+            "#,
+            condition = scope.condition
+        ),
+        params_from_iter(values),
+    )?;
+
+    debug!(
+        "Trimmed {} clips ({}) to enforce max size of {}",
+        deleted, scope.condition, max_count
+    );
+    Ok(deleted)
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Clip;
+
+    fn setup_test_store() -> ClipStore {
+        ClipStore::new(":memory:").expect("Failed to create in-memory store")
+    }
+
+    fn save(store: &ClipStore, content: &str, pinned: bool, age_days: i64) {
+        save_from(store, content, "App", &[], pinned, age_days);
+    }
+
+    fn save_from(
+        store: &ClipStore,
+        content: &str,
+        app: &str,
+        tags: &[&str],
+        pinned: bool,
+        age_days: i64,
+    ) {
+        let tags = tags.iter().map(|t| t.to_string()).collect();
+        let mut clip = Clip::new(content.into(), app.into(), "Win".into(), tags, pinned);
+        clip.created_at = Utc::now() - Duration::days(age_days);
+        clip.updated_at = clip.created_at;
+        clip.last_copied_at = clip.created_at;
+        store.save_clip(clip).unwrap();
+    }
+
+    fn contents(store: &ClipStore) -> Vec<String> {
+        store
+            .list_recent_clips(100)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.content)
+            .collect()
+    }
+
+    fn limits(max_age_days: Option<u32>, max_count: Option<u32>) -> RetentionLimits {
+        RetentionLimits {
+            max_age_days,
+            max_count,
+        }
+    }
+
+    #[test]
+    fn test_cleanup_by_age() {
+        let store = setup_test_store();
+        save(&store, "Old", false, 10);
+        save(&store, "New", false, 0);
+
+        let report = store
+            .perform_cleanup(&RetentionPolicy::new(limits(Some(5), Some(100))))
+            .unwrap();
+
+        assert_eq!(contents(&store), vec!["New"]);
+        assert_eq!(report.unpinned_removed, 1);
+    }
+
+    #[test]
+    fn test_cleanup_by_max_size() {
+        let store = setup_test_store();
+        for i in 0..5 {
+            save(&store, &format!("Clip {}", i), false, 5 - i);
+        }
+
+        store
+            .perform_cleanup(&RetentionPolicy::new(limits(Some(30), Some(3))))
+            .unwrap();
+
+        assert_eq!(contents(&store), vec!["Clip 4", "Clip 3", "Clip 2"]);
+    }
+
+    #[test]
+    fn test_huge_age_limit_keeps_everything() {
+        let store = setup_test_store();
+        save(&store, "Old", false, 10);
+        save(&store, "Old pinned", true, 10);
+
+        let mut policy = RetentionPolicy::new(limits(Some(u32::MAX), None));
+        policy.pinned = limits(Some(100_000_000), None);
+        let report = store.perform_cleanup(&policy).unwrap();
+
+        assert_eq!(contents(&store), vec!["Old pinned", "Old"]);
+        assert_eq!(report.unpinned_removed + report.pinned_removed, 0);
+    }
+
+    #[test]
+    fn test_pinned_clips_survive_age_pass() {
+        let store = setup_test_store();
+        save(&store, "Old pinned", true, 100);
+        save(&store, "Old unpinned", false, 100);
+
+        let report = store
+            .perform_cleanup(&RetentionPolicy::new(limits(Some(30), None)))
+            .unwrap();
+
+        assert_eq!(contents(&store), vec!["Old pinned"]);
+        assert_eq!(report.pinned_removed, 0);
+        assert_eq!(report.unpinned_removed, 1);
+    }
+
+    #[test]
+    fn test_pinned_clips_survive_size_pass() {
+        let store = setup_test_store();
+        save(&store, "Pinned oldest", true, 10);
+        for i in 0..3 {
+            save(&store, &format!("Clip {}", i), false, 3 - i);
+        }
+
+        store
+            .perform_cleanup(&RetentionPolicy::new(limits(None, Some(2))))
+            .unwrap();
+
+        assert_eq!(contents(&store), vec!["Clip 2", "Clip 1", "Pinned oldest"]);
+    }
+
+    #[test]
+    fn test_snippets_survive_age_and_size_passes() {
+        let store = setup_test_store();
+        for (content, pinned) in [("Dear {{name}},", false), ("Regards, {{me}}", true)] {
+            let mut clip = Clip::new(content.into(), "App".into(), "Win".into(), vec![], pinned);
+            clip.is_snippet = true;
+            clip.created_at = Utc::now() - Duration::days(100);
+            clip.updated_at = clip.created_at;
+            clip.last_copied_at = clip.created_at;
+            store.save_clip(clip).unwrap();
+        }
+        for i in 0..3 {
+            save(&store, &format!("Clip {}", i), false, 3 - i);
+        }
+
+        let mut policy = RetentionPolicy::new(limits(Some(30), Some(1)));
+        policy.pinned = limits(Some(30), Some(0));
+        let report = store.perform_cleanup(&policy).unwrap();
+
+        assert_eq!(
+            contents(&store),
+            vec!["Clip 2", "Regards, {{me}}", "Dear {{name}},"]
+        );
+        assert_eq!(report.pinned_removed, 0);
+        assert_eq!(report.unpinned_removed, 2);
+    }
+
+    #[test]
+    fn test_separate_pinned_limits() {
+        let store = setup_test_store();
+        save(&store, "Pinned ancient", true, 400);
+        save(&store, "Pinned old", true, 20);
+        save(&store, "Pinned new", true, 1);
+        save(&store, "Unpinned new", false, 1);
+
+        let policy = RetentionPolicy::new(limits(Some(30), Some(10)))
+            .with_pinned(limits(Some(365), Some(1)));
+        let report = store.perform_cleanup(&policy).unwrap();
+
+        assert_eq!(contents(&store), vec!["Unpinned new", "Pinned new"]);
+        assert_eq!(report.pinned_removed, 2);
+        assert_eq!(report.unpinned_removed, 0);
+        assert_eq!(report.total(), 2);
+    }
+
+    #[test]
+    fn test_default_policy_removes_nothing() {
+        let store = setup_test_store();
+        save(&store, "Old", false, 1000);
+
+        let report = store.perform_cleanup(&RetentionPolicy::default()).unwrap();
+
+        assert_eq!(report, CleanupReport::default());
+        assert_eq!(contents(&store), vec!["Old"]);
+    }
+
+    fn rule(name: &str, max_age_days: Option<u32>, max_count: Option<u32>) -> RetentionRule {
+        RetentionRule {
+            name: name.into(),
+            limits: limits(max_age_days, max_count),
+            ..RetentionRule::default()
+        }
+    }
+
+    #[test]
+    fn test_rules_by_app_and_tag_override_default_limits() {
+        let store = setup_test_store();
+        save_from(&store, "ls -la", "Alacritty", &[], false, 2);
+        save_from(
+            &store,
+            "https://old.example",
+            "Firefox",
+            &["#url"],
+            false,
+            60,
+        );
+        save_from(
+            &store,
+            "https://ancient.example",
+            "Firefox",
+            &["#url"],
+            false,
+            120,
+        );
+        save_from(&store, "old note", "Editor", &[], false, 60);
+        save_from(&store, "recent note", "Editor", &[], false, 2);
+
+        let policy = RetentionPolicy::new(limits(Some(30), None)).with_rules(vec![
+            RetentionRule {
+                app_name: Some("alacritty".into()),
+                ..rule("Terminal", Some(1), None)
+            },
+            RetentionRule {
+                tag: Some("url".into()),
+                ..rule("Links", Some(90), None)
+            },
+        ]);
+        let report = store.perform_cleanup(&policy).unwrap();
+
+        assert_eq!(contents(&store), vec!["recent note", "https://old.example"]);
+        assert_eq!(
+            report.rules,
+            vec![
+                RuleCleanup {
+                    name: "Terminal".into(),
+                    removed: 1
+                },
+                RuleCleanup {
+                    name: "Links".into(),
+                    removed: 1
+                },
+            ]
+        );
+        assert_eq!(report.unpinned_removed, 1);
+        assert_eq!(report.total(), 3);
+    }
+
+    #[test]
+    fn test_first_matching_rule_wins() {
+        let store = setup_test_store();
+        save_from(&store, "terminal url", "Alacritty", &["#url"], false, 5);
+
+        let policy = RetentionPolicy::default().with_rules(vec![
+            RetentionRule {
+                tag: Some("#url".into()),
+                ..rule("Links", Some(90), None)
+            },
+            RetentionRule {
+                app_name: Some("Alacritty".into()),
+                ..rule("Terminal", Some(1), None)
+            },
+        ]);
+        let report = store.perform_cleanup(&policy).unwrap();
+
+        assert_eq!(contents(&store), vec!["terminal url"]);
+        assert_eq!(report.total(), 0);
+    }
+
+    #[test]
+    fn test_rule_by_content_size_with_own_count() {
+        let store = setup_test_store();
+        for i in 0..3 {
+            save(
+                &store,
+                &format!("large {}{}", i, "x".repeat(100)),
+                false,
+                3 - i,
+            );
+        }
+        save(&store, "small", false, 10);
+
+        let policy = RetentionPolicy::default().with_rules(vec![RetentionRule {
+            min_content_bytes: Some(100),
+            ..rule("Large", None, Some(1))
+        }]);
+        let report = store.perform_cleanup(&policy).unwrap();
+
+        let remaining = contents(&store);
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining[0].starts_with("large 2"));
+        assert_eq!(remaining[1], "small");
+        assert_eq!(report.rules[0].removed, 2);
+    }
+
+    #[test]
+    fn test_rules_never_touch_pinned_clips() {
+        let store = setup_test_store();
+        save_from(&store, "pinned command", "Alacritty", &[], true, 10);
+
+        let policy = RetentionPolicy::default().with_rules(vec![RetentionRule {
+            app_name: Some("Alacritty".into()),
+            ..rule("Terminal", Some(1), Some(0))
+        }]);
+        store.perform_cleanup(&policy).unwrap();
+
+        assert_eq!(contents(&store), vec!["pinned command"]);
+    }
+
+    #[test]
+    fn test_rule_deserializes_from_settings_json() {
+        let rule: RetentionRule =
+            serde_json::from_str(r##"{"name":"Links","tag":"#url","maxAgeDays":90}"##).unwrap();
+
+        assert_eq!(rule.tag.as_deref(), Some("#url"));
+        assert_eq!(rule.limits, limits(Some(90), None));
+        assert_eq!(rule.app_name, None);
+    }
+}
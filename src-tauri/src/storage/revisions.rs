@@ -0,0 +1,312 @@
+// src-tauri/src/storage/revisions.rs
+//! Editing clip content, with revision history.
+//!
+//! [`ClipStore::update_clip_content`] replaces a clip's content in place and keeps
+//! the previous content as a [`ClipRevision`]. Reverting to a revision is an edit
+//! too, so the content it replaces is kept as well and nothing is ever lost.
+//!
+//! Editing refreshes the clip's auto tags from the new content; user tags, the
+//! note, pin state and timestamps other than `updated_at` are kept. Revisions are
+//! encrypted like clip content and removed with their clip.
+
+// ===== Imports =====
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::storage::{
+    clip::line_count,
+    clip_store::{encode_content, fetch_clip, from_epoch_ms},
+    encryption::ClipCipher,
+    tags::replace_auto_tags,
+    Clip, ClipStore,
+};
+
+// ===== Domain Types =====
+
+/// An earlier version of a clip's content.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ClipRevision {
+    /// Database ID of the revision.
+    pub id: i64,
+    /// ID of the clip this revision belongs to.
+    pub clip_id: i32,
+    /// The full content of this version.
+    pub content: String,
+    /// When this version was replaced by an edit.
+    pub replaced_at: DateTime<Utc>,
+}
+
+// ===== Public API Implementation =====
+
+impl ClipStore {
+    /// Replaces the content of a live clip, keeping the old content as a revision.
+    ///
+    /// `auto_tags` computes the clip's new auto tags from its content and app name.
+    /// Saving unchanged content changes nothing. Returns the updated clip.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`rusqlite::Error::QueryReturnedNoRows`] if no live clip has the
+    /// given ID, or another error if `content` is blank, the store is locked or
+    /// the update fails.
+    pub fn update_clip_content(
+        &self,
+        id: i32,
+        content: String,
+        auto_tags: fn(&str, &str) -> Vec<String>,
+    ) -> SqliteResult<Clip> {
+        if content.trim().is_empty() {
+            return Err(rusqlite::Error::ToSqlConversionFailure(
+                "Clip content cannot be empty".into(),
+            ));
+        }
+
+        self.write_with_cipher(move |conn, cipher| {
+            let tx = conn.transaction()?;
+            replace_content(&tx, id, &content, cipher, auto_tags)?;
+            let clip = fetch_clip(&tx, id)?;
+            tx.commit()?;
+            Ok(clip)
+        })
+    }
+
+    /// Lists the revisions of a clip, most recently replaced first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails or the store is locked.
+    pub fn list_clip_revisions(&self, clip_id: i32) -> SqliteResult<Vec<ClipRevision>> {
+        let conn = self.reader();
+
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT id, clip_id, clip_text(content), replaced_at
+            FROM clip_revisions
+            WHERE clip_id = ?1
+            ORDER BY replaced_at DESC, id DESC
+            "#,
+        )?;
+
+        let revisions = stmt
+            .query_map([clip_id], |row| {
+                Ok(ClipRevision {
+                    id: row.get(0)?,
+                    clip_id: row.get(1)?,
+                    content: row.get(2)?,
+                    replaced_at: from_epoch_ms(row.get(3)?),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(revisions)
+    }
+
+    /// Restores the content of a revision, keeping the current content as a new revision.
+    ///
+    /// `auto_tags` is used as in [`ClipStore::update_clip_content`]. Returns the
+    /// updated clip.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`rusqlite::Error::QueryReturnedNoRows`] if the revision does not
+    /// exist or its clip is in the trash, or another error if the store is locked
+    /// or the update fails.
+    pub fn revert_clip_revision(
+        &self,
+        revision_id: i64,
+        auto_tags: fn(&str, &str) -> Vec<String>,
+    ) -> SqliteResult<Clip> {
+        self.write_with_cipher(move |conn, cipher| {
+            let tx = conn.transaction()?;
+            let (clip_id, content): (i32, String) = tx.query_row(
+                "SELECT clip_id, clip_text(content) FROM clip_revisions WHERE id = ?1",
+                [revision_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )?;
+
+            replace_content(&tx, clip_id, &content, cipher, auto_tags)?;
+            let clip = fetch_clip(&tx, clip_id)?;
+            tx.commit()?;
+
+            debug!("Reverted clip id={} to revision {}", clip_id, revision_id);
+            Ok(clip)
+        })
+    }
+}
+
+// ===== Helper Functions =====
+
+/// Sets the content of a live clip to `content`, recording the old content as a revision.
+fn replace_content(
+    conn: &Connection,
+    id: i32,
+    content: &str,
+    cipher: Option<&ClipCipher>,
+    auto_tags: fn(&str, &str) -> Vec<String>,
+) -> SqliteResult<()> {
+    let (current, app_name): (String, String) = conn
+        .query_row(
+            "SELECT clip_text(content), app_name FROM clips WHERE id = ?1 AND deleted_at IS NULL",
+            [id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?
+        .ok_or(rusqlite::Error::QueryReturnedNoRows)?;
+    if current == content {
+        return Ok(());
+    }
+
+    let now = Utc::now().timestamp_millis();
+    // The stored value is copied as is, so it stays encrypted if it was
+    conn.execute(
+        "INSERT INTO clip_revisions (clip_id, content, replaced_at) \
+         SELECT id, content, ?1 FROM clips WHERE id = ?2",
+        params![now, id],
+    )?;
+
+    let (stored, preview, hash) = encode_content(content, cipher)?;
+    conn.execute(
+        r#"
+        UPDATE clips
+        SET content = ?1, preview = ?2, content_hash = ?3, byte_len = ?4, line_count = ?5,
+            updated_at = ?6
+        WHERE id = ?7
+        "#,
+        params![
+            stored,
+            preview,
+            hash,
+            content.len() as i64,
+            line_count(content),
+            now,
+            id
+        ],
+    )?;
+
+    replace_auto_tags(conn, id, &auto_tags(content, &app_name))?;
+
+    debug!("Edited clip id={} ({} bytes)", id, content.len());
+    Ok(())
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{ClipQuery, SaveOutcome};
+
+    fn setup_test_store() -> ClipStore {
+        ClipStore::new(":memory:").expect("Failed to create in-memory store")
+    }
+
+    /// Tags every clip with its app name and `#edited`.
+    fn fake_tags(_content: &str, app_name: &str) -> Vec<String> {
+        vec![format!("#{}", app_name.to_lowercase()), "#edited".into()]
+    }
+
+    fn save(store: &ClipStore, content: &str) -> i32 {
+        let clip = Clip::new(
+            content.into(),
+            "Editor".into(),
+            "Win".into(),
+            vec!["#original".into()],
+            false,
+        );
+        store.save_clip(clip).unwrap().id.unwrap()
+    }
+
+    #[test]
+    fn test_edit_keeps_previous_content_as_revision() {
+        let store = setup_test_store();
+        let id = save(&store, "teh quick fox");
+        store.add_user_tag(id, "mine").unwrap();
+
+        let edited = store
+            .update_clip_content(id, "the quick fox".into(), fake_tags)
+            .unwrap();
+        assert_eq!(edited.content, "the quick fox");
+        assert_eq!(edited.tags, vec!["#edited", "#editor"]);
+        assert_eq!(edited.user_tags, vec!["mine"]);
+
+        let revisions = store.list_clip_revisions(id).unwrap();
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].content, "teh quick fox");
+
+        store
+            .update_clip_content(id, "the quick fox".into(), fake_tags)
+            .unwrap();
+        assert_eq!(store.list_clip_revisions(id).unwrap().len(), 1);
+        assert!(store
+            .update_clip_content(id, "  ".into(), fake_tags)
+            .is_err());
+    }
+
+    #[test]
+    fn test_revert_restores_content_and_keeps_history() {
+        let store = setup_test_store();
+        let id = save(&store, "first");
+        store
+            .update_clip_content(id, "second".into(), fake_tags)
+            .unwrap();
+        let first = store.list_clip_revisions(id).unwrap()[0].id;
+
+        let reverted = store.revert_clip_revision(first, fake_tags).unwrap();
+        assert_eq!(reverted.content, "first");
+
+        let contents: Vec<String> = store
+            .list_clip_revisions(id)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.content)
+            .collect();
+        assert_eq!(contents, vec!["second", "first"]);
+        assert!(store.revert_clip_revision(999, fake_tags).is_err());
+    }
+
+    #[test]
+    fn test_edited_content_is_searched_and_deduplicated() {
+        let store = setup_test_store();
+        let id = save(&store, "old words");
+        store
+            .update_clip_content(id, "new words".into(), fake_tags)
+            .unwrap();
+
+        let query = ClipQuery::parse("new").unwrap();
+        assert_eq!(store.search_clips(&query, 10).unwrap().len(), 1);
+        let query = ClipQuery::parse("old").unwrap();
+        assert!(store.search_clips(&query, 10).unwrap().is_empty());
+
+        let again = Clip::new(
+            "new words".into(),
+            "App".into(),
+            "Win".into(),
+            vec![],
+            false,
+        );
+        assert!(matches!(
+            store.save_or_bump_clip(again).unwrap(),
+            SaveOutcome::Bumped(clip) if clip.id == Some(id)
+        ));
+    }
+
+    #[test]
+    fn test_revisions_are_removed_with_their_clip() {
+        let store = setup_test_store();
+        let id = save(&store, "draft");
+        store
+            .update_clip_content(id, "final".into(), fake_tags)
+            .unwrap();
+
+        store.remove_clip(id).unwrap();
+        assert!(store
+            .update_clip_content(id, "trashed".into(), fake_tags)
+            .is_err());
+        store.empty_trash().unwrap();
+
+        assert!(store.list_clip_revisions(id).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,411 @@
+// src-tauri/src/storage/search.rs
+//! Full-text search over clipboard history.
+//!
+//! Search is backed by an SQLite FTS5 index (`clips_fts`) covering the clip
+//! `content`, `app_name`, `window_title`, auto and user tags, and note. The
+//! index is kept in sync with the `clips` and `clip_tags` tables by triggers,
+//! so callers never need to maintain it manually.
+//!
+//! Queries are expressed as a parsed [`ClipQuery`], combining free text with
+//! structured filters.
+
+// ===== Imports =====
+
+use rusqlite::{params_from_iter, types::Value, Result as SqliteResult};
+use serde::{Deserialize, Serialize};
+
+use crate::storage::{
+    clip_store::{row_to_clip, CLIP_COLUMNS, CLIP_COLUMN_COUNT},
+    Clip, ClipOrder, ClipQuery, ClipStore,
+};
+
+// ===== Constants =====
+
+/// Marker inserted before each matched term in a snippet.
+pub const SNIPPET_MATCH_START: &str = "<mark>";
+
+/// Marker inserted after each matched term in a snippet.
+pub const SNIPPET_MATCH_END: &str = "</mark>";
+
+/// Maximum number of tokens included in a snippet.
+const SNIPPET_TOKENS: i64 = 16;
+
+/// Snippet length (in characters) used when a query has no free text to highlight.
+const PLAIN_SNIPPET_CHARS: i64 = 120;
+
+// ===== Domain Types =====
+
+/// A single ranked search hit.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ClipSearchResult {
+    /// The matching clip.
+    pub clip: Clip,
+
+    /// A short excerpt of the best matching column, with matched terms wrapped
+    /// in [`SNIPPET_MATCH_START`] / [`SNIPPET_MATCH_END`].
+    pub snippet: String,
+
+    /// Relevance score. Higher values indicate a better match.
+    pub score: f64,
+}
+
+// ===== Public API Implementation =====
+
+impl ClipStore {
+    /// Searches clip history with a parsed [`ClipQuery`].
+    ///
+    /// Free-text terms are matched against the full-text index and results are
+    /// ordered by relevance (BM25). Structured filters (`app:`, `tag:`, dates,
+    /// `pinned:`) narrow the result set.
+    ///
+    /// When the query has no free text, matching clips are returned most recently copied first
+    /// with a score of `0.0` and the beginning of the content as the snippet. The same
+    /// applies when clip content is encrypted, in which case free text is matched by
+    /// scanning decrypted content instead of the index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn search_clips(
+        &self,
+        query: &ClipQuery,
+        limit: i32,
+    ) -> SqliteResult<Vec<ClipSearchResult>> {
+        // Encrypted content is not indexed
+        let use_fts = !self.is_encrypted();
+        let (filter_sql, mut values) = if use_fts {
+            query.filter_sql()
+        } else {
+            query.where_sql(false)
+        };
+
+        let sql = match query.match_expression().filter(|_| use_fts) {
+            Some(match_expr) => {
+                let mut params = vec![
+                    Value::Text(SNIPPET_MATCH_START.to_string()),
+                    Value::Text(SNIPPET_MATCH_END.to_string()),
+                    Value::Integer(SNIPPET_TOKENS),
+                    Value::Text(match_expr),
+                ];
+                params.append(&mut values);
+                values = params;
+
+                format!(
+                    r#"
+                    SELECT {CLIP_COLUMNS},
+                           snippet(clips_fts, -1, ?, ?, '…', ?) AS snippet,
+                           -bm25(clips_fts) AS score
+                    FROM clips_fts
+                    JOIN clips ON clips.id = clips_fts.rowid
+                    WHERE clips_fts MATCH ? AND {filter_sql}
+                    ORDER BY bm25(clips_fts)
+                    LIMIT ?
+                    "#
+                )
+            }
+            None => format!(
+                r#"
+                SELECT {CLIP_COLUMNS},
+                       substr(clip_text(coalesce(clips.preview, clips.content)), 1, {PLAIN_SNIPPET_CHARS}) AS snippet,
+                       0.0 AS score
+                FROM clips
+                WHERE {filter_sql}
+                ORDER BY clips.last_copied_at DESC
+                LIMIT ?
+                "#
+            ),
+        };
+        values.push(Value::Integer(limit.into()));
+
+        let conn = self.reader();
+        let mut stmt = conn.prepare(&sql)?;
+
+        let results = stmt
+            .query_map(params_from_iter(values), |row| {
+                Ok(ClipSearchResult {
+                    clip: row_to_clip(row)?,
+                    snippet: row.get(CLIP_COLUMN_COUNT)?,
+                    score: row.get(CLIP_COLUMN_COUNT + 1)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(results)
+    }
+
+    /// Lists clips matching a parsed [`ClipQuery`] in the given order.
+    ///
+    /// Unlike [`ClipStore::search_clips`], results are not ranked by relevance and
+    /// carry no snippet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn query_clips(
+        &self,
+        query: &ClipQuery,
+        order: ClipOrder,
+        limit: i32,
+    ) -> SqliteResult<Vec<Clip>> {
+        let (where_sql, mut values) = query.where_sql(!self.is_encrypted());
+        values.push(Value::Integer(limit.into()));
+
+        let conn = self.reader();
+        let mut stmt = conn.prepare(&format!(
+            r#"
+            SELECT {CLIP_COLUMNS}
+            FROM clips
+            WHERE {where_sql}
+            ORDER BY {}
+            LIMIT ?
+            "#,
+            order.order_by_sql()
+        ))?;
+
+        let clips = stmt
+            .query_map(params_from_iter(values), row_to_clip)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(clips)
+    }
+
+    /// Lists every clip matching `query` with its full content, oldest first.
+    ///
+    /// Unlike other listings, long clips are not cut down to a preview. Use this
+    /// when whole bodies are needed, e.g. for exports.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails or the store is locked.
+    pub fn query_full_clips(&self, query: &ClipQuery) -> SqliteResult<Vec<Clip>> {
+        let (where_sql, values) = query.where_sql(!self.is_encrypted());
+
+        let conn = self.reader();
+        let mut stmt = conn.prepare(&format!(
+            r#"
+            SELECT {CLIP_COLUMNS}, clip_text(clips.content)
+            FROM clips
+            WHERE {where_sql}
+            ORDER BY clips.created_at ASC, clips.id ASC
+            "#
+        ))?;
+
+        let clips = stmt
+            .query_map(params_from_iter(values), |row| {
+                Ok(Clip {
+                    content: row.get(CLIP_COLUMN_COUNT)?,
+                    is_truncated: false,
+                    ..row_to_clip(row)?
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(clips)
+    }
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn setup_test_store() -> ClipStore {
+        ClipStore::new(":memory:").expect("Failed to create in-memory store")
+    }
+
+    fn search(store: &ClipStore, query: &str) -> Vec<ClipSearchResult> {
+        let query = ClipQuery::parse(query).unwrap();
+        store.search_clips(&query, 10).unwrap()
+    }
+
+    fn save(store: &ClipStore, content: &str, app: &str, tags: &[&str]) -> Clip {
+        let clip = Clip::new(
+            content.into(),
+            app.into(),
+            "Window".into(),
+            tags.iter().map(|t| t.to_string()).collect(),
+            false,
+        );
+        store.save_clip(clip).unwrap()
+    }
+
+    #[test]
+    fn test_search_matches_content_with_snippet() {
+        let store = setup_test_store();
+        save(&store, "Invoice 2024 for ACME", "Firefox", &["#url"]);
+        save(&store, "Unrelated text", "Terminal", &[]);
+
+        let results = search(&store, "invoice");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].clip.content, "Invoice 2024 for ACME");
+        assert!(results[0].snippet.contains("<mark>Invoice</mark>"));
+    }
+
+    #[test]
+    fn test_search_matches_metadata_and_prefixes() {
+        let store = setup_test_store();
+        save(&store, "cargo build", "Alacritty", &["#terminal"]);
+        save(&store, "hello", "Firefox", &["#browser"]);
+
+        let by_app = search(&store, "alacr");
+        assert_eq!(by_app.len(), 1);
+        assert_eq!(by_app[0].clip.content, "cargo build");
+
+        let by_tag = search(&store, "browser");
+        assert_eq!(by_tag.len(), 1);
+        assert_eq!(by_tag[0].clip.content, "hello");
+    }
+
+    #[test]
+    fn test_search_matches_user_tags_and_notes() {
+        let store = setup_test_store();
+        let id = save(&store, "lorem ipsum", "App", &[]).id.unwrap();
+        save(&store, "dolor sit", "App", &[]);
+
+        store.add_user_tag(id, "quarterly").unwrap();
+        store.set_clip_note(id, Some("send to accounting")).unwrap();
+
+        assert_eq!(search(&store, "quarterly")[0].clip.content, "lorem ipsum");
+        assert_eq!(search(&store, "accounting")[0].clip.content, "lorem ipsum");
+
+        store.set_clip_note(id, None).unwrap();
+        assert!(search(&store, "accounting").is_empty());
+    }
+
+    #[test]
+    fn test_search_ranks_better_matches_first() {
+        let store = setup_test_store();
+        save(
+            &store,
+            "rust is mentioned once among many other words here",
+            "App",
+            &[],
+        );
+        save(&store, "rust rust rust", "App", &[]);
+
+        let results = search(&store, "rust");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].clip.content, "rust rust rust");
+        assert!(results[0].score >= results[1].score);
+    }
+
+    #[test]
+    fn test_search_index_follows_updates_and_deletes() {
+        let store = setup_test_store();
+        let saved = save(&store, "temporary secret", "App", &[]);
+        assert_eq!(search(&store, "secret").len(), 1);
+
+        store.remove_clip(saved.id.unwrap()).unwrap();
+        assert!(search(&store, "secret").is_empty());
+
+        save(&store, "another secret", "App", &[]);
+        store.clear_clip_history().unwrap();
+        assert!(search(&store, "secret").is_empty());
+    }
+
+    #[test]
+    fn test_index_is_backfilled_for_existing_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("legacy.db");
+        {
+            let conn = rusqlite::Connection::open(&db_path).unwrap();
+            conn.execute_batch(
+                r#"
+                CREATE TABLE clips (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    content TEXT NOT NULL,
+                    app_name TEXT,
+                    window_title TEXT,
+                    auto_tags TEXT,
+                    is_pinned BOOLEAN NOT NULL DEFAULT 0,
+                    created_at TEXT NOT NULL,
+                    updated_at TEXT NOT NULL
+                );
+                INSERT INTO clips (content, app_name, window_title, auto_tags, is_pinned, created_at, updated_at)
+                VALUES ('legacy invoice', 'App', 'Win', '', 0, '2024-01-01T00:00:00+00:00', '2024-01-01T00:00:00+00:00');
+                "#,
+            )
+            .unwrap();
+        }
+
+        let store = ClipStore::new(&db_path).unwrap();
+        let results = search(&store, "invoice");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].clip.content, "legacy invoice");
+    }
+
+    #[test]
+    fn test_search_handles_fts_syntax_characters() {
+        let store = setup_test_store();
+        save(&store, "SELECT * FROM users WHERE id = 1", "App", &[]);
+
+        let results = search(&store, r#"* "FROM users" WHERE"#);
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    fn test_search_applies_filters() {
+        let store = setup_test_store();
+        save(&store, "invoice from firefox", "Firefox", &["#url"]);
+        save(&store, "invoice from terminal", "Alacritty", &["#terminal"]);
+
+        let results = search(&store, "app:firefox invoice");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].clip.app_name, "Firefox");
+
+        let results = search(&store, "tag:terminal invoice");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].clip.app_name, "Alacritty");
+    }
+
+    #[test]
+    fn test_search_without_free_text_lists_filtered_clips() {
+        let store = setup_test_store();
+        save(&store, "pinned one", "App", &[]);
+        save(&store, "plain", "App", &[]);
+        let pinned = store.list_recent_clips(10).unwrap()[1].clone();
+        store.toggle_pin_status(pinned.id.unwrap(), true).unwrap();
+
+        let results = search(&store, "pinned:yes");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].snippet, "pinned one");
+        assert_eq!(results[0].score, 0.0);
+    }
+
+    #[test]
+    fn test_query_clips_filters_by_date_and_text() {
+        let store = setup_test_store();
+        let mut old = Clip::new("old note".into(), "App".into(), "Win".into(), vec![], false);
+        old.created_at = Utc::now() - Duration::days(10);
+        store.save_clip(old).unwrap();
+        save(&store, "new note", "App", &[]);
+
+        let query = ClipQuery::parse("note after:2d").unwrap();
+        let clips = store.query_clips(&query, ClipOrder::Recent, 10).unwrap();
+        assert_eq!(clips.len(), 1);
+        assert_eq!(clips[0].content, "new note");
+
+        let query = ClipQuery::parse("before:last week").unwrap();
+        let clips = store.query_clips(&query, ClipOrder::Recent, 10).unwrap();
+        assert_eq!(clips.len(), 1);
+        assert_eq!(clips[0].content, "old note");
+    }
+
+    #[test]
+    fn test_query_full_clips_returns_whole_bodies_oldest_first() {
+        let store = setup_test_store();
+        let long = "x".repeat(crate::storage::PREVIEW_CHARS * 2);
+        let mut old = Clip::new(long.clone(), "App".into(), "Win".into(), vec![], false);
+        old.created_at = Utc::now() - Duration::days(1);
+        store.save_clip(old).unwrap();
+        save(&store, "short", "App", &[]);
+
+        let clips = store.query_full_clips(&ClipQuery::default()).unwrap();
+        assert_eq!(clips.len(), 2);
+        assert_eq!(clips[0].content, long);
+        assert!(!clips[0].is_truncated);
+        assert_eq!(clips[1].content, "short");
+    }
+}
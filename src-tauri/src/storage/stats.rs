@@ -0,0 +1,420 @@
+// src-tauri/src/storage/stats.rs
+//! Usage statistics over the clipboard history.
+//!
+//! [`ClipStore::clip_stats`] summarises the live clips created within a time
+//! range: how many came from each app, carried each tag, or were captured in
+//! each day or hour, which contents are copied most often, and how much space
+//! they take up. All figures are read from one snapshot of the database.
+
+// ===== Imports =====
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, Result as SqliteResult};
+use serde::{Deserialize, Serialize};
+
+use crate::storage::{ClipStore, TagCount};
+
+// ===== Constants =====
+
+/// Maximum number of entries in [`ClipStats::top_repeated`].
+const TOP_REPEATED_LIMIT: i64 = 10;
+
+/// Size of a clip in bytes; computed from the content for rows that predate stored sizes.
+const CLIP_BYTES: &str = "coalesce(clips.byte_len, length(CAST(clip_text(clips.content) AS BLOB)))";
+
+/// Live clips created in `[?1, ?2)`, in milliseconds since the epoch.
+const IN_RANGE: &str =
+    "clips.deleted_at IS NULL AND clips.created_at >= ?1 AND clips.created_at < ?2";
+
+// ===== Domain Types =====
+
+/// Length of the periods clips are counted in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StatsInterval {
+    /// Calendar days in local time, labelled `YYYY-MM-DD`.
+    #[default]
+    Day,
+    /// Hours in local time, labelled `YYYY-MM-DDTHH:00`.
+    Hour,
+}
+
+/// Number of clips captured from one application.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AppCount {
+    /// The application name, empty if it was not recorded.
+    pub name: String,
+    /// Number of clips captured from the application.
+    pub count: i64,
+}
+
+/// Number of clips captured in one day or hour.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PeriodCount {
+    /// Start of the period in local time (see [`StatsInterval`]).
+    pub period: String,
+    /// Number of clips captured in the period.
+    pub count: i64,
+}
+
+/// A content copied more than once.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RepeatedContent {
+    /// The most recent clip holding the content.
+    pub clip_id: i32,
+    /// The content, cut down to a preview if long.
+    pub content: String,
+    /// Number of times the content was copied, across all clips holding it.
+    pub copy_count: i64,
+}
+
+/// Usage statistics for the clips created within a time range.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ClipStats {
+    /// Start of the range, or `None` for the beginning of the history.
+    pub from: Option<DateTime<Utc>>,
+    /// End of the range (exclusive), or `None` for now.
+    pub to: Option<DateTime<Utc>>,
+    /// Number of clips in the range.
+    pub total_clips: i64,
+    /// Number of pinned clips in the range.
+    pub pinned_clips: i64,
+    /// Combined size of the clips in the range, in bytes.
+    pub total_bytes: i64,
+    /// Average clip size in bytes; `0` if the range holds no clips.
+    pub average_bytes: f64,
+    /// Size of the whole database file, in bytes.
+    pub database_bytes: i64,
+    /// Clips per application, most first.
+    pub by_app: Vec<AppCount>,
+    /// Clips per tag, most first.
+    pub by_tag: Vec<TagCount>,
+    /// Clips per day or hour, oldest first. Periods without clips are omitted.
+    pub by_period: Vec<PeriodCount>,
+    /// The most often copied contents, most first.
+    pub top_repeated: Vec<RepeatedContent>,
+}
+
+// ===== Public API Implementation =====
+
+impl ClipStore {
+    /// Computes usage statistics for live clips created in `[from, to)`.
+    ///
+    /// Either bound may be `None` to leave that side of the range open. Trashed
+    /// clips are not counted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a query fails or the store is locked.
+    pub fn clip_stats(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        interval: StatsInterval,
+    ) -> SqliteResult<ClipStats> {
+        let bounds = (
+            from.map_or(i64::MIN, |t| t.timestamp_millis()),
+            to.map_or(i64::MAX, |t| t.timestamp_millis()),
+        );
+
+        let conn = self.reader();
+        // A read transaction keeps every figure on the same snapshot
+        let tx = conn.unchecked_transaction()?;
+
+        let (total_clips, pinned_clips, total_bytes): (i64, i64, i64) = tx.query_row(
+            &format!(
+                "SELECT COUNT(*), coalesce(SUM(clips.is_pinned), 0), coalesce(SUM({CLIP_BYTES}), 0) \
+                 FROM clips WHERE {IN_RANGE}"
+            ),
+            params![bounds.0, bounds.1],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+
+        let stats = ClipStats {
+            from,
+            to,
+            total_clips,
+            pinned_clips,
+            total_bytes,
+            average_bytes: if total_clips > 0 {
+                total_bytes as f64 / total_clips as f64
+            } else {
+                0.0
+            },
+            database_bytes: tx.query_row(
+                "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+                [],
+                |row| row.get(0),
+            )?,
+            by_app: count_by_app(&tx, bounds)?,
+            by_tag: count_by_tag(&tx, bounds)?,
+            by_period: count_by_period(&tx, bounds, interval)?,
+            top_repeated: top_repeated(&tx, bounds)?,
+        };
+
+        tx.commit()?;
+        Ok(stats)
+    }
+}
+
+// ===== Helper Functions =====
+
+/// Counts clips in range per application.
+fn count_by_app(conn: &Connection, bounds: (i64, i64)) -> SqliteResult<Vec<AppCount>> {
+    let mut stmt = conn.prepare(&format!(
+        r#"
+        SELECT coalesce(clips.app_name, '') AS name, COUNT(*) AS count
+        FROM clips
+        WHERE {IN_RANGE}
+        GROUP BY name
+        ORDER BY count DESC, name ASC
+        "#
+    ))?;
+
+    let apps = stmt
+        .query_map(params![bounds.0, bounds.1], |row| {
+            Ok(AppCount {
+                name: row.get(0)?,
+                count: row.get(1)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(apps)
+}
+
+/// Counts clips in range per tag.
+fn count_by_tag(conn: &Connection, bounds: (i64, i64)) -> SqliteResult<Vec<TagCount>> {
+    let mut stmt = conn.prepare(&format!(
+        r#"
+        SELECT tags.name, COUNT(DISTINCT clips.id) AS count
+        FROM clips
+        JOIN clip_tags ON clip_tags.clip_id = clips.id
+        JOIN tags ON tags.id = clip_tags.tag_id
+        WHERE {IN_RANGE}
+        GROUP BY tags.id
+        ORDER BY count DESC, tags.name ASC
+        "#
+    ))?;
+
+    let tags = stmt
+        .query_map(params![bounds.0, bounds.1], |row| {
+            Ok(TagCount {
+                name: row.get(0)?,
+                count: row.get(1)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(tags)
+}
+
+/// Counts clips in range per local day or hour.
+fn count_by_period(
+    conn: &Connection,
+    bounds: (i64, i64),
+    interval: StatsInterval,
+) -> SqliteResult<Vec<PeriodCount>> {
+    let format = match interval {
+        StatsInterval::Day => "%Y-%m-%d",
+        StatsInterval::Hour => "%Y-%m-%dT%H:00",
+    };
+
+    let mut stmt = conn.prepare(&format!(
+        r#"
+        SELECT strftime(?3, clips.created_at / 1000, 'unixepoch', 'localtime') AS period,
+               COUNT(*)
+        FROM clips
+        WHERE {IN_RANGE}
+        GROUP BY period
+        ORDER BY period ASC
+        "#
+    ))?;
+
+    let periods = stmt
+        .query_map(params![bounds.0, bounds.1, format], |row| {
+            Ok(PeriodCount {
+                period: row.get(0)?,
+                count: row.get(1)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(periods)
+}
+
+/// Lists the contents copied most often in range, counting every clip holding them.
+fn top_repeated(conn: &Connection, bounds: (i64, i64)) -> SqliteResult<Vec<RepeatedContent>> {
+    // With max(), SQLite takes the bare columns from the row holding the maximum
+    let mut stmt = conn.prepare(&format!(
+        r#"
+        SELECT max(clips.id), clip_text(coalesce(clips.preview, clips.content)),
+               SUM(clips.copy_count) AS copies
+        FROM clips
+        WHERE {IN_RANGE}
+        GROUP BY clips.content_hash
+        HAVING copies > 1
+        ORDER BY copies DESC, max(clips.id) DESC
+        LIMIT ?3
+        "#
+    ))?;
+
+    let repeated = stmt
+        .query_map(params![bounds.0, bounds.1, TOP_REPEATED_LIMIT], |row| {
+            Ok(RepeatedContent {
+                clip_id: row.get(0)?,
+                content: row.get(1)?,
+                copy_count: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(repeated)
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Clip;
+    use chrono::{Duration, Local, TimeZone};
+
+    fn setup_test_store() -> ClipStore {
+        ClipStore::new(":memory:").expect("Failed to create in-memory store")
+    }
+
+    fn save(
+        store: &ClipStore,
+        content: &str,
+        app: &str,
+        tags: &[&str],
+        pinned: bool,
+        created_at: DateTime<Utc>,
+    ) {
+        let tags = tags.iter().map(|t| t.to_string()).collect();
+        let mut clip = Clip::new(content.into(), app.into(), "Win".into(), tags, pinned);
+        clip.created_at = created_at;
+        clip.last_copied_at = created_at;
+        store.save_clip(clip).unwrap();
+    }
+
+    #[test]
+    fn test_counts_sizes_and_breakdowns() {
+        let store = setup_test_store();
+        let now = Utc::now();
+        save(&store, "https://a.example", "Firefox", &["#url"], true, now);
+        save(&store, "hello", "Firefox", &[], false, now);
+        save(&store, "ls -la", "Alacritty", &["#terminal"], false, now);
+
+        let stats = store.clip_stats(None, None, StatsInterval::Day).unwrap();
+
+        assert_eq!(stats.total_clips, 3);
+        assert_eq!(stats.pinned_clips, 1);
+        assert_eq!(stats.total_bytes, 17 + 5 + 6);
+        assert!((stats.average_bytes - 28.0 / 3.0).abs() < f64::EPSILON);
+        assert!(stats.database_bytes > 0);
+        assert_eq!(
+            stats.by_app,
+            vec![
+                AppCount {
+                    name: "Firefox".into(),
+                    count: 2
+                },
+                AppCount {
+                    name: "Alacritty".into(),
+                    count: 1
+                },
+            ]
+        );
+        assert_eq!(stats.by_tag.len(), 2);
+        assert_eq!(stats.by_period.len(), 1);
+        assert_eq!(stats.by_period[0].count, 3);
+    }
+
+    #[test]
+    fn test_range_excludes_other_and_trashed_clips() {
+        let store = setup_test_store();
+        let now = Utc::now();
+        save(&store, "old", "App", &[], false, now - Duration::days(30));
+        save(&store, "recent", "App", &[], false, now - Duration::days(1));
+        save(
+            &store,
+            "trashed",
+            "App",
+            &[],
+            false,
+            now - Duration::days(1),
+        );
+        let trashed = store.list_recent_clips(1).unwrap()[0].id.unwrap();
+        store.remove_clip(trashed).unwrap();
+
+        let stats = store
+            .clip_stats(Some(now - Duration::days(7)), None, StatsInterval::Day)
+            .unwrap();
+
+        assert_eq!(stats.total_clips, 1);
+        assert_eq!(stats.by_app[0].count, 1);
+    }
+
+    #[test]
+    fn test_hourly_periods_use_local_time() {
+        let store = setup_test_store();
+        let morning = Local.with_ymd_and_hms(2026, 3, 2, 9, 15, 0).unwrap();
+        save(&store, "a", "App", &[], false, morning.to_utc());
+        save(
+            &store,
+            "b",
+            "App",
+            &[],
+            false,
+            (morning + Duration::minutes(30)).to_utc(),
+        );
+        save(
+            &store,
+            "c",
+            "App",
+            &[],
+            false,
+            (morning + Duration::hours(2)).to_utc(),
+        );
+
+        let stats = store.clip_stats(None, None, StatsInterval::Hour).unwrap();
+
+        assert_eq!(
+            stats.by_period,
+            vec![
+                PeriodCount {
+                    period: "2026-03-02T09:00".into(),
+                    count: 2
+                },
+                PeriodCount {
+                    period: "2026-03-02T11:00".into(),
+                    count: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_top_repeated_counts_bumps_and_duplicates() {
+        let store = setup_test_store();
+        let now = Utc::now();
+        save(&store, "once", "App", &[], false, now);
+        save(&store, "twice", "App", &[], false, now);
+        save(&store, "twice", "App", &[], false, now);
+        for _ in 0..3 {
+            let clip = Clip::new("bumped".into(), "App".into(), "Win".into(), vec![], false);
+            store.save_or_bump_clip(clip).unwrap();
+        }
+
+        let stats = store.clip_stats(None, None, StatsInterval::Day).unwrap();
+
+        let repeated: Vec<(&str, i64)> = stats
+            .top_repeated
+            .iter()
+            .map(|r| (r.content.as_str(), r.copy_count))
+            .collect();
+        assert_eq!(repeated, vec![("bumped", 3), ("twice", 2)]);
+    }
+}
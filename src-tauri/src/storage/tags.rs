@@ -0,0 +1,320 @@
+// src-tauri/src/storage/tags.rs
+//! Tag storage and tag-based queries.
+//!
+//! Tags live in a `tags` table (one row per distinct name) linked to clips
+//! through the `clip_tags` join table. Links are removed automatically when
+//! a clip is deleted.
+//!
+//! Each link is either an auto tag, generated from the clip's content and source,
+//! or a user tag added with [`ClipStore::add_user_tag`]. The two are flagged apart
+//! by `clip_tags.is_user` and returned in [`Clip::tags`] and [`Clip::user_tags`].
+//! Tag filters and counts cover both.
+
+// ===== Imports =====
+
+use chrono::Utc;
+use rusqlite::{params, Connection, Result as SqliteResult};
+use serde::{Deserialize, Serialize};
+
+use crate::storage::{clip_store::fetch_clip, Clip, ClipOrder, ClipQuery, ClipStore};
+
+// ===== Domain Types =====
+
+/// A tag together with the number of clips that carry it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TagCount {
+    /// The tag name (e.g., `"#url"`).
+    pub name: String,
+    /// Number of clips carrying the tag.
+    pub count: i64,
+}
+
+// ===== Public API Implementation =====
+
+impl ClipStore {
+    /// Lists every tag in use, with the number of clips carrying it.
+    ///
+    /// Tags are ordered by descending count, then alphabetically.
+    /// Tags attached only to trashed clips, or to no clip at all, are omitted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn list_tags(&self) -> SqliteResult<Vec<TagCount>> {
+        let conn = self.reader();
+
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT tags.name, COUNT(DISTINCT clips.id) AS count
+            FROM tags
+            JOIN clip_tags ON clip_tags.tag_id = tags.id
+            JOIN clips ON clips.id = clip_tags.clip_id
+            WHERE clips.deleted_at IS NULL
+            GROUP BY tags.id
+            ORDER BY count DESC, tags.name ASC
+            "#,
+        )?;
+
+        let tags = stmt
+            .query_map([], |row| {
+                Ok(TagCount {
+                    name: row.get(0)?,
+                    count: row.get(1)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(tags)
+    }
+
+    /// Adds a user tag to a live clip and returns the updated clip.
+    ///
+    /// The tag is trimmed. Adding a tag the clip already carries as a user tag
+    /// changes nothing; one it carries as an auto tag is added as a user tag too,
+    /// so it is kept if auto tags change.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`rusqlite::Error::QueryReturnedNoRows`] if no live clip has the
+    /// given ID, or another error if the tag is blank or the update fails.
+    pub fn add_user_tag(&self, id: i32, tag: &str) -> SqliteResult<Clip> {
+        let tag = tag.trim().to_string();
+        if tag.is_empty() {
+            return Err(rusqlite::Error::ToSqlConversionFailure(
+                "Tags cannot be empty".into(),
+            ));
+        }
+
+        self.write(move |conn| {
+            let tx = conn.transaction()?;
+            touch_live_clip(&tx, id)?;
+            attach_tags(&tx, id, std::slice::from_ref(&tag), true)?;
+            let clip = fetch_clip(&tx, id)?;
+            tx.commit()?;
+            Ok(clip)
+        })
+    }
+
+    /// Removes a user tag from a live clip and returns the updated clip.
+    ///
+    /// Auto tags are left alone; removing a tag the user did not add changes nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`rusqlite::Error::QueryReturnedNoRows`] if no live clip has the
+    /// given ID, or another error if the update fails.
+    pub fn remove_user_tag(&self, id: i32, tag: &str) -> SqliteResult<Clip> {
+        let tag = tag.trim().to_string();
+
+        self.write(move |conn| {
+            let tx = conn.transaction()?;
+            touch_live_clip(&tx, id)?;
+            tx.execute(
+                r#"
+                DELETE FROM clip_tags
+                WHERE clip_id = ?1 AND is_user = 1
+                  AND tag_id = (SELECT id FROM tags WHERE name = ?2)
+                "#,
+                params![id, tag],
+            )?;
+            prune_unused_tags(&tx)?;
+            let clip = fetch_clip(&tx, id)?;
+            tx.commit()?;
+            Ok(clip)
+        })
+    }
+
+    /// Lists the most recent clips carrying **all** of the given tags.
+    ///
+    /// Tag names must match exactly as stored (e.g., `"#url"`).
+    /// An empty `tags` slice matches every clip.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn list_clips_by_tags(&self, tags: &[String], limit: i32) -> SqliteResult<Vec<Clip>> {
+        let query = ClipQuery {
+            tags: tags.to_vec(),
+            ..ClipQuery::default()
+        };
+        self.query_clips(&query, ClipOrder::Recent, limit)
+    }
+}
+
+// ===== Helper Functions =====
+
+/// Links `tags` to the clip with the given ID as user or auto tags, creating tag
+/// rows as needed.
+///
+/// Duplicate and blank tags are ignored.
+pub(super) fn attach_tags(
+    conn: &Connection,
+    clip_id: i32,
+    tags: &[String],
+    is_user: bool,
+) -> SqliteResult<()> {
+    let mut insert_tag = conn.prepare_cached("INSERT OR IGNORE INTO tags (name) VALUES (?1)")?;
+    let mut link_tag = conn.prepare_cached(
+        "INSERT OR IGNORE INTO clip_tags (clip_id, tag_id, is_user) \
+         SELECT ?1, id, ?3 FROM tags WHERE name = ?2",
+    )?;
+
+    for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        insert_tag.execute([tag])?;
+        link_tag.execute(params![clip_id, tag, is_user])?;
+    }
+
+    Ok(())
+}
+
+/// Replaces the auto tags of the clip with the given ID, leaving its user tags alone.
+pub(super) fn replace_auto_tags(
+    conn: &Connection,
+    clip_id: i32,
+    tags: &[String],
+) -> SqliteResult<()> {
+    conn.execute(
+        "DELETE FROM clip_tags WHERE clip_id = ?1 AND is_user = 0",
+        [clip_id],
+    )?;
+    attach_tags(conn, clip_id, tags, false)?;
+    prune_unused_tags(conn)?;
+    Ok(())
+}
+
+/// Bumps `updated_at` of a live clip, failing if there is none with the given ID.
+fn touch_live_clip(conn: &Connection, id: i32) -> SqliteResult<()> {
+    let updated = conn.execute(
+        "UPDATE clips SET updated_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+        params![Utc::now().timestamp_millis(), id],
+    )?;
+    match updated {
+        0 => Err(rusqlite::Error::QueryReturnedNoRows),
+        _ => Ok(()),
+    }
+}
+
+/// Deletes tags that are no longer attached to any clip.
+pub(super) fn prune_unused_tags(conn: &Connection) -> SqliteResult<usize> {
+    conn.execute(
+        "DELETE FROM tags WHERE NOT EXISTS (SELECT 1 FROM clip_tags WHERE clip_tags.tag_id = tags.id)",
+        [],
+    )
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_test_store() -> ClipStore {
+        ClipStore::new(":memory:").expect("Failed to create in-memory store")
+    }
+
+    fn save(store: &ClipStore, content: &str, tags: &[&str]) -> Clip {
+        let clip = Clip::new(
+            content.into(),
+            "App".into(),
+            "Win".into(),
+            tags.iter().map(|t| t.to_string()).collect(),
+            false,
+        );
+        store.save_clip(clip).unwrap()
+    }
+
+    #[test]
+    fn test_tags_round_trip_without_loss() {
+        let store = setup_test_store();
+        save(&store, "tagged", &["#url", "a,b", "#url", " "]);
+
+        let clips = store.list_recent_clips(1).unwrap();
+        assert_eq!(clips[0].tags, vec!["#url".to_string(), "a,b".to_string()]);
+    }
+
+    #[test]
+    fn test_list_tags_with_counts() {
+        let store = setup_test_store();
+        save(&store, "one", &["#url", "#browser"]);
+        save(&store, "two", &["#url"]);
+        let removed = save(&store, "three", &["#code"]);
+        store.remove_clip(removed.id.unwrap()).unwrap();
+
+        let tags = store.list_tags().unwrap();
+        assert_eq!(
+            tags,
+            vec![
+                TagCount {
+                    name: "#url".into(),
+                    count: 2
+                },
+                TagCount {
+                    name: "#browser".into(),
+                    count: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_clips_by_tags_requires_all_tags() {
+        let store = setup_test_store();
+        save(&store, "url only", &["#url"]);
+        save(&store, "url and browser", &["#url", "#browser"]);
+        save(&store, "untagged", &[]);
+
+        let clips = store
+            .list_clips_by_tags(&["#url".into(), "#browser".into()], 10)
+            .unwrap();
+        assert_eq!(clips.len(), 1);
+        assert_eq!(clips[0].content, "url and browser");
+
+        let clips = store.list_clips_by_tags(&["#url".into()], 10).unwrap();
+        assert_eq!(clips.len(), 2);
+    }
+
+    #[test]
+    fn test_user_tags_are_kept_apart_from_auto_tags() {
+        let store = setup_test_store();
+        let id = save(&store, "tagged", &["#url"]).id.unwrap();
+
+        store.add_user_tag(id, " work ").unwrap();
+        let clip = store.add_user_tag(id, "#url").unwrap();
+        assert_eq!(clip.tags, vec!["#url".to_string()]);
+        assert_eq!(clip.user_tags, vec!["#url".to_string(), "work".to_string()]);
+        assert!(store.add_user_tag(id, "  ").is_err());
+
+        let tags = store.list_tags().unwrap();
+        assert_eq!(tags.iter().find(|t| t.name == "#url").unwrap().count, 1);
+        assert_eq!(
+            store
+                .list_clips_by_tags(&["work".into()], 10)
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let clip = store.remove_user_tag(id, "#url").unwrap();
+        assert_eq!(clip.tags, vec!["#url".to_string()]);
+        assert_eq!(clip.user_tags, vec!["work".to_string()]);
+
+        let clip = store.remove_user_tag(id, "work").unwrap();
+        assert!(clip.user_tags.is_empty());
+        assert!(store.list_tags().unwrap().iter().all(|t| t.name != "work"));
+    }
+
+    #[test]
+    fn test_prune_unused_tags() {
+        let store = setup_test_store();
+        let saved = save(&store, "temp", &["#temp"]);
+
+        let id = saved.id.unwrap();
+        let pruned = store
+            .write(move |conn| {
+                conn.execute("DELETE FROM clips WHERE id = ?1", [id])?;
+                prune_unused_tags(conn)
+            })
+            .unwrap();
+        assert_eq!(pruned, 1);
+    }
+}
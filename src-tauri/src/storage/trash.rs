@@ -0,0 +1,349 @@
+// src-tauri/src/storage/trash.rs
+//! Soft deletion of clips.
+//!
+//! [`ClipStore::remove_clip`] and [`ClipStore::clear_clip_history`] move clips to
+//! the trash by setting `deleted_at` instead of deleting rows. Trashed clips are
+//! hidden from listings, search and retention, and can be restored individually,
+//! all at once, or by undoing the last deletion.
+//!
+//! Each deletion is recorded as a batch (`deleted_batch`), so undo restores exactly
+//! the clips removed by the most recent operation. Trashed clips are deleted for
+//! good by [`ClipStore::empty_trash`] or, after a retention period, by
+//! [`ClipStore::purge_trash`].
+
+// ===== Imports =====
+
+use chrono::{Duration, Utc};
+use rusqlite::{params, params_from_iter, types::Value, Connection, Result as SqliteResult};
+use tracing::debug;
+
+use crate::storage::{
+    clip_store::{fetch_clip, row_to_clip, CLIP_COLUMNS},
+    tags::prune_unused_tags,
+    Clip, ClipStore,
+};
+
+// ===== Public API Implementation =====
+
+impl ClipStore {
+    /// Lists trashed clips, most recently deleted first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn list_trash(&self, limit: i32) -> SqliteResult<Vec<Clip>> {
+        let conn = self.reader();
+
+        let mut stmt = conn.prepare(&format!(
+            r#"
+            SELECT {CLIP_COLUMNS}
+            FROM clips
+            WHERE clips.deleted_at IS NOT NULL
+            ORDER BY clips.deleted_at DESC, clips.id DESC
+            LIMIT ?1
+            "#
+        ))?;
+
+        let clips = stmt
+            .query_map([limit], row_to_clip)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(clips)
+    }
+
+    /// Restores a trashed clip.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`rusqlite::Error::QueryReturnedNoRows`] if no trashed clip has the
+    /// given ID, or another error if the update fails.
+    pub fn restore_clip(&self, id: i32) -> SqliteResult<Clip> {
+        self.write(move |conn| {
+            let tx = conn.transaction()?;
+
+            let mut restored = restore_where(&tx, "id = ?", vec![Value::Integer(id.into())])?;
+            tx.commit()?;
+
+            restored.pop().ok_or(rusqlite::Error::QueryReturnedNoRows)
+        })
+    }
+
+    /// Restores every trashed clip and returns them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the update fails.
+    pub fn restore_all(&self) -> SqliteResult<Vec<Clip>> {
+        self.write(move |conn| {
+            let tx = conn.transaction()?;
+
+            let restored = restore_where(&tx, "1", Vec::new())?;
+            tx.commit()?;
+
+            Ok(restored)
+        })
+    }
+
+    /// Reverses the most recent deletion, restoring every clip it moved to the trash.
+    ///
+    /// Returns the restored clips; empty if the trash is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the update fails.
+    pub fn undo_last_deletion(&self) -> SqliteResult<Vec<Clip>> {
+        self.write(move |conn| {
+            let tx = conn.transaction()?;
+
+            let last_batch: Option<i64> = tx.query_row(
+                "SELECT max(deleted_batch) FROM clips WHERE deleted_at IS NOT NULL",
+                [],
+                |row| row.get(0),
+            )?;
+            let restored = match last_batch {
+                Some(batch) => {
+                    restore_where(&tx, "deleted_batch = ?", vec![Value::Integer(batch)])?
+                }
+                None => Vec::new(),
+            };
+            tx.commit()?;
+
+            debug!("Undo restored {} clips", restored.len());
+            Ok(restored)
+        })
+    }
+
+    /// Permanently deletes every trashed clip. Returns the number deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the deletion fails.
+    pub fn empty_trash(&self) -> SqliteResult<usize> {
+        self.write(move |conn| {
+            let purged = conn.execute("DELETE FROM clips WHERE deleted_at IS NOT NULL", [])?;
+            prune_unused_tags(conn)?;
+
+            debug!("Emptied trash ({} clips)", purged);
+            Ok(purged)
+        })
+    }
+
+    /// Permanently deletes clips trashed more than `days` days ago. Returns the number deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the deletion fails.
+    pub fn purge_trash(&self, days: u32) -> SqliteResult<usize> {
+        // A period reaching past the earliest representable date purges nothing
+        let Some(cutoff) = Utc::now().checked_sub_signed(Duration::days(days.into())) else {
+            return Ok(0);
+        };
+        let cutoff = cutoff.timestamp_millis();
+
+        self.write(move |conn| {
+            let purged = conn.execute(
+                "DELETE FROM clips WHERE deleted_at IS NOT NULL AND deleted_at < ?1",
+                [cutoff],
+            )?;
+            prune_unused_tags(conn)?;
+
+            debug!(
+                "Purged {} clips trashed more than {} days ago",
+                purged, days
+            );
+            Ok(purged)
+        })
+    }
+}
+
+// ===== Helper Functions =====
+
+/// Moves the live clips matching `condition` to the trash as a new deletion batch.
+///
+/// Returns the number of clips trashed.
+pub(super) fn move_to_trash(
+    conn: &Connection,
+    condition: &str,
+    values: Vec<Value>,
+) -> SqliteResult<usize> {
+    let batch: i64 = conn.query_row(
+        "SELECT coalesce(max(deleted_batch), 0) + 1 FROM clips",
+        [],
+        |row| row.get(0),
+    )?;
+
+    let mut batch_values = vec![
+        Value::Integer(Utc::now().timestamp_millis()),
+        Value::Integer(batch),
+    ];
+    batch_values.extend(values);
+
+    conn.execute(
+        &format!(
+            "UPDATE clips SET deleted_at = ?, deleted_batch = ? \
+             WHERE deleted_at IS NULL AND {condition}"
+        ),
+        params_from_iter(batch_values),
+    )
+}
+
+/// Restores the trashed clips matching `condition` and returns them.
+fn restore_where(
+    conn: &Connection,
+    condition: &str,
+    values: Vec<Value>,
+) -> SqliteResult<Vec<Clip>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id FROM clips WHERE deleted_at IS NOT NULL AND {condition} ORDER BY id"
+    ))?;
+    let ids = stmt
+        .query_map(params_from_iter(values), |row| row.get::<_, i32>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut restored = Vec::with_capacity(ids.len());
+    for id in ids {
+        conn.execute(
+            "UPDATE clips SET deleted_at = NULL, deleted_batch = NULL WHERE id = ?1",
+            params![id],
+        )?;
+        restored.push(fetch_clip(conn, id)?);
+    }
+
+    Ok(restored)
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_test_store() -> ClipStore {
+        ClipStore::new(":memory:").expect("Failed to create in-memory store")
+    }
+
+    fn save(store: &ClipStore, content: &str, tags: &[&str]) -> Clip {
+        let tags = tags.iter().map(|t| t.to_string()).collect();
+        let clip = Clip::new(content.into(), "App".into(), "Win".into(), tags, false);
+        store.save_clip(clip).unwrap()
+    }
+
+    fn live_contents(store: &ClipStore) -> Vec<String> {
+        let mut contents: Vec<String> = store
+            .list_recent_clips(100)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.content)
+            .collect();
+        contents.sort();
+        contents
+    }
+
+    #[test]
+    fn test_removed_clip_moves_to_trash_and_restores() {
+        let store = setup_test_store();
+        let saved = save(&store, "Keep me", &["#note"]);
+        let id = saved.id.unwrap();
+
+        store.remove_clip(id).unwrap();
+        assert!(store.list_recent_clips(10).unwrap().is_empty());
+        assert!(store.list_tags().unwrap().is_empty());
+
+        let trash = store.list_trash(10).unwrap();
+        assert_eq!(trash.len(), 1);
+        assert!(trash[0].deleted_at.is_some());
+
+        let restored = store.restore_clip(id).unwrap();
+        assert_eq!(restored.content, "Keep me");
+        assert_eq!(restored.deleted_at, None);
+        assert_eq!(restored.tags, vec!["#note"]);
+        assert_eq!(live_contents(&store), vec!["Keep me"]);
+        assert!(store.list_trash(10).unwrap().is_empty());
+
+        assert!(matches!(
+            store.restore_clip(id),
+            Err(rusqlite::Error::QueryReturnedNoRows)
+        ));
+    }
+
+    #[test]
+    fn test_undo_reverses_only_the_last_deletion() {
+        let store = setup_test_store();
+        let first = save(&store, "first", &[]);
+        save(&store, "second", &[]);
+        save(&store, "third", &[]);
+
+        store.remove_clip(first.id.unwrap()).unwrap();
+        store.clear_clip_history().unwrap();
+        assert!(store.list_recent_clips(10).unwrap().is_empty());
+        assert_eq!(store.list_trash(10).unwrap().len(), 3);
+
+        let restored = store.undo_last_deletion().unwrap();
+        assert_eq!(restored.len(), 2);
+        assert_eq!(live_contents(&store), vec!["second", "third"]);
+
+        let restored = store.undo_last_deletion().unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(live_contents(&store), vec!["first", "second", "third"]);
+
+        assert!(store.undo_last_deletion().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_restore_all_empties_trash() {
+        let store = setup_test_store();
+        let a = save(&store, "a", &[]);
+        let b = save(&store, "b", &[]);
+        store.remove_clip(a.id.unwrap()).unwrap();
+        store.remove_clip(b.id.unwrap()).unwrap();
+
+        assert_eq!(store.restore_all().unwrap().len(), 2);
+        assert_eq!(live_contents(&store), vec!["a", "b"]);
+        assert!(store.list_trash(10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_trashed_clips_do_not_block_new_copies() {
+        let store = setup_test_store();
+        let saved = save(&store, "repeat", &[]);
+        store.remove_clip(saved.id.unwrap()).unwrap();
+
+        let clip = Clip::new("repeat".into(), "App".into(), "Win".into(), vec![], false);
+        let outcome = store.save_or_bump_clip(clip).unwrap();
+        assert!(matches!(outcome, crate::storage::SaveOutcome::Inserted(_)));
+    }
+
+    #[test]
+    fn test_empty_and_purge_trash_delete_for_good() {
+        let store = setup_test_store();
+        let old = save(&store, "old", &["#old"]);
+        let recent = save(&store, "recent", &[]);
+        store.remove_clip(old.id.unwrap()).unwrap();
+        store.remove_clip(recent.id.unwrap()).unwrap();
+
+        let long_ago = (Utc::now() - Duration::days(40)).timestamp_millis();
+        let old_id = old.id.unwrap();
+        store
+            .write(move |conn| {
+                conn.execute(
+                    "UPDATE clips SET deleted_at = ?1 WHERE id = ?2",
+                    params![long_ago, old_id],
+                )
+            })
+            .unwrap();
+
+        assert_eq!(store.purge_trash(100_000_000).unwrap(), 0);
+        assert_eq!(store.purge_trash(30).unwrap(), 1);
+        let trash = store.list_trash(10).unwrap();
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].content, "recent");
+
+        assert_eq!(store.empty_trash().unwrap(), 1);
+        assert!(store.list_trash(10).unwrap().is_empty());
+        let tags: i64 = store
+            .reader()
+            .query_row("SELECT COUNT(*) FROM tags", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(tags, 0);
+    }
+}
@@ -0,0 +1,216 @@
+// src-tauri/src/storage/usage.rs
+//! Clip usage tracking and frecency ranking.
+//!
+//! Every time a clip is pasted back out of the history, [`ClipStore::record_clip_use`]
+//! increments its `use_count` and stamps `last_used_at`. Listings can then be
+//! ordered by [`ClipOrder::Frecency`], which favours clips that are used often
+//! *and* recently, so frequently reused snippets rise to the top without pinning.
+//!
+//! # Scoring
+//!
+//! The frecency score is `(use_count + 1) × weight`, where `weight` depends on
+//! how long ago the clip was last used (or copied, if it was never used):
+//!
+//! | Age         | Weight |
+//! |-------------|--------|
+//! | ≤ 4 days    | 100    |
+//! | ≤ 14 days   | 70     |
+//! | ≤ 31 days   | 50     |
+//! | ≤ 90 days   | 30     |
+//! | older       | 10     |
+//!
+//! Ties are broken by `last_copied_at`, newest first, then by insertion order.
+
+// ===== Imports =====
+
+use chrono::Utc;
+use rusqlite::{params, types::Value, Result as SqliteResult};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::storage::{clip_store::fetch_clip, expiry::delete_clips, Clip, ClipStore};
+
+// ===== Constants =====
+
+/// Frecency score of a row in `clips`; see the module documentation.
+const FRECENCY_SCORE: &str = r#"
+    (clips.use_count + 1) * (
+        SELECT CASE
+            WHEN age <= 4 THEN 100
+            WHEN age <= 14 THEN 70
+            WHEN age <= 31 THEN 50
+            WHEN age <= 90 THEN 30
+            ELSE 10
+        END
+        FROM (
+            SELECT (unixepoch() * 1000 - coalesce(clips.last_used_at, clips.last_copied_at))
+                 / 86400000.0 AS age
+        )
+    )"#;
+
+// ===== Domain Types =====
+
+/// Sort order for clip listings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ClipOrder {
+    /// Most recently copied first.
+    #[default]
+    Recent,
+    /// Highest frecency score (frequency decayed by recency) first.
+    Frecency,
+}
+
+// ===== Public API Implementation =====
+
+impl ClipOrder {
+    /// Returns the SQL `ORDER BY` expression for this order over the `clips` table.
+    pub(crate) fn order_by_sql(self) -> String {
+        match self {
+            ClipOrder::Recent => "clips.last_copied_at DESC, clips.id DESC".to_string(),
+            ClipOrder::Frecency => {
+                format!("{FRECENCY_SCORE} DESC, clips.last_copied_at DESC, clips.id DESC")
+            }
+        }
+    }
+}
+
+impl ClipStore {
+    /// Records that the clip with the given ID was pasted back out of the history.
+    ///
+    /// Increments `use_count` and sets `last_used_at` to now. Returns the updated clip.
+    /// A clip marked with `delete_after_use` is then deleted for good, and its last
+    /// state is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`rusqlite::Error::QueryReturnedNoRows`] if no live clip has the given
+    /// ID, or another error if the update fails.
+    pub fn record_clip_use(&self, id: i32) -> SqliteResult<Clip> {
+        let clip = self.write(move |conn| {
+            let tx = conn.transaction()?;
+            let updated = tx.execute(
+                r#"
+                UPDATE clips SET use_count = use_count + 1, last_used_at = ?1
+                WHERE id = ?2 AND deleted_at IS NULL
+                "#,
+                params![Utc::now().timestamp_millis(), id],
+            )?;
+            if updated == 0 {
+                return Err(rusqlite::Error::QueryReturnedNoRows);
+            }
+            let clip = fetch_clip(&tx, id)?;
+            if clip.delete_after_use {
+                delete_clips(&tx, "id = ?", vec![Value::Integer(id.into())])?;
+            }
+            tx.commit()?;
+            Ok(clip)
+        })?;
+
+        if clip.delete_after_use {
+            debug!("Deleted clip id={} after use", id);
+        } else {
+            debug!("Recorded use of clip id={} ({} uses)", id, clip.use_count);
+        }
+        Ok(clip)
+    }
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn setup_test_store() -> ClipStore {
+        ClipStore::new(":memory:").expect("Failed to create in-memory store")
+    }
+
+    fn save(store: &ClipStore, content: &str, age_days: i64) -> i32 {
+        let mut clip = Clip::new(content.into(), "App".into(), "Win".into(), vec![], false);
+        clip.created_at = Utc::now() - Duration::days(age_days);
+        clip.last_copied_at = clip.created_at;
+        store.save_clip(clip).unwrap().id.unwrap()
+    }
+
+    fn contents(clips: Vec<Clip>) -> Vec<String> {
+        clips.into_iter().map(|c| c.content).collect()
+    }
+
+    #[test]
+    fn test_record_clip_use() {
+        let store = setup_test_store();
+        let id = save(&store, "snippet", 0);
+
+        store.record_clip_use(id).unwrap();
+        let clip = store.record_clip_use(id).unwrap();
+
+        assert_eq!(clip.use_count, 2);
+        assert!(clip.last_used_at.is_some());
+    }
+
+    #[test]
+    fn test_record_use_of_missing_clip_fails() {
+        let store = setup_test_store();
+        assert!(matches!(
+            store.record_clip_use(42),
+            Err(rusqlite::Error::QueryReturnedNoRows)
+        ));
+    }
+
+    #[test]
+    fn test_record_use_of_trashed_clip_fails() {
+        let store = setup_test_store();
+        let id = save(&store, "trashed", 0);
+        store.remove_clip(id).unwrap();
+
+        assert!(matches!(
+            store.record_clip_use(id),
+            Err(rusqlite::Error::QueryReturnedNoRows)
+        ));
+        assert!(matches!(
+            store.get_clip_content(id),
+            Err(rusqlite::Error::QueryReturnedNoRows)
+        ));
+
+        let clip = store.restore_clip(id).unwrap();
+        assert_eq!(clip.use_count, 0);
+        assert_eq!(store.get_clip_content(id).unwrap(), "trashed");
+    }
+
+    #[test]
+    fn test_frecency_ranks_reused_clips_first() {
+        let store = setup_test_store();
+        let snippet = save(&store, "reused snippet", 3);
+        save(&store, "one-off", 0);
+        for _ in 0..3 {
+            store.record_clip_use(snippet).unwrap();
+        }
+
+        let recent = store.list_clips(ClipOrder::Recent, 10).unwrap();
+        assert_eq!(contents(recent), vec!["one-off", "reused snippet"]);
+
+        let frecent = store.list_clips(ClipOrder::Frecency, 10).unwrap();
+        assert_eq!(contents(frecent), vec!["reused snippet", "one-off"]);
+    }
+
+    #[test]
+    fn test_frecency_decays_with_age() {
+        let store = setup_test_store();
+        save(&store, "stale", 200);
+        save(&store, "fresh", 1);
+        // Two uses, but the last one long ago
+        store
+            .write(|conn| {
+                conn.execute(
+                    "UPDATE clips SET use_count = 2, last_used_at = ?1 WHERE content = 'stale'",
+                    [(Utc::now() - Duration::days(100)).timestamp_millis()],
+                )
+            })
+            .unwrap();
+
+        let frecent = store.list_clips(ClipOrder::Frecency, 10).unwrap();
+        assert_eq!(contents(frecent), vec!["fresh", "stale"]);
+    }
+}
@@ -0,0 +1,264 @@
+// src-tauri/src/storage/worker.rs
+//! Connection management: one writer thread and a pool of read connections.
+//!
+//! SQLite in WAL mode lets readers proceed while a write is in progress, but only
+//! across separate connections. The store therefore owns:
+//!
+//! - A [`Writer`]: a dedicated thread that owns the only read-write connection and
+//!   runs write jobs sent to it over a channel, one at a time.
+//! - A [`ReadPool`]: a few read-only connections, each behind its own mutex.
+//!
+//! A long listing or search holds a pool connection, not the writer, so it never
+//! delays a capture; a large cleanup runs on the writer thread and never delays
+//! a listing.
+
+// ===== Imports =====
+
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Mutex, MutexGuard,
+    },
+    thread::{self, JoinHandle},
+};
+
+use rusqlite::{Connection, OpenFlags, Result as SqliteResult};
+use tracing::{error, warn};
+
+// ===== Domain Types =====
+
+/// Number of read-only connections in the pool.
+pub(super) const READ_POOL_SIZE: usize = 4;
+
+/// A unit of work run on the writer thread.
+type Job = Box<dyn FnOnce(&mut Connection) + Send>;
+
+/// Handle to the writer thread.
+///
+/// Dropping it closes the channel; the thread finishes queued jobs and exits.
+#[derive(Debug)]
+pub(super) struct Writer {
+    sender: Option<mpsc::Sender<Job>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// Read-only connections handed out round-robin.
+#[derive(Debug)]
+pub(super) struct ReadPool {
+    connections: Vec<Mutex<Connection>>,
+    next: AtomicUsize,
+}
+
+// ===== Public API Implementation =====
+
+impl Writer {
+    /// Starts the writer thread, which takes ownership of `conn`.
+    pub(super) fn spawn(mut conn: Connection) -> SqliteResult<Self> {
+        let (sender, receiver) = mpsc::channel::<Job>();
+
+        let thread = thread::Builder::new()
+            .name("clipcontex-db-writer".into())
+            .spawn(move || {
+                for job in receiver {
+                    // A panicking job must not take the writer down with it
+                    if catch_unwind(AssertUnwindSafe(|| job(&mut conn))).is_err() {
+                        error!("Database write job panicked");
+                    }
+                }
+            })
+            .map_err(|e| {
+                rusqlite::Error::ToSqlConversionFailure(
+                    format!("Failed to start the database writer: {}", e).into(),
+                )
+            })?;
+
+        Ok(Self {
+            sender: Some(sender),
+            thread: Some(thread),
+        })
+    }
+
+    /// Runs `job` on the writer thread and waits for its result.
+    ///
+    /// Jobs run in the order they are submitted. The job runs on another thread,
+    /// so it must own the data it uses.
+    ///
+    /// # Errors
+    ///
+    /// Returns the job's error, or an error if the writer thread has stopped.
+    pub(super) fn execute<T, F>(&self, job: F) -> SqliteResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> SqliteResult<T> + Send + 'static,
+    {
+        let (reply, result) = mpsc::sync_channel(1);
+        let job: Job = Box::new(move |conn| {
+            // The caller may have stopped waiting; nothing to do then
+            let _ = reply.send(job(conn));
+        });
+
+        self.sender
+            .as_ref()
+            .ok_or_else(writer_stopped)?
+            .send(job)
+            .map_err(|_| writer_stopped())?;
+
+        result.recv().map_err(|_| writer_stopped())?
+    }
+}
+
+impl Drop for Writer {
+    fn drop(&mut self) {
+        drop(self.sender.take());
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                warn!("Database writer thread panicked during shutdown");
+            }
+        }
+    }
+}
+
+impl ReadPool {
+    /// Opens `size` read-only connections to the database at `db_path`, which may
+    /// be a `file:` URI.
+    ///
+    /// `prepare` is run on each connection, e.g. to register SQL functions.
+    pub(super) fn open(
+        db_path: &Path,
+        size: usize,
+        prepare: impl Fn(&Connection) -> SqliteResult<()>,
+    ) -> SqliteResult<Self> {
+        let connections = (0..size)
+            .map(|_| {
+                let conn = Connection::open_with_flags(
+                    db_path,
+                    OpenFlags::SQLITE_OPEN_READ_ONLY
+                        | OpenFlags::SQLITE_OPEN_NO_MUTEX
+                        | OpenFlags::SQLITE_OPEN_URI,
+                )?;
+                prepare(&conn)?;
+                Ok(Mutex::new(conn))
+            })
+            .collect::<SqliteResult<Vec<_>>>()?;
+
+        Ok(Self {
+            connections,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// Acquires a read connection.
+    ///
+    /// Takes the first idle connection, starting from a rotating position; if all
+    /// are busy, waits for one. Poisoned connections are recovered, since reads
+    /// leave no state behind.
+    pub(super) fn get(&self) -> MutexGuard<'_, Connection> {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let size = self.connections.len();
+
+        for offset in 0..size {
+            let slot = &self.connections[(start + offset) % size];
+            match slot.try_lock() {
+                Ok(conn) => return conn,
+                Err(std::sync::TryLockError::Poisoned(e)) => return e.into_inner(),
+                Err(std::sync::TryLockError::WouldBlock) => continue,
+            }
+        }
+
+        self.connections[start % size]
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+// ===== Helper Functions =====
+
+/// Error returned once the writer thread is gone.
+fn writer_stopped() -> rusqlite::Error {
+    rusqlite::Error::ToSqlConversionFailure("The database writer has stopped".into())
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Barrier};
+    use tempfile::tempdir;
+
+    fn open_pair() -> (tempfile::TempDir, Writer, ReadPool) {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch("PRAGMA journal_mode=WAL; CREATE TABLE items (value INTEGER NOT NULL);")
+            .unwrap();
+
+        let writer = Writer::spawn(conn).unwrap();
+        let pool = ReadPool::open(&path, 2, |_| Ok(())).unwrap();
+        (dir, writer, pool)
+    }
+
+    #[test]
+    fn test_writes_are_visible_to_readers() {
+        let (_dir, writer, pool) = open_pair();
+
+        let inserted = writer
+            .execute(|conn| conn.execute("INSERT INTO items VALUES (1), (2)", []))
+            .unwrap();
+
+        let count: i64 = pool
+            .get()
+            .query_row("SELECT COUNT(*) FROM items", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!((inserted, count), (2, 2));
+    }
+
+    #[test]
+    fn test_read_connections_reject_writes() {
+        let (_dir, _writer, pool) = open_pair();
+
+        let result = pool.get().execute("INSERT INTO items VALUES (1)", []);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_writer_survives_a_panicking_job() {
+        let (_dir, writer, _pool) = open_pair();
+
+        let panicked = writer.execute::<(), _>(|_| panic!("job failed"));
+        let after = writer.execute(|conn| conn.execute("INSERT INTO items VALUES (1)", []));
+
+        assert!(panicked.is_err());
+        assert_eq!(after.unwrap(), 1);
+    }
+
+    #[test]
+    fn test_writes_proceed_while_a_read_transaction_is_open() {
+        let (_dir, writer, pool) = open_pair();
+        let writer = Arc::new(writer);
+        let pool = Arc::new(pool);
+        let barrier = Arc::new(Barrier::new(2));
+
+        let reader = {
+            let (pool, barrier) = (pool.clone(), barrier.clone());
+            thread::spawn(move || {
+                let conn = pool.get();
+                conn.execute_batch("BEGIN; SELECT COUNT(*) FROM items;")
+                    .unwrap();
+                barrier.wait();
+                barrier.wait();
+                conn.execute_batch("COMMIT").unwrap();
+            })
+        };
+
+        barrier.wait();
+        let written = writer.execute(|conn| conn.execute("INSERT INTO items VALUES (1)", []));
+        barrier.wait();
+        reader.join().unwrap();
+
+        assert_eq!(written.unwrap(), 1);
+    }
+}
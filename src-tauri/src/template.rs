@@ -0,0 +1,423 @@
+// src-tauri/src/template.rs
+//! Placeholder expansion for snippet clips.
+//!
+//! Snippets are clips whose content is a template, expanded each time the snippet
+//! is pasted from the quick picker:
+//!
+//! ```text
+//! Hi {input:Name}, sent from {app} on {date:%A, %d %B}.
+//! ```
+//!
+//! Supported placeholders:
+//! - `{date}` / `{date:<format>}`: The current local date, formatted with a
+//!   `strftime`-style format (default `%Y-%m-%d`).
+//! - `{clipboard}`: The text currently on the clipboard.
+//! - `{input:<label>}`: A value the user types in when pasting; the same label
+//!   always expands to the same value.
+//! - `{uuid}`: A fresh random (version 4) UUID.
+//! - `{app}`: The name of the app the snippet is pasted into.
+//!
+//! Literal braces are written `{{` and `}}`. A [`Template`] is validated when it
+//! is parsed, so a snippet that parses always expands once its inputs are given.
+
+// ===== Imports =====
+
+use std::{
+    collections::HashMap,
+    fmt::{Display, Write},
+};
+
+use chrono::{
+    format::{Item, StrftimeItems},
+    DateTime, Local,
+};
+use uuid::Uuid;
+
+// ===== Constants =====
+
+/// Format used by `{date}` when none is given.
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+// ===== Domain Types =====
+
+/// A parsed snippet template.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+/// A placeholder in a [`Template`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Placeholder {
+    /// `{date}` or `{date:<format>}`, holding the format.
+    Date(String),
+    /// `{clipboard}`.
+    Clipboard,
+    /// `{input:<label>}`, holding the trimmed label.
+    Input(String),
+    /// `{uuid}`.
+    Uuid,
+    /// `{app}`.
+    App,
+}
+
+/// Everything a [`Template`] needs to be expanded.
+#[derive(Debug, Clone)]
+pub struct TemplateContext {
+    /// The current local time, used by `{date}`.
+    pub now: DateTime<Local>,
+    /// The current clipboard text, used by `{clipboard}`.
+    pub clipboard: String,
+    /// The app being pasted into, used by `{app}`.
+    pub app: String,
+    /// Values typed in by the user for `{input:<label>}`, keyed by label.
+    pub inputs: HashMap<String, String>,
+}
+
+/// An error produced while parsing or expanding a template.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateError {
+    /// A `{` at the given byte offset has no matching `}`.
+    UnclosedPlaceholder { position: usize },
+    /// A `}` at the given byte offset closes no placeholder (write `}}` for a literal brace).
+    UnmatchedBrace { position: usize },
+    /// The placeholder at the given byte offset is not one of the supported ones.
+    UnknownPlaceholder { name: String, position: usize },
+    /// A placeholder that needs an argument was given none (e.g., `{input}`).
+    MissingArgument { name: String, position: usize },
+    /// A placeholder that takes no argument was given one (e.g., `{uuid:4}`).
+    UnexpectedArgument { name: String, position: usize },
+    /// A `{date:...}` format could not be understood.
+    InvalidDateFormat { format: String, position: usize },
+    /// No value was given for an `{input:<label>}` placeholder.
+    MissingInput { label: String },
+}
+
+/// A piece of a template: literal text or a placeholder and its byte offset.
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Text(String),
+    Placeholder(Placeholder, usize),
+}
+
+// ===== Public API Implementation =====
+
+impl Template {
+    /// Parses and validates a template.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TemplateError`] locating the first malformed brace or placeholder.
+    pub fn parse(source: &str) -> Result<Self, TemplateError> {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut chars = source.char_indices().peekable();
+
+        while let Some((position, c)) = chars.next() {
+            match c {
+                '{' if chars.next_if(|&(_, next)| next == '{').is_some() => text.push('{'),
+                '}' if chars.next_if(|&(_, next)| next == '}').is_some() => text.push('}'),
+                '}' => return Err(TemplateError::UnmatchedBrace { position }),
+                '{' => {
+                    let mut body = String::new();
+                    loop {
+                        match chars.next() {
+                            Some((_, '}')) => break,
+                            Some((_, '{')) | None => {
+                                return Err(TemplateError::UnclosedPlaceholder { position })
+                            }
+                            Some((_, c)) => body.push(c),
+                        }
+                    }
+
+                    if !text.is_empty() {
+                        segments.push(Segment::Text(std::mem::take(&mut text)));
+                    }
+                    let placeholder = Placeholder::parse(&body, position)?;
+                    segments.push(Segment::Placeholder(placeholder, position));
+                }
+                c => text.push(c),
+            }
+        }
+
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+        Ok(Self { segments })
+    }
+
+    /// Returns the placeholders of the template, in order.
+    pub fn placeholders(&self) -> impl Iterator<Item = &Placeholder> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Placeholder(placeholder, _) => Some(placeholder),
+            Segment::Text(_) => None,
+        })
+    }
+
+    /// Returns the labels of the `{input:<label>}` placeholders, without
+    /// duplicates, in order of first appearance.
+    pub fn inputs(&self) -> Vec<&str> {
+        let mut labels: Vec<&str> = Vec::new();
+        for placeholder in self.placeholders() {
+            if let Placeholder::Input(label) = placeholder {
+                if !labels.contains(&label.as_str()) {
+                    labels.push(label);
+                }
+            }
+        }
+        labels
+    }
+
+    /// Expands every placeholder and returns the resulting text.
+    ///
+    /// Each `{uuid}` expands to a different UUID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TemplateError::MissingInput`] if `context` has no value for an
+    /// `{input:<label>}` placeholder.
+    pub fn expand(&self, context: &TemplateContext) -> Result<String, TemplateError> {
+        let mut output = String::new();
+
+        for segment in &self.segments {
+            let (placeholder, position) = match segment {
+                Segment::Text(text) => {
+                    output.push_str(text);
+                    continue;
+                }
+                Segment::Placeholder(placeholder, position) => (placeholder, *position),
+            };
+
+            match placeholder {
+                Placeholder::Date(format) => {
+                    write!(output, "{}", context.now.format(format)).map_err(|_| {
+                        TemplateError::InvalidDateFormat {
+                            format: format.clone(),
+                            position,
+                        }
+                    })?;
+                }
+                Placeholder::Clipboard => output.push_str(&context.clipboard),
+                Placeholder::Input(label) => {
+                    let value =
+                        context
+                            .inputs
+                            .get(label)
+                            .ok_or_else(|| TemplateError::MissingInput {
+                                label: label.clone(),
+                            })?;
+                    output.push_str(value);
+                }
+                Placeholder::Uuid => output.push_str(&random_uuid()),
+                Placeholder::App => output.push_str(&context.app),
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+impl Placeholder {
+    /// Parses the text between the braces of the placeholder starting at `position`.
+    fn parse(body: &str, position: usize) -> Result<Self, TemplateError> {
+        let (name, argument) = match body.split_once(':') {
+            Some((name, argument)) => (name.trim(), Some(argument)),
+            None => (body.trim(), None),
+        };
+
+        match (name, argument) {
+            ("date", None) => Ok(Placeholder::Date(DEFAULT_DATE_FORMAT.to_string())),
+            ("date", Some(format)) => {
+                let is_valid = !format.is_empty()
+                    && !StrftimeItems::new(format).any(|item| matches!(item, Item::Error));
+                if !is_valid {
+                    return Err(TemplateError::InvalidDateFormat {
+                        format: format.to_string(),
+                        position,
+                    });
+                }
+                Ok(Placeholder::Date(format.to_string()))
+            }
+            ("input", Some(label)) if !label.trim().is_empty() => {
+                Ok(Placeholder::Input(label.trim().to_string()))
+            }
+            ("input", _) => Err(TemplateError::MissingArgument {
+                name: name.to_string(),
+                position,
+            }),
+            ("clipboard" | "uuid" | "app", Some(_)) => Err(TemplateError::UnexpectedArgument {
+                name: name.to_string(),
+                position,
+            }),
+            ("clipboard", None) => Ok(Placeholder::Clipboard),
+            ("uuid", None) => Ok(Placeholder::Uuid),
+            ("app", None) => Ok(Placeholder::App),
+            _ => Err(TemplateError::UnknownPlaceholder {
+                name: name.to_string(),
+                position,
+            }),
+        }
+    }
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::UnclosedPlaceholder { position } => {
+                write!(f, "Unclosed placeholder starting at position {}", position)
+            }
+            TemplateError::UnmatchedBrace { position } => write!(
+                f,
+                "Unmatched '}}' at position {} (write '}}}}' for a literal brace)",
+                position
+            ),
+            TemplateError::UnknownPlaceholder { name, position } => {
+                write!(
+                    f,
+                    "Unknown placeholder '{{{}}}' at position {}",
+                    name, position
+                )
+            }
+            TemplateError::MissingArgument { name, position } => write!(
+                f,
+                "Placeholder '{{{}}}' at position {} needs a value after ':'",
+                name, position
+            ),
+            TemplateError::UnexpectedArgument { name, position } => write!(
+                f,
+                "Placeholder '{{{}}}' at position {} takes no value",
+                name, position
+            ),
+            TemplateError::InvalidDateFormat { format, position } => {
+                write!(
+                    f,
+                    "Invalid date format '{}' at position {}",
+                    format, position
+                )
+            }
+            TemplateError::MissingInput { label } => write!(f, "No value given for '{}'", label),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+// ===== Helper Functions =====
+
+/// Generates a random version 4 UUID in its hyphenated form.
+fn random_uuid() -> String {
+    Uuid::new_v4().to_string()
+}
+
+// ===== Tests =====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn context() -> TemplateContext {
+        TemplateContext {
+            now: Local.with_ymd_and_hms(2024, 3, 5, 14, 30, 0).unwrap(),
+            clipboard: "copied text".into(),
+            app: "Mail".into(),
+            inputs: HashMap::from([("Name".to_string(), "Ada".to_string())]),
+        }
+    }
+
+    fn expand(source: &str) -> String {
+        Template::parse(source).unwrap().expand(&context()).unwrap()
+    }
+
+    #[test]
+    fn test_expands_every_placeholder() {
+        assert_eq!(
+            expand("Hi {input:Name}, from {app}: {clipboard}"),
+            "Hi Ada, from Mail: copied text"
+        );
+        assert_eq!(expand("{date}"), "2024-03-05");
+        assert_eq!(expand("{date:%d/%m %H:%M}"), "05/03 14:30");
+        assert_eq!(expand("no placeholders"), "no placeholders");
+    }
+
+    #[test]
+    fn test_doubled_braces_are_literal() {
+        assert_eq!(expand("fn main() {{ {app} }}"), "fn main() { Mail }");
+        assert_eq!(expand("{{uuid}}"), "{uuid}");
+    }
+
+    #[test]
+    fn test_uuids_are_random_v4() {
+        let template = Template::parse("{uuid} {uuid}").unwrap();
+        let expanded = template.expand(&context()).unwrap();
+        let (a, b) = expanded.split_once(' ').unwrap();
+
+        assert_ne!(a, b);
+        for uuid in [a, b] {
+            let groups: Vec<usize> = uuid.split('-').map(str::len).collect();
+            assert_eq!(groups, vec![8, 4, 4, 4, 12]);
+            assert_eq!(&uuid[14..15], "4");
+            assert!("89ab".contains(&uuid[19..20]));
+        }
+    }
+
+    #[test]
+    fn test_inputs_are_listed_once_in_order() {
+        let template = Template::parse("{input:B} {input: A } {input:B} {app}").unwrap();
+        assert_eq!(template.inputs(), vec!["B", "A"]);
+
+        let err = template.expand(&context()).unwrap_err();
+        assert_eq!(err, TemplateError::MissingInput { label: "B".into() });
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let cases = [
+            (
+                "Hi {app",
+                TemplateError::UnclosedPlaceholder { position: 3 },
+            ),
+            ("{a{pp}", TemplateError::UnclosedPlaceholder { position: 0 }),
+            ("x } y", TemplateError::UnmatchedBrace { position: 2 }),
+            (
+                "{name}",
+                TemplateError::UnknownPlaceholder {
+                    name: "name".into(),
+                    position: 0,
+                },
+            ),
+            (
+                "{input: }",
+                TemplateError::MissingArgument {
+                    name: "input".into(),
+                    position: 0,
+                },
+            ),
+            (
+                "{uuid:4}",
+                TemplateError::UnexpectedArgument {
+                    name: "uuid".into(),
+                    position: 0,
+                },
+            ),
+            (
+                "{date:%Q}",
+                TemplateError::InvalidDateFormat {
+                    format: "%Q".into(),
+                    position: 0,
+                },
+            ),
+            (
+                "{date:}",
+                TemplateError::InvalidDateFormat {
+                    format: "".into(),
+                    position: 0,
+                },
+            ),
+        ];
+
+        for (source, expected) in cases {
+            assert_eq!(Template::parse(source), Err(expected), "{source}");
+        }
+    }
+}
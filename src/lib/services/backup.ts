@@ -0,0 +1,17 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { BackupInfo } from "$lib/stores/types";
+
+// Lists database backups, newest first
+export async function listBackups(): Promise<BackupInfo[]> {
+  return await invoke<BackupInfo[]>("list_backups");
+}
+
+// Backs up the database now
+export async function createBackup(): Promise<BackupInfo> {
+  return await invoke<BackupInfo>("create_backup");
+}
+
+// Replaces the database with a backup; returns the backup taken of the replaced database
+export async function restoreBackup(fileName: string): Promise<BackupInfo> {
+  return await invoke<BackupInfo>("restore_backup", { fileName });
+}
@@ -2,16 +2,36 @@
 import { writable, derived, get } from "svelte/store";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import type { Clip } from "$lib/stores/types";
+import type {
+  Clip,
+  ClipCursor,
+  ClipOrder,
+  ClipPage,
+  ClipRevision,
+  ClipSearchResult,
+  ClipSelection,
+} from "$lib/stores/types";
 
 const EVT_CLIP_ADDED: string = "clip-added";
+const EVT_CLIP_BUMPED: string = "clip-bumped";
 const EVT_CLIP_UPDATED: string = "clip-updated";
 const EVT_CLIP_DELETED: string = "clip-deleted";
+const EVT_CLIPS_DELETED: string = "clips-deleted";
+const EVT_CLIPS_UPDATED: string = "clips-updated";
 const EVT_HISTORY_CLEARED: string = "history-cleared";
+const EVT_CLIPS_RESTORED: string = "clips-restored";
+const EVT_HISTORY_IMPORTED: string = "history-imported";
+const EVT_HISTORY_RESTORED: string = "history-restored";
 
 // --- Core store: single source of truth ---
 export const allClipsStore = writable<Clip[]>([]);
 
+// --- Pagination state for the main window ---
+const PAGE_SIZE = 100;
+export const nextCursor = writable<ClipCursor | null>(null);
+export const totalClips = writable(0);
+export const hasMoreClips = derived(nextCursor, (cursor) => cursor !== null);
+
 // Tauri Event Initialization
 let eventInitialized: boolean = false;
 
@@ -22,24 +42,62 @@ export async function initClipEvents() {
   await listen<Clip>(EVT_CLIP_ADDED, (e) => {
     allClipsStore.update((clips) => {
       if (clips.some((c) => c.id === e.payload.id)) return clips;
+      totalClips.update((n) => n + 1);
       return [e.payload, ...clips];
     });
   });
 
+  await listen<Clip>(EVT_CLIP_BUMPED, (e) => {
+    allClipsStore.update((clips) => [
+      e.payload,
+      ...clips.filter((c) => c.id !== e.payload.id),
+    ]);
+  });
+
   await listen<number>(EVT_CLIP_DELETED, (e) => {
     allClipsStore.update((clips) => clips.filter((c) => c.id !== e.payload));
+    totalClips.update((n) => Math.max(0, n - 1));
   });
 
-  await listen<number, boolean>(EVT_CLIP_UPDATED, (e) => {
-    const [id, isPinned] = e.payload;
-
+  await listen<Clip>(EVT_CLIP_UPDATED, (e) => {
     allClipsStore.update((clips) =>
-      clips.map((c) => (c.id === id ? { ...c, is_pinned: isPinned } : c)),
+      clips.map((c) => (c.id === e.payload.id ? e.payload : c)),
     );
   });
 
+  await listen<number[]>(EVT_CLIPS_DELETED, (e) => {
+    const removed = new Set(e.payload);
+    allClipsStore.update((clips) => clips.filter((c) => !removed.has(c.id)));
+    totalClips.update((n) => Math.max(0, n - removed.size));
+  });
+
+  // Bulk updates only list the affected IDs, so reload if any of them is shown
+  await listen<number[]>(EVT_CLIPS_UPDATED, (e) => {
+    const updated = new Set(e.payload);
+    if (get(allClipsStore).some((c) => updated.has(c.id))) {
+      loadFirstPage();
+    }
+  });
+
   await listen(EVT_HISTORY_CLEARED, () => {
     allClipsStore.set([]);
+    nextCursor.set(null);
+    totalClips.set(0);
+  });
+
+  // Restored clips may belong anywhere in the history, so reload from the top
+  await listen<Clip[]>(EVT_CLIPS_RESTORED, () => {
+    loadFirstPage();
+  });
+
+  // Imported clips keep their original timestamps, so the same applies
+  await listen(EVT_HISTORY_IMPORTED, () => {
+    loadFirstPage();
+  });
+
+  // A restored backup replaces the whole history
+  await listen<string>(EVT_HISTORY_RESTORED, () => {
+    loadFirstPage();
   });
 }
 
@@ -63,27 +121,179 @@ async function safeInvoke<T = any>(
 }
 
 // Public API
-export async function loadClips(limit = 200) {
-  const loaded = await safeInvoke<Clip[]>("list_recent_clips", { limit });
+export async function loadClips(limit = 200, order: ClipOrder = "recent") {
+  const loaded = await safeInvoke<Clip[]>("list_recent_clips", {
+    limit,
+    order,
+  });
   allClipsStore.set(loaded);
 }
 
+// Loads the first page of history, replacing anything loaded before
+export async function loadFirstPage(pageSize = PAGE_SIZE) {
+  const page = await safeInvoke<ClipPage>("list_clips_page", { pageSize });
+  allClipsStore.set(page.clips ?? []);
+  nextCursor.set(page.next_cursor ?? null);
+  totalClips.set(page.total ?? 0);
+}
+
+// Appends the next page of history, if any
+let loadingMore = false;
+export async function loadMoreClips(pageSize = PAGE_SIZE) {
+  const cursor = get(nextCursor);
+  if (!cursor || loadingMore) return;
+
+  loadingMore = true;
+  try {
+    const page = await safeInvoke<ClipPage>("list_clips_page", {
+      cursor,
+      pageSize,
+    });
+    allClipsStore.update((clips) => {
+      const seen = new Set(clips.map((c) => c.id));
+      return [...clips, ...(page.clips ?? []).filter((c) => !seen.has(c.id))];
+    });
+    nextCursor.set(page.next_cursor ?? null);
+    totalClips.set(page.total ?? get(totalClips));
+  } finally {
+    loadingMore = false;
+  }
+}
+
+export async function searchClips(query: string, limit = 50) {
+  return await safeInvoke<ClipSearchResult[]>("search_clips", { query, limit });
+}
+
 export async function togglePin(id: number, isPinned: boolean) {
   await safeInvoke("toggle_pin_status", { id, isPinned });
 }
 
+// Saves the previous content as a revision and regenerates auto tags
+export async function updateClipContent(id: number, content: string) {
+  await safeInvoke("update_clip_content", { id, content });
+}
+
+// Most recently replaced first
+export async function listClipRevisions(id: number): Promise<ClipRevision[]> {
+  return await safeInvoke<ClipRevision[]>("list_clip_revisions", { id });
+}
+
+export async function revertClipRevision(revisionId: number) {
+  await safeInvoke("revert_clip_revision", { revisionId });
+}
+
+// User tags are kept apart from auto tags; only they can be removed
+export async function addClipTag(id: number, tag: string) {
+  await safeInvoke("add_clip_tag", { id, tag });
+}
+
+export async function removeClipTag(id: number, tag: string) {
+  await safeInvoke("remove_clip_tag", { id, tag });
+}
+
+// A null or blank note clears it
+export async function setClipNote(id: number, note: string | null) {
+  await safeInvoke("set_clip_note", { id, note });
+}
+
 export async function deleteClip(id: number) {
   await safeInvoke("remove_clip", { id });
 }
 
+// --- Bulk operations ---
+// Each runs in one transaction and resolves to the IDs of the affected clips
+export async function deleteClips(selection: ClipSelection) {
+  return await safeInvoke<number[]>("remove_clips", selection);
+}
+
+export async function setClipsPinned(
+  selection: ClipSelection,
+  isPinned: boolean,
+) {
+  return await safeInvoke<number[]>("set_clips_pinned", {
+    ...selection,
+    isPinned,
+  });
+}
+
+export async function addClipsTag(selection: ClipSelection, tag: string) {
+  return await safeInvoke<number[]>("add_clips_tag", { ...selection, tag });
+}
+
 export async function clearAllClips() {
   await safeInvoke("clear_clip_history");
 }
 
+// --- Trash ---
+export async function listTrash(limit = 200) {
+  return await safeInvoke<Clip[]>("list_trash", { limit });
+}
+
+export async function restoreClip(id: number) {
+  await safeInvoke("restore_clip", { id });
+}
+
+export async function restoreAllClips() {
+  await safeInvoke("restore_all");
+}
+
+// Reverses the most recent delete or clear; resolves to the restored clips
+export async function undoDelete() {
+  return await safeInvoke<Clip[]>("undo_delete");
+}
+
+export async function emptyTrash() {
+  await safeInvoke("empty_trash");
+}
+
 export async function ignorePasting(content: string) {
   await safeInvoke("ignore_next_clip", { content });
 }
 
+// Returns the full body; listed clips only carry a preview when is_truncated is set
+export async function getClipContent(clip: Clip): Promise<string> {
+  if (!clip.is_truncated) return clip.content;
+  return await invoke<string>("get_clip_content", { id: clip.id });
+}
+
+// Records a paste (for frecency ranking) and ignores the resulting clipboard update
+export async function useClip(id: number) {
+  await safeInvoke("use_clip", { id });
+}
+
+// --- Self-destructing clips ---
+// Expired clips are deleted for good (not trashed) and reported via clip-deleted
+export async function setClipExpiry(
+  id: number,
+  ttlSeconds: number | null,
+  deleteAfterUse: boolean,
+) {
+  await safeInvoke("set_clip_expiry", { id, ttlSeconds, deleteAfterUse });
+}
+
+// --- Snippets ---
+// Only clips whose content is a valid template can become snippets
+export async function setSnippet(id: number, isSnippet: boolean) {
+  await safeInvoke("set_snippet", { id, isSnippet });
+}
+
+// Rejects with the first syntax error; resolves to the {input:…} labels otherwise
+export async function validateSnippet(content: string): Promise<string[]> {
+  return await invoke<string[]>("validate_snippet", { content });
+}
+
+export async function snippetInputs(id: number): Promise<string[]> {
+  return await invoke<string[]>("snippet_inputs", { id });
+}
+
+// Expands placeholders, records the paste and ignores the resulting clipboard update
+export async function useSnippet(
+  id: number,
+  inputs: Record<string, string> = {},
+): Promise<string> {
+  return await invoke<string>("use_snippet", { id, inputs });
+}
+
 // Derived UI stores
 export const searchTerm = writable("");
 const normalizedQuery = derived(searchTerm, (term) =>
@@ -100,7 +310,7 @@ const filteredClips = derived(
       const content = c.content?.toLowerCase() ?? "";
       const app = c.app_name?.toLowerCase() ?? "";
       const title = c.window_title?.toLowerCase() ?? "";
-      const auto = c.auto_tags?.toLowerCase() ?? "";
+      const auto = c.tags?.join(" ").toLowerCase() ?? "";
       return (
         content.includes(q) ||
         app.includes(q) ||
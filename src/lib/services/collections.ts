@@ -0,0 +1,122 @@
+import { writable } from "svelte/store";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import type { Clip, ClipSelection, Collection } from "$lib/stores/types";
+
+const EVT_COLLECTION_UPDATED: string = "collection-updated";
+const EVT_COLLECTION_DELETED: string = "collection-deleted";
+const EVT_COLLECTION_MEMBERSHIP_CHANGED: string =
+  "collection-membership-changed";
+const EVT_COLLECTION_CLIPS_ADDED: string = "collection-clips-added";
+
+export const collections = writable<Collection[]>([]);
+
+let eventInitialized: boolean = false;
+
+function byName(a: Collection, b: Collection) {
+  return a.name.localeCompare(b.name, undefined, { sensitivity: "base" });
+}
+
+export async function initCollectionEvents() {
+  if (eventInitialized) return;
+  eventInitialized = true;
+
+  await listen<Collection>(EVT_COLLECTION_UPDATED, (e) => {
+    collections.update((list) =>
+      [...list.filter((c) => c.id !== e.payload.id), e.payload].sort(byName),
+    );
+  });
+
+  await listen<number>(EVT_COLLECTION_DELETED, (e) => {
+    collections.update((list) => list.filter((c) => c.id !== e.payload));
+  });
+}
+
+// Calls back whenever a clip joins or leaves a collection
+export async function onCollectionMembershipChanged(
+  callback: (collectionId: number, clipId: number, isMember: boolean) => void,
+) {
+  return await listen<[number, number, boolean]>(
+    EVT_COLLECTION_MEMBERSHIP_CHANGED,
+    (e) => callback(...e.payload),
+  );
+}
+
+// Calls back once per bulk add, with only the clips that were newly added
+export async function onCollectionClipsAdded(
+  callback: (collectionId: number, clipIds: number[]) => void,
+) {
+  return await listen<[number, number[]]>(EVT_COLLECTION_CLIPS_ADDED, (e) =>
+    callback(...e.payload),
+  );
+}
+
+export async function loadCollections() {
+  collections.set(await invoke<Collection[]>("list_collections"));
+}
+
+// Rejects with an error message if the name is blank or already taken
+export async function createCollection(name: string): Promise<Collection> {
+  return await invoke<Collection>("create_collection", { name });
+}
+
+export async function renameCollection(
+  id: number,
+  name: string,
+): Promise<Collection> {
+  return await invoke<Collection>("rename_collection", { id, name });
+}
+
+// Deletes the collection only; its clips stay in the history
+export async function deleteCollection(id: number) {
+  await invoke("delete_collection", { id });
+}
+
+export async function addClipToCollection(
+  collectionId: number,
+  clipId: number,
+): Promise<Collection> {
+  return await invoke<Collection>("add_clip_to_collection", {
+    collectionId,
+    clipId,
+  });
+}
+
+// Appends in one transaction; clips already in the collection keep their place
+export async function addClipsToCollection(
+  collectionId: number,
+  selection: ClipSelection,
+): Promise<Collection> {
+  return await invoke<Collection>("add_clips_to_collection", {
+    collectionId,
+    ...selection,
+  });
+}
+
+export async function removeClipFromCollection(
+  collectionId: number,
+  clipId: number,
+): Promise<Collection> {
+  return await invoke<Collection>("remove_clip_from_collection", {
+    collectionId,
+    clipId,
+  });
+}
+
+export async function moveClipInCollection(
+  collectionId: number,
+  clipId: number,
+  index: number,
+): Promise<Collection> {
+  return await invoke<Collection>("move_clip_in_collection", {
+    collectionId,
+    clipId,
+    index,
+  });
+}
+
+export async function loadCollectionClips(
+  collectionId: number,
+): Promise<Clip[]> {
+  return await invoke<Clip[]>("list_collection_clips", { collectionId });
+}
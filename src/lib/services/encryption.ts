@@ -0,0 +1,44 @@
+import { writable } from "svelte/store";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import type { EncryptionStatus } from "$lib/stores/types";
+
+const EVT_LOCK_STATE_CHANGED: string = "lock-state-changed";
+
+export const encryptionStatus = writable<EncryptionStatus>({
+  enabled: false,
+  locked: false,
+});
+
+let eventInitialized: boolean = false;
+
+export async function initEncryptionEvents() {
+  if (eventInitialized) return;
+  eventInitialized = true;
+
+  await listen<EncryptionStatus>(EVT_LOCK_STATE_CHANGED, (e) => {
+    encryptionStatus.set(e.payload);
+  });
+}
+
+export async function loadEncryptionStatus() {
+  encryptionStatus.set(await invoke<EncryptionStatus>("encryption_status"));
+}
+
+// Encrypts existing clips in place; the passphrase cannot be recovered
+export async function enableEncryption(passphrase: string) {
+  encryptionStatus.set(
+    await invoke<EncryptionStatus>("enable_encryption", { passphrase }),
+  );
+}
+
+// Rejects with an error message if the passphrase is wrong
+export async function unlockHistory(passphrase: string) {
+  encryptionStatus.set(
+    await invoke<EncryptionStatus>("unlock_history", { passphrase }),
+  );
+}
+
+export async function lockHistory() {
+  encryptionStatus.set(await invoke<EncryptionStatus>("lock_history"));
+}
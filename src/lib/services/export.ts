@@ -0,0 +1,15 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { ExportFormat, ExportSummary } from "$lib/stores/types";
+
+// Writes the clips matching `query` (all clips if empty) to `path`
+export async function exportClips(
+  path: string,
+  format: ExportFormat,
+  query = "",
+): Promise<ExportSummary> {
+  return await invoke<ExportSummary>("export_clips", {
+    path,
+    format,
+    query: query.trim() || null,
+  });
+}
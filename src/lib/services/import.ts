@@ -0,0 +1,13 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { ImportReport, ImportSource } from "$lib/stores/types";
+
+// Imports another clipboard manager's history from `path`, or from its default location if empty
+export async function importHistory(
+  source: ImportSource,
+  path = "",
+): Promise<ImportReport> {
+  return await invoke<ImportReport>("import_history", {
+    source,
+    path: path.trim() || null,
+  });
+}
@@ -7,6 +7,16 @@ import { showToast } from "$lib/utils/toast";
 const DEFAULT_SETTINGS: AppSettings = {
   autoCleanDays: 30,
   maxHistorySize: 200,
+  pinnedAutoCleanDays: 0,
+  pinnedMaxHistorySize: 0,
+  retentionRules: [],
+  trashRetentionDays: 30,
+  backupIntervalHours: 24,
+  backupKeepCount: 7,
+  compressBackups: true,
+  isDedupeEnabled: true,
+  expiryRules: [],
+  lockedCapture: "queue",
   ignoredApps: ["Bitwarden", "1Password"],
   isNewUser: true,
   isAutostartEnabled: true,
@@ -38,6 +48,23 @@ export async function loadSettings(): Promise<AppSettings> {
     return {
       autoCleanDays: config.autoCleanDays ?? DEFAULT_SETTINGS.autoCleanDays,
       maxHistorySize: config.maxHistorySize ?? DEFAULT_SETTINGS.maxHistorySize,
+      pinnedAutoCleanDays:
+        config.pinnedAutoCleanDays ?? DEFAULT_SETTINGS.pinnedAutoCleanDays,
+      pinnedMaxHistorySize:
+        config.pinnedMaxHistorySize ?? DEFAULT_SETTINGS.pinnedMaxHistorySize,
+      retentionRules: config.retentionRules ?? DEFAULT_SETTINGS.retentionRules,
+      trashRetentionDays:
+        config.trashRetentionDays ?? DEFAULT_SETTINGS.trashRetentionDays,
+      backupIntervalHours:
+        config.backupIntervalHours ?? DEFAULT_SETTINGS.backupIntervalHours,
+      backupKeepCount:
+        config.backupKeepCount ?? DEFAULT_SETTINGS.backupKeepCount,
+      compressBackups:
+        config.compressBackups ?? DEFAULT_SETTINGS.compressBackups,
+      isDedupeEnabled:
+        config.isDedupeEnabled ?? DEFAULT_SETTINGS.isDedupeEnabled,
+      expiryRules: config.expiryRules ?? DEFAULT_SETTINGS.expiryRules,
+      lockedCapture: config.lockedCapture ?? DEFAULT_SETTINGS.lockedCapture,
       ignoredApps,
       isNewUser: config.isNewUser ?? DEFAULT_SETTINGS.isNewUser,
       isAutostartEnabled:
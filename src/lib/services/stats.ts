@@ -0,0 +1,15 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { ClipStats, StatsInterval } from "$lib/stores/types";
+
+// Usage statistics for clips created in [from, to); open-ended where omitted
+export async function loadClipStats(
+  from: Date | null = null,
+  to: Date | null = null,
+  interval: StatsInterval = "day",
+): Promise<ClipStats> {
+  return await invoke<ClipStats>("clip_stats", {
+    from: from?.toISOString() ?? null,
+    to: to?.toISOString() ?? null,
+    interval,
+  });
+}
@@ -0,0 +1,24 @@
+import { writable } from "svelte/store";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import type { StorageStatus } from "$lib/stores/types";
+
+const EVT_STORAGE_DEGRADED: string = "storage-degraded";
+
+export const storageStatus = writable<StorageStatus>({ state: "healthy" });
+
+let eventInitialized: boolean = false;
+
+export async function initStorageEvents() {
+  if (eventInitialized) return;
+  eventInitialized = true;
+
+  await listen<StorageStatus>(EVT_STORAGE_DEGRADED, (e) => {
+    storageStatus.set(e.payload);
+  });
+}
+
+// The event fires once at startup, possibly before this window listens
+export async function loadStorageStatus() {
+  storageStatus.set(await invoke<StorageStatus>("storage_status"));
+}
@@ -5,9 +5,37 @@ export type ShortcutConfig = {
 
 export type ToastStatus = 'error' | 'success';
 
+export interface RetentionRule {
+  name: string;
+  appName?: string | null;
+  tag?: string | null;
+  minContentBytes?: number | null;
+  maxAgeDays?: number | null;
+  maxCount?: number | null;
+}
+
+// Makes clips copied from an app expire and/or self-destruct once pasted
+export interface ExpiryRule {
+  appName: string;
+  ttlSeconds?: number | null;
+  deleteAfterUse?: boolean;
+}
+
+export type LockedCapture = "queue" | "pause";
+
 export interface AppSettings {
   autoCleanDays: number;
   maxHistorySize: number;
+  pinnedAutoCleanDays: number;
+  pinnedMaxHistorySize: number;
+  retentionRules: RetentionRule[];
+  trashRetentionDays: number;
+  backupIntervalHours: number;
+  backupKeepCount: number;
+  compressBackups: boolean;
+  isDedupeEnabled: boolean;
+  expiryRules: ExpiryRule[];
+  lockedCapture: LockedCapture;
   ignoredApps: string[];
   isNewUser: boolean;
   isAutostartEnabled: boolean;
@@ -19,8 +47,137 @@ export interface Clip {
   window_title: string;
   app_name: string;
   content: string;
-  auto_tags: string;
+  byte_len: number;
+  line_count: number;
+  is_truncated: boolean;
+  tags: string[];
+  user_tags: string[];
+  note: string | null;
   is_pinned: boolean;
+  is_snippet: boolean;
+  expires_at: string | null;
+  delete_after_use: boolean;
   created_at: string;
   updated_at: string;
+  copy_count: number;
+  last_copied_at: string;
+  use_count: number;
+  last_used_at: string | null;
+  deleted_at: string | null;
+}
+
+export interface ClipRevision {
+  id: number;
+  clip_id: number;
+  content: string;
+  replaced_at: string;
+}
+
+// Selects the clips of a bulk operation: explicit IDs or a search query
+export type ClipSelection = { ids: number[] } | { query: string };
+
+export type ClipOrder = "recent" | "frecency";
+
+export interface ClipCursor {
+  last_copied_at: string;
+  id: number;
+}
+
+export interface ClipPage {
+  clips: Clip[];
+  next_cursor: ClipCursor | null;
+  total: number;
+}
+
+export interface ClipSearchResult {
+  clip: Clip;
+  snippet: string;
+  score: number;
+}
+
+export interface EncryptionStatus {
+  enabled: boolean;
+  locked: boolean;
+}
+
+export interface TagCount {
+  name: string;
+  count: number;
+}
+
+export interface Collection {
+  id: number;
+  name: string;
+  clip_count: number;
+  created_at: string;
+}
+
+export type StatsInterval = "day" | "hour";
+
+export interface AppCount {
+  name: string;
+  count: number;
 }
+
+export interface PeriodCount {
+  // Local `YYYY-MM-DD`, or `YYYY-MM-DDTHH:00` for hourly stats
+  period: string;
+  count: number;
+}
+
+export interface RepeatedContent {
+  clip_id: number;
+  content: string;
+  copy_count: number;
+}
+
+export interface ClipStats {
+  from: string | null;
+  to: string | null;
+  total_clips: number;
+  pinned_clips: number;
+  total_bytes: number;
+  average_bytes: number;
+  database_bytes: number;
+  by_app: AppCount[];
+  by_tag: TagCount[];
+  by_period: PeriodCount[];
+  top_repeated: RepeatedContent[];
+}
+
+export type ExportFormat = "json" | "csv" | "markdown";
+
+export interface ExportSummary {
+  count: number;
+  path: string;
+}
+
+export type ImportSource =
+  | "clipcontex"
+  | "copyq"
+  | "klipper"
+  | "clipboardIndicator"
+  | "gpaste";
+
+export interface ImportReport {
+  imported: number;
+  skipped: number;
+  failed: number;
+}
+
+export interface BackupInfo {
+  file_name: string;
+  created_at: string;
+  size_bytes: number;
+  compressed: boolean;
+}
+
+export type StorageStatus =
+  | { state: "healthy" }
+  | {
+      state: "recovered";
+      quarantined_path: string;
+      salvaged: number;
+      lost: number;
+    }
+  | { state: "degraded"; reason: string };